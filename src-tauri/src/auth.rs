@@ -0,0 +1,125 @@
+//! 再認証の検証
+//!
+//! Supabase Auth のユーザー取得API（`/auth/v1/user`）でアクセストークンを検証し、
+//! トークンの持ち主を `VerifiedUser` として返す。
+//! 封印したプロファイルを開くなど、再認証が必要な操作は `VerifiedUser` を受け取るため、
+//! フロントエンドから渡されたユーザーIDだけでは実行できない。
+//!
+//! 接続先はビルド時（無ければ実行時）の環境変数
+//! `NEXT_PUBLIC_SUPABASE_URL` / `NEXT_PUBLIC_SUPABASE_ANON_KEY` から読む。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/lib.rs
+//!   ├─ src-tauri/src/db/sign_out.rs
+//!   └─ src-tauri/src/commands/profiles_commands.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// 再認証のエラー
+#[derive(Debug, Error)]
+pub enum AuthError {
+    /// 接続先が設定されていない
+    #[error("Authentication is not configured")]
+    NotConfigured,
+
+    /// トークンが無効
+    #[error("Access token was rejected: {0}")]
+    Rejected(String),
+
+    /// 通信エラー
+    #[error("Authentication request failed: {0}")]
+    Request(String),
+}
+
+/// Supabase Auth の接続先
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    pub supabase_url: String,
+    pub anon_key: String,
+}
+
+impl AuthConfig {
+    /// 環境変数から読む
+    pub fn from_env() -> Result<Self, AuthError> {
+        let read = |name: &str, built: Option<&'static str>| {
+            built
+                .map(str::to_string)
+                .or_else(|| std::env::var(name).ok())
+                .filter(|value| !value.is_empty())
+                .ok_or(AuthError::NotConfigured)
+        };
+        Ok(Self {
+            supabase_url: read(
+                "NEXT_PUBLIC_SUPABASE_URL",
+                option_env!("NEXT_PUBLIC_SUPABASE_URL"),
+            )?,
+            anon_key: read(
+                "NEXT_PUBLIC_SUPABASE_ANON_KEY",
+                option_env!("NEXT_PUBLIC_SUPABASE_ANON_KEY"),
+            )?,
+        })
+    }
+}
+
+/// アクセストークンの検証に成功したユーザー（`verify_access_token` でのみ作られる）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedUser {
+    user_id: String,
+}
+
+impl VerifiedUser {
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    #[cfg(test)]
+    pub(crate) fn for_test(user_id: &str) -> Self {
+        Self {
+            user_id: user_id.to_string(),
+        }
+    }
+}
+
+/// `/auth/v1/user` の応答（必要な項目のみ）
+#[derive(Deserialize)]
+struct AuthUser {
+    id: String,
+}
+
+/// アクセストークンを検証する
+pub async fn verify_access_token(
+    config: &AuthConfig,
+    access_token: &str,
+) -> Result<VerifiedUser, AuthError> {
+    if access_token.is_empty() {
+        return Err(AuthError::Rejected("empty token".to_string()));
+    }
+
+    let url = format!("{}/auth/v1/user", config.supabase_url.trim_end_matches('/'));
+    let response = reqwest::Client::new()
+        .get(url)
+        .header("apikey", &config.anon_key)
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .map_err(|e| AuthError::Request(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(AuthError::Rejected(response.status().to_string()));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| AuthError::Request(e.to_string()))?;
+    let user: AuthUser =
+        serde_json::from_str(&body).map_err(|e| AuthError::Rejected(e.to_string()))?;
+    if user.id.is_empty() {
+        return Err(AuthError::Rejected("missing user id".to_string()));
+    }
+    Ok(VerifiedUser { user_id: user.id })
+}
@@ -0,0 +1,102 @@
+//! Card Notes Tauri Commands
+//!
+//! カードノート（穴埋め・表裏のカードなど、兄弟カードを生成する元のテキスト）のTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/card_notes.rs (LocalDB)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::{
+    CardNoteKind, CardNoteUpdate, CardNoteWithCards, LocalCard, LocalCardNote, LocalDB,
+};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// IDでカードノートを取得
+#[tauri::command]
+pub async fn get_card_note(db: State<'_, LocalDB>, id: String) -> CmdResult<Option<LocalCardNote>> {
+    db.get_card_note(&id).map_err(|e| e.to_string())
+}
+
+/// デッキのカードノートを取得
+#[tauri::command]
+pub async fn get_card_notes(
+    db: State<'_, LocalDB>,
+    deck_id: String,
+) -> CmdResult<Vec<LocalCardNote>> {
+    db.get_card_notes_by_deck(&deck_id)
+        .map_err(|e| e.to_string())
+}
+
+/// カードノートの兄弟カードを取得
+#[tauri::command]
+pub async fn get_card_note_cards(
+    db: State<'_, LocalDB>,
+    card_note_id: String,
+) -> CmdResult<Vec<LocalCard>> {
+    db.get_card_note_cards(&card_note_id)
+        .map_err(|e| e.to_string())
+}
+
+/// カードノートを作成（兄弟カードを生成する）
+#[tauri::command]
+pub async fn create_card_note(
+    db: State<'_, LocalDB>,
+    deck_id: String,
+    user_id: String,
+    kind: CardNoteKind,
+    source_text: String,
+    back_text: Option<String>,
+    extra: Option<String>,
+) -> CmdResult<CardNoteWithCards> {
+    db.create_card_note(
+        &deck_id,
+        &user_id,
+        kind,
+        &source_text,
+        back_text.as_deref(),
+        extra.as_deref(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// カードノートを更新（兄弟カードを作り直す）
+#[tauri::command]
+pub async fn update_card_note(
+    db: State<'_, LocalDB>,
+    id: String,
+    updates: CardNoteUpdate,
+) -> CmdResult<Option<CardNoteWithCards>> {
+    db.update_card_note(&id, updates).map_err(|e| e.to_string())
+}
+
+/// カードノートと兄弟カードを削除（論理削除）
+#[tauri::command]
+pub async fn delete_card_note(db: State<'_, LocalDB>, id: String) -> CmdResult<bool> {
+    db.delete_card_note(&id).map_err(|e| e.to_string())
+}
+
+/// 同期待ちのカードノートを取得
+#[tauri::command]
+pub async fn get_pending_sync_card_notes(db: State<'_, LocalDB>) -> CmdResult<Vec<LocalCardNote>> {
+    db.get_pending_sync_card_notes().map_err(|e| e.to_string())
+}
+
+/// カードノートを同期済みにマーク
+#[tauri::command]
+pub async fn mark_card_note_synced(
+    db: State<'_, LocalDB>,
+    id: String,
+    server_updated_at: String,
+) -> CmdResult<()> {
+    db.mark_card_note_synced(&id, &server_updated_at)
+        .map_err(|e| e.to_string())
+}
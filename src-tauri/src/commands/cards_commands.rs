@@ -8,13 +8,15 @@
 //!   └─ src-tauri/src/commands/mod.rs
 //!
 //! Dependencies:
-//!   └─ src-tauri/src/db/mod.rs (LocalDB)
+//!   ├─ src-tauri/src/db/mod.rs (LocalDB)
+//!   ├─ src-tauri/src/db/review.rs (復習のスケジュール)
+//!   └─ src-tauri/src/db/card_status.rs (一時停止・延期・フラグ)
 //!
 //! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
 //! Issue: https://github.com/otomatty/for-all-learners/issues/192
 
-use crate::db::models::LocalCard;
-use crate::db::LocalDB;
+use crate::db::models::{CardUpdate, LocalCard};
+use crate::db::{LocalDB, ReviewResult};
 use tauri::State;
 
 /// エラー型のエイリアス
@@ -38,6 +40,16 @@ pub async fn create_card(db: State<'_, LocalDB>, card: LocalCard) -> CmdResult<(
     db.insert_card(&card).map_err(|e| e.to_string())
 }
 
+/// カードを更新
+#[tauri::command]
+pub async fn update_card(
+    db: State<'_, LocalDB>,
+    id: String,
+    updates: CardUpdate,
+) -> CmdResult<Option<LocalCard>> {
+    db.update_card(&id, updates).map_err(|e| e.to_string())
+}
+
 /// カードを削除（論理削除）
 #[tauri::command]
 pub async fn delete_card(db: State<'_, LocalDB>, id: String) -> CmdResult<bool> {
@@ -50,6 +62,53 @@ pub async fn get_due_cards(db: State<'_, LocalDB>, user_id: String) -> CmdResult
     db.get_due_cards(&user_id).map_err(|e| e.to_string())
 }
 
+/// カードを復習（デッキの学習オプションで次回の復習を決め、学習ログを残す）
+#[tauri::command]
+pub async fn review_card(
+    db: State<'_, LocalDB>,
+    id: String,
+    quality: i32,
+    practice_mode: String,
+) -> CmdResult<ReviewResult> {
+    db.review_card(&id, quality, &practice_mode)
+        .map_err(|e| e.to_string())
+}
+
+/// カードを一時停止する（変更したカードの数を返す）
+#[tauri::command]
+pub async fn suspend_cards(db: State<'_, LocalDB>, ids: Vec<String>) -> CmdResult<usize> {
+    db.suspend_cards(&ids).map_err(|e| e.to_string())
+}
+
+/// カードの一時停止を解除する
+#[tauri::command]
+pub async fn unsuspend_cards(db: State<'_, LocalDB>, ids: Vec<String>) -> CmdResult<usize> {
+    db.unsuspend_cards(&ids).map_err(|e| e.to_string())
+}
+
+/// カードを次の学習日まで延期する
+#[tauri::command]
+pub async fn bury_cards(db: State<'_, LocalDB>, ids: Vec<String>) -> CmdResult<usize> {
+    db.bury_cards(&ids).map_err(|e| e.to_string())
+}
+
+/// カードの延期を取り消す
+#[tauri::command]
+pub async fn unbury_cards(db: State<'_, LocalDB>, ids: Vec<String>) -> CmdResult<usize> {
+    db.unbury_cards(&ids).map_err(|e| e.to_string())
+}
+
+/// カードのフラグを変更する（None で外す）
+#[tauri::command]
+pub async fn set_card_flags(
+    db: State<'_, LocalDB>,
+    ids: Vec<String>,
+    flag: Option<String>,
+) -> CmdResult<usize> {
+    db.set_card_flags(&ids, flag.as_deref())
+        .map_err(|e| e.to_string())
+}
+
 /// 同期待ちカードを取得
 #[tauri::command]
 pub async fn get_pending_sync_cards(db: State<'_, LocalDB>) -> CmdResult<Vec<LocalCard>> {
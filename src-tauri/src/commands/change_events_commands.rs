@@ -0,0 +1,38 @@
+//! Change Events Tauri Commands
+//!
+//! `db://changed` イベントに合わせて再実行するクエリの購読コマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/change_events.rs (LocalDB, QuerySubscription)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::{LocalDB, QuerySubscription};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// クエリを購読（参照テーブルに変更があると invalidated_queries に含まれる）
+#[tauri::command]
+pub async fn subscribe_query(
+    db: State<'_, LocalDB>,
+    query_key: String,
+    tables: Vec<String>,
+    ids: Option<Vec<String>>,
+) -> CmdResult<QuerySubscription> {
+    db.subscribe_query(&query_key, tables, ids)
+        .map_err(|e| e.to_string())
+}
+
+/// クエリの購読を解除
+#[tauri::command]
+pub async fn unsubscribe_query(db: State<'_, LocalDB>, subscription_id: String) -> CmdResult<bool> {
+    db.unsubscribe_query(&subscription_id)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,36 @@
+//! Content Tauri Commands
+//!
+//! ページ本文（Tiptapドキュメント）の変換のTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/content/
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::content::{self, TiptapNode};
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// Tiptap ドキュメントを Markdown に変換
+#[tauri::command]
+pub async fn convert_tiptap_to_markdown(doc: TiptapNode) -> CmdResult<String> {
+    Ok(content::tiptap_to_markdown(&doc))
+}
+
+/// Tiptap ドキュメントをプレーンテキストに変換
+#[tauri::command]
+pub async fn convert_tiptap_to_plain_text(doc: TiptapNode) -> CmdResult<String> {
+    Ok(content::tiptap_to_plain_text(&doc))
+}
+
+/// Markdown を Tiptap ドキュメントに変換
+#[tauri::command]
+pub async fn convert_markdown_to_tiptap(markdown: String) -> CmdResult<TiptapNode> {
+    Ok(content::markdown_to_tiptap(&markdown))
+}
@@ -0,0 +1,108 @@
+//! Deck Configs Tauri Commands
+//!
+//! デッキの学習オプション（プリセット）のTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/deck_configs.rs (LocalDB)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::models::{DeckConfigUpdate, LocalDeck, LocalDeckConfig};
+use crate::db::{DeckStudyOptions, LocalDB};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// ユーザーのプリセットを取得
+#[tauri::command]
+pub async fn get_deck_configs(
+    db: State<'_, LocalDB>,
+    user_id: String,
+) -> CmdResult<Vec<LocalDeckConfig>> {
+    db.get_deck_configs(&user_id).map_err(|e| e.to_string())
+}
+
+/// IDでプリセットを取得
+#[tauri::command]
+pub async fn get_deck_config(
+    db: State<'_, LocalDB>,
+    id: String,
+) -> CmdResult<Option<LocalDeckConfig>> {
+    db.get_deck_config(&id).map_err(|e| e.to_string())
+}
+
+/// プリセットを作成
+#[tauri::command]
+pub async fn create_deck_config(db: State<'_, LocalDB>, config: LocalDeckConfig) -> CmdResult<()> {
+    db.insert_deck_config(&config).map_err(|e| e.to_string())
+}
+
+/// プリセットを更新
+#[tauri::command]
+pub async fn update_deck_config(
+    db: State<'_, LocalDB>,
+    id: String,
+    updates: DeckConfigUpdate,
+) -> CmdResult<Option<LocalDeckConfig>> {
+    db.update_deck_config(&id, updates)
+        .map_err(|e| e.to_string())
+}
+
+/// プリセットを削除（論理削除）
+#[tauri::command]
+pub async fn delete_deck_config(db: State<'_, LocalDB>, id: String) -> CmdResult<bool> {
+    db.delete_deck_config(&id).map_err(|e| e.to_string())
+}
+
+/// デッキにプリセットを割り当て（None で既定値に戻す）
+#[tauri::command]
+pub async fn set_deck_config(
+    db: State<'_, LocalDB>,
+    deck_id: String,
+    config_id: Option<String>,
+) -> CmdResult<LocalDeck> {
+    db.set_deck_config(&deck_id, config_id.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// デッキの学習オプションを取得（プリセットが無ければ既定値）
+#[tauri::command]
+pub async fn get_deck_study_options(
+    db: State<'_, LocalDB>,
+    deck_id: String,
+) -> CmdResult<DeckStudyOptions> {
+    db.get_deck_study_options(&deck_id)
+        .map_err(|e| e.to_string())
+}
+
+/// 同期待ちのプリセットを取得
+#[tauri::command]
+pub async fn get_pending_sync_deck_configs(
+    db: State<'_, LocalDB>,
+) -> CmdResult<Vec<LocalDeckConfig>> {
+    db.get_pending_sync_deck_configs()
+        .map_err(|e| e.to_string())
+}
+
+/// 削除済みのプリセットを取得
+#[tauri::command]
+pub async fn get_deleted_deck_configs(db: State<'_, LocalDB>) -> CmdResult<Vec<LocalDeckConfig>> {
+    db.get_deleted_deck_configs().map_err(|e| e.to_string())
+}
+
+/// プリセットを同期済みにマーク
+#[tauri::command]
+pub async fn mark_deck_config_synced(
+    db: State<'_, LocalDB>,
+    id: String,
+    server_updated_at: String,
+) -> CmdResult<()> {
+    db.mark_deck_config_synced(&id, &server_updated_at)
+        .map_err(|e| e.to_string())
+}
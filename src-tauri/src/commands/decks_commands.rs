@@ -8,13 +8,14 @@
 //!   └─ src-tauri/src/commands/mod.rs
 //!
 //! Dependencies:
-//!   └─ src-tauri/src/db/mod.rs (LocalDB)
+//!   ├─ src-tauri/src/db/mod.rs (LocalDB)
+//!   └─ src-tauri/src/db/deck_tree.rs (デッキの階層)
 //!
 //! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
 //! Issue: https://github.com/otomatty/for-all-learners/issues/192
 
-use crate::db::models::LocalDeck;
-use crate::db::LocalDB;
+use crate::db::models::{LocalCard, LocalDeck};
+use crate::db::{DeckTreeNode, LocalDB};
 use tauri::State;
 
 /// エラー型のエイリアス
@@ -44,6 +45,32 @@ pub async fn delete_deck(db: State<'_, LocalDB>, id: String) -> CmdResult<bool>
     db.delete_deck(&id).map_err(|e| e.to_string())
 }
 
+/// ユーザーのデッキを階層で取得（件数は子孫のデッキを含む）
+#[tauri::command]
+pub async fn get_deck_tree(
+    db: State<'_, LocalDB>,
+    user_id: String,
+) -> CmdResult<Vec<DeckTreeNode>> {
+    db.get_deck_tree(&user_id).map_err(|e| e.to_string())
+}
+
+/// デッキを別のデッキの下に移動（`parent_deck_id` が None ならルートへ）
+#[tauri::command]
+pub async fn move_deck(
+    db: State<'_, LocalDB>,
+    id: String,
+    parent_deck_id: Option<String>,
+) -> CmdResult<LocalDeck> {
+    db.move_deck(&id, parent_deck_id.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// デッキを学習するときのカードを取得（子孫のデッキを含む）
+#[tauri::command]
+pub async fn get_study_cards(db: State<'_, LocalDB>, deck_id: String) -> CmdResult<Vec<LocalCard>> {
+    db.get_study_cards(&deck_id).map_err(|e| e.to_string())
+}
+
 /// 同期待ちデッキを取得
 #[tauri::command]
 pub async fn get_pending_sync_decks(db: State<'_, LocalDB>) -> CmdResult<Vec<LocalDeck>> {
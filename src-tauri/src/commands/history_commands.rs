@@ -0,0 +1,39 @@
+//! History Tauri Commands
+//!
+//! 変更履歴の取得・復元のTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/history.rs (LocalDB)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::models::LocalRevision;
+use crate::db::{HistoryEntity, LocalDB, RestoredEntity};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// 変更履歴を取得（entity: "note" | "page" | "card"）
+#[tauri::command]
+pub async fn get_history(
+    db: State<'_, LocalDB>,
+    entity: HistoryEntity,
+    id: String,
+) -> CmdResult<Vec<LocalRevision>> {
+    db.get_history(entity, &id).map_err(|e| e.to_string())
+}
+
+/// リビジョンの内容に復元
+#[tauri::command]
+pub async fn restore_revision(
+    db: State<'_, LocalDB>,
+    revision_id: String,
+) -> CmdResult<RestoredEntity> {
+    db.restore_revision(&revision_id).map_err(|e| e.to_string())
+}
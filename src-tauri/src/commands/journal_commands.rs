@@ -0,0 +1,37 @@
+//! Journal Tauri Commands
+//!
+//! 操作の元に戻す・やり直すのTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/journal.rs (LocalDB)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::{JournalEntrySummary, LocalDB, UndoState};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// 直前の操作を元に戻す
+#[tauri::command]
+pub async fn undo(db: State<'_, LocalDB>) -> CmdResult<Option<JournalEntrySummary>> {
+    db.undo().map_err(|e| e.to_string())
+}
+
+/// 元に戻した操作をやり直す
+#[tauri::command]
+pub async fn redo(db: State<'_, LocalDB>) -> CmdResult<Option<JournalEntrySummary>> {
+    db.redo().map_err(|e| e.to_string())
+}
+
+/// 元に戻す・やり直すの可否を取得
+#[tauri::command]
+pub async fn get_undo_state(db: State<'_, LocalDB>) -> CmdResult<UndoState> {
+    db.get_undo_state().map_err(|e| e.to_string())
+}
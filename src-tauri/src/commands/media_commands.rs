@@ -0,0 +1,195 @@
+//! Media Tauri Commands
+//!
+//! ローカルメディアストアのTauriコマンド。
+//! リモートのファイルをダウンロードして取り込み、カード・ページから参照する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/media.rs (LocalDB)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::media::parse_media_uri;
+use crate::db::{LocalDB, LocalMedia, MediaEntity, MediaGcResult};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// ダウンロードするファイルの上限サイズ
+const MAX_DOWNLOAD_BYTES: u64 = 50 * 1024 * 1024;
+
+/// リモートのファイルを取得する（内容, Content-Type）
+pub(crate) async fn fetch_media(url: &str) -> CmdResult<(Vec<u8>, Option<String>)> {
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err(format!("Unsupported media URL: {}", url));
+    }
+
+    let response = reqwest::get(url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?;
+    if response
+        .content_length()
+        .is_some_and(|length| length > MAX_DOWNLOAD_BYTES)
+    {
+        return Err(format!("Media is too large: {}", url));
+    }
+
+    let mime_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(';').next().unwrap_or(value).trim().to_string());
+    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+    if bytes.len() as u64 > MAX_DOWNLOAD_BYTES {
+        return Err(format!("Media is too large: {}", url));
+    }
+    Ok((bytes.to_vec(), mime_type))
+}
+
+/// カード・ページのメディアフィールドの値を取得（フィールド名, URL）
+fn entity_media_urls(
+    db: &LocalDB,
+    entity: MediaEntity,
+    entity_id: &str,
+) -> CmdResult<Vec<(&'static str, Option<String>)>> {
+    match entity {
+        MediaEntity::Card => {
+            let card = db
+                .get_card_by_id(entity_id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Card not found: {}", entity_id))?;
+            Ok(vec![
+                ("source_audio_url", card.source_audio_url),
+                ("source_ocr_image_url", card.source_ocr_image_url),
+            ])
+        }
+        MediaEntity::Page => {
+            let page = db
+                .get_page_by_id(entity_id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Page not found: {}", entity_id))?;
+            Ok(vec![("thumbnail_url", page.thumbnail_url)])
+        }
+    }
+}
+
+/// URLのファイルをダウンロードして取り込む
+#[tauri::command]
+pub async fn download_media(db: State<'_, LocalDB>, url: String) -> CmdResult<LocalMedia> {
+    let (bytes, mime_type) = fetch_media(&url).await?;
+    db.ingest_media(&bytes, mime_type.as_deref(), Some(&url))
+        .map_err(|e| e.to_string())
+}
+
+/// ファイルの内容を取り込む（録音・撮影した画像など）
+#[tauri::command]
+pub async fn ingest_media(
+    db: State<'_, LocalDB>,
+    bytes: Vec<u8>,
+    mime_type: Option<String>,
+    source_url: Option<String>,
+) -> CmdResult<LocalMedia> {
+    db.ingest_media(&bytes, mime_type.as_deref(), source_url.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// カード・ページのフィールドからメディアを参照する
+#[tauri::command]
+pub async fn attach_media(
+    db: State<'_, LocalDB>,
+    hash: String,
+    entity_type: MediaEntity,
+    entity_id: String,
+    field: String,
+) -> CmdResult<()> {
+    db.attach_media(&hash, entity_type, &entity_id, &field)
+        .map_err(|e| e.to_string())
+}
+
+/// カード・ページからの参照を外す
+#[tauri::command]
+pub async fn detach_media(
+    db: State<'_, LocalDB>,
+    entity_type: MediaEntity,
+    entity_id: String,
+    field: Option<String>,
+) -> CmdResult<usize> {
+    db.detach_media(entity_type, &entity_id, field.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// カード・ページのメディアをダウンロードして参照を登録する
+///
+/// 取り込み済みのURLはダウンロードしない。取得に失敗したフィールドは
+/// ログに残して飛ばす（オンラインに戻ったら再度呼び出す）。
+#[tauri::command]
+pub async fn cache_entity_media(
+    db: State<'_, LocalDB>,
+    entity_type: MediaEntity,
+    entity_id: String,
+) -> CmdResult<Vec<LocalMedia>> {
+    let mut cached = Vec::new();
+    for (field, url) in entity_media_urls(&db, entity_type, &entity_id)? {
+        let Some(url) = url.filter(|url| !url.is_empty()) else {
+            db.detach_media(entity_type, &entity_id, Some(field))
+                .map_err(|e| e.to_string())?;
+            continue;
+        };
+
+        // ローカルのURI（生成したサムネイルなど）はそのメディア、リモートURLは取り込み済みのものを使う
+        let existing = match parse_media_uri(&url) {
+            Some(hash) => db.get_media(hash),
+            None => db.find_media_by_source_url(&url),
+        }
+        .map_err(|e| e.to_string())?;
+        let media = match existing {
+            Some(media) => Some(media),
+            None => match fetch_media(&url).await {
+                Ok((bytes, mime_type)) => Some(
+                    db.ingest_media(&bytes, mime_type.as_deref(), Some(&url))
+                        .map_err(|e| e.to_string())?,
+                ),
+                Err(err) => {
+                    log::warn!("Failed to download media {}: {}", url, err);
+                    None
+                }
+            },
+        };
+
+        if let Some(media) = media {
+            db.attach_media(&media.hash, entity_type, &entity_id, field)
+                .map_err(|e| e.to_string())?;
+            cached.push(media);
+        }
+    }
+    Ok(cached)
+}
+
+/// カード・ページが参照しているメディアを取得（フィールド名, メディア）
+#[tauri::command]
+pub async fn get_entity_media(
+    db: State<'_, LocalDB>,
+    entity_type: MediaEntity,
+    entity_id: String,
+) -> CmdResult<Vec<(String, LocalMedia)>> {
+    db.get_entity_media(entity_type, &entity_id)
+        .map_err(|e| e.to_string())
+}
+
+/// リモートURLを取り込み済みメディアのURIに解決する
+#[tauri::command]
+pub async fn resolve_media_url(db: State<'_, LocalDB>, url: String) -> CmdResult<Option<String>> {
+    db.resolve_media_url(&url).map_err(|e| e.to_string())
+}
+
+/// 参照されなくなったメディアを削除する
+#[tauri::command]
+pub async fn collect_media_garbage(db: State<'_, LocalDB>) -> CmdResult<MediaGcResult> {
+    db.collect_media_garbage().map_err(|e| e.to_string())
+}
@@ -9,32 +9,77 @@
 //!
 //! Children (Submodules):
 //!   ├─ notes_commands.rs
+//!   ├─ note_pages_commands.rs
+//!   ├─ note_members_commands.rs
 //!   ├─ pages_commands.rs
 //!   ├─ decks_commands.rs
+//!   ├─ deck_configs_commands.rs
 //!   ├─ cards_commands.rs
+//!   ├─ card_notes_commands.rs
+//!   ├─ questions_commands.rs
 //!   ├─ study_goals_commands.rs
 //!   ├─ learning_logs_commands.rs
-//!   └─ milestones_commands.rs
+//!   ├─ milestones_commands.rs
+//!   ├─ profiles_commands.rs
+//!   ├─ content_commands.rs
+//!   ├─ change_events_commands.rs
+//!   ├─ history_commands.rs
+//!   ├─ journal_commands.rs
+//!   ├─ media_commands.rs
+//!   ├─ page_links_commands.rs
+//!   ├─ thumbnail_commands.rs
+//!   ├─ tags_commands.rs
+//!   └─ page_documents_commands.rs
 //!
 //! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
 //! Issue: https://github.com/otomatty/for-all-learners/issues/192
 
+pub mod card_notes_commands;
 pub mod cards_commands;
+pub mod change_events_commands;
+pub mod content_commands;
+pub mod deck_configs_commands;
 pub mod decks_commands;
+pub mod history_commands;
+pub mod journal_commands;
 pub mod learning_logs_commands;
+pub mod media_commands;
 pub mod milestones_commands;
+pub mod note_members_commands;
+pub mod note_pages_commands;
 pub mod notes_commands;
+pub mod page_documents_commands;
+pub mod page_links_commands;
 pub mod pages_commands;
+pub mod profiles_commands;
+pub mod questions_commands;
 pub mod study_goals_commands;
+pub mod tags_commands;
+pub mod thumbnail_commands;
 pub mod user_settings_commands;
 
 // 各モジュールからコマンドを再エクスポート
+pub use card_notes_commands::*;
 pub use cards_commands::*;
+pub use change_events_commands::*;
+pub use content_commands::*;
+pub use deck_configs_commands::*;
 pub use decks_commands::*;
+pub use history_commands::*;
+pub use journal_commands::*;
 pub use learning_logs_commands::*;
+pub use media_commands::*;
 pub use milestones_commands::*;
+pub use note_members_commands::*;
+pub use note_pages_commands::*;
 pub use notes_commands::*;
+pub use page_documents_commands::*;
+pub use page_links_commands::*;
 pub use pages_commands::*;
+pub use profiles_commands::*;
+pub use questions_commands::*;
 pub use study_goals_commands::*;
+pub use tags_commands::*;
+pub use thumbnail_commands::*;
 pub use user_settings_commands::*;
 
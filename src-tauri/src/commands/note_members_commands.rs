@@ -0,0 +1,160 @@
+//! Note Members Tauri Commands
+//!
+//! ノートの共有（メンバーの招待・共有リンク）のTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/note_members.rs (LocalDB)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::{LocalDB, LocalNoteMember, LocalNoteShareLink, NoteRole, SharedNote};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// ノートのメンバーを取得
+#[tauri::command]
+pub async fn get_note_members(
+    db: State<'_, LocalDB>,
+    note_id: String,
+) -> CmdResult<Vec<LocalNoteMember>> {
+    db.get_note_members(&note_id).map_err(|e| e.to_string())
+}
+
+/// 自分に共有されたノートを取得
+#[tauri::command]
+pub async fn get_shared_notes(
+    db: State<'_, LocalDB>,
+    user_id: String,
+) -> CmdResult<Vec<SharedNote>> {
+    db.get_shared_notes(&user_id).map_err(|e| e.to_string())
+}
+
+/// ユーザーのノートに対する権限を取得
+#[tauri::command]
+pub async fn get_note_role(
+    db: State<'_, LocalDB>,
+    note_id: String,
+    user_id: String,
+) -> CmdResult<Option<NoteRole>> {
+    db.get_note_role(&note_id, &user_id)
+        .map_err(|e| e.to_string())
+}
+
+/// ユーザーをノートに招待
+#[tauri::command]
+pub async fn invite_note_member(
+    db: State<'_, LocalDB>,
+    note_id: String,
+    user_id: String,
+    role: NoteRole,
+) -> CmdResult<LocalNoteMember> {
+    db.invite_note_member(&note_id, &user_id, role)
+        .map_err(|e| e.to_string())
+}
+
+/// 招待を受ける
+#[tauri::command]
+pub async fn accept_note_invitation(
+    db: State<'_, LocalDB>,
+    note_id: String,
+    user_id: String,
+) -> CmdResult<LocalNoteMember> {
+    db.accept_note_invitation(&note_id, &user_id)
+        .map_err(|e| e.to_string())
+}
+
+/// メンバーを外す
+#[tauri::command]
+pub async fn remove_note_member(
+    db: State<'_, LocalDB>,
+    note_id: String,
+    user_id: String,
+) -> CmdResult<bool> {
+    db.remove_note_member(&note_id, &user_id)
+        .map_err(|e| e.to_string())
+}
+
+/// 共有リンクを作成
+#[tauri::command]
+pub async fn create_note_share_link(
+    db: State<'_, LocalDB>,
+    note_id: String,
+    role: NoteRole,
+    expires_at: Option<String>,
+) -> CmdResult<LocalNoteShareLink> {
+    db.create_note_share_link(&note_id, role, expires_at.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// ノートの共有リンクを取得
+#[tauri::command]
+pub async fn get_note_share_links(
+    db: State<'_, LocalDB>,
+    note_id: String,
+) -> CmdResult<Vec<LocalNoteShareLink>> {
+    db.get_note_share_links(&note_id).map_err(|e| e.to_string())
+}
+
+/// 共有リンクを取り消す
+#[tauri::command]
+pub async fn revoke_note_share_link(db: State<'_, LocalDB>, id: String) -> CmdResult<bool> {
+    db.revoke_note_share_link(&id).map_err(|e| e.to_string())
+}
+
+/// 共有リンクでノートに参加
+#[tauri::command]
+pub async fn join_note_by_share_link(
+    db: State<'_, LocalDB>,
+    token: String,
+    user_id: String,
+) -> CmdResult<LocalNoteMember> {
+    db.join_note_by_share_link(&token, &user_id)
+        .map_err(|e| e.to_string())
+}
+
+/// 同期待ちのメンバーを取得
+#[tauri::command]
+pub async fn get_pending_sync_note_members(
+    db: State<'_, LocalDB>,
+) -> CmdResult<Vec<LocalNoteMember>> {
+    db.get_pending_sync_note_members()
+        .map_err(|e| e.to_string())
+}
+
+/// 同期待ちの共有リンクを取得
+#[tauri::command]
+pub async fn get_pending_sync_note_share_links(
+    db: State<'_, LocalDB>,
+) -> CmdResult<Vec<LocalNoteShareLink>> {
+    db.get_pending_sync_note_share_links()
+        .map_err(|e| e.to_string())
+}
+
+/// メンバーを同期済みにマーク
+#[tauri::command]
+pub async fn mark_note_member_synced(
+    db: State<'_, LocalDB>,
+    id: String,
+    server_updated_at: String,
+) -> CmdResult<()> {
+    db.mark_note_member_synced(&id, &server_updated_at)
+        .map_err(|e| e.to_string())
+}
+
+/// 共有リンクを同期済みにマーク
+#[tauri::command]
+pub async fn mark_note_share_link_synced(
+    db: State<'_, LocalDB>,
+    id: String,
+    server_updated_at: String,
+) -> CmdResult<()> {
+    db.mark_note_share_link_synced(&id, &server_updated_at)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,88 @@
+//! Note Pages Tauri Commands
+//!
+//! ノートとページの対応（ページの追加・削除・並べ替え）のTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/note_pages.rs (LocalDB)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::{LocalDB, LocalNote, LocalNotePage};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// ノート内の対応を並び順で取得
+#[tauri::command]
+pub async fn get_note_pages(
+    db: State<'_, LocalDB>,
+    note_id: String,
+) -> CmdResult<Vec<LocalNotePage>> {
+    db.get_note_pages(&note_id).map_err(|e| e.to_string())
+}
+
+/// ページが入っているノートを取得
+#[tauri::command]
+pub async fn get_notes_by_page(
+    db: State<'_, LocalDB>,
+    page_id: String,
+) -> CmdResult<Vec<LocalNote>> {
+    db.get_notes_by_page(&page_id).map_err(|e| e.to_string())
+}
+
+/// ノートにページを入れる
+#[tauri::command]
+pub async fn link_page_to_note(
+    db: State<'_, LocalDB>,
+    note_id: String,
+    page_id: String,
+    position: Option<i64>,
+) -> CmdResult<LocalNotePage> {
+    db.link_page_to_note(&note_id, &page_id, position)
+        .map_err(|e| e.to_string())
+}
+
+/// ノートからページを外す
+#[tauri::command]
+pub async fn unlink_page_from_note(
+    db: State<'_, LocalDB>,
+    note_id: String,
+    page_id: String,
+) -> CmdResult<bool> {
+    db.unlink_page_from_note(&note_id, &page_id)
+        .map_err(|e| e.to_string())
+}
+
+/// ノート内のページを並べ替える
+#[tauri::command]
+pub async fn reorder_note_pages(
+    db: State<'_, LocalDB>,
+    note_id: String,
+    page_ids: Vec<String>,
+) -> CmdResult<Vec<LocalNotePage>> {
+    db.reorder_note_pages(&note_id, &page_ids)
+        .map_err(|e| e.to_string())
+}
+
+/// 同期待ちの対応を取得
+#[tauri::command]
+pub async fn get_pending_sync_note_pages(db: State<'_, LocalDB>) -> CmdResult<Vec<LocalNotePage>> {
+    db.get_pending_sync_note_pages().map_err(|e| e.to_string())
+}
+
+/// 対応を同期済みにマーク
+#[tauri::command]
+pub async fn mark_note_page_synced(
+    db: State<'_, LocalDB>,
+    id: String,
+    server_updated_at: String,
+) -> CmdResult<()> {
+    db.mark_note_page_synced(&id, &server_updated_at)
+        .map_err(|e| e.to_string())
+}
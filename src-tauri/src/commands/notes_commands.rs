@@ -33,9 +33,9 @@ pub async fn get_note(db: State<'_, LocalDB>, id: String) -> CmdResult<Option<Lo
     db.get_note_by_id(&id).map_err(|e| e.to_string())
 }
 
-/// ノートを作成
+/// ノートを作成（スラッグを確定したノートを返す）
 #[tauri::command]
-pub async fn create_note(db: State<'_, LocalDB>, note: LocalNote) -> CmdResult<()> {
+pub async fn create_note(db: State<'_, LocalDB>, note: LocalNote) -> CmdResult<LocalNote> {
     db.insert_note(&note).map_err(|e| e.to_string())
 }
 
@@ -61,6 +61,39 @@ pub async fn hard_delete_note(db: State<'_, LocalDB>, id: String) -> CmdResult<(
     db.hard_delete_note(&id).map_err(|e| e.to_string())
 }
 
+/// タイトルからスラッグを提案
+#[tauri::command]
+pub async fn suggest_note_slug(
+    db: State<'_, LocalDB>,
+    owner_id: String,
+    title: String,
+) -> CmdResult<String> {
+    db.suggest_note_slug(&owner_id, &title)
+        .map_err(|e| e.to_string())
+}
+
+/// ノートのスラッグを変更（旧スラッグはリダイレクトとして残る）
+#[tauri::command]
+pub async fn change_note_slug(
+    db: State<'_, LocalDB>,
+    id: String,
+    slug: Option<String>,
+) -> CmdResult<LocalNote> {
+    db.change_note_slug(&id, slug.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// スラッグからノートを取得（旧スラッグのリダイレクトもたどる）
+#[tauri::command]
+pub async fn resolve_note_slug(
+    db: State<'_, LocalDB>,
+    owner_id: String,
+    slug: String,
+) -> CmdResult<Option<LocalNote>> {
+    db.resolve_note_slug(&owner_id, &slug)
+        .map_err(|e| e.to_string())
+}
+
 /// 同期待ちノートを取得
 #[tauri::command]
 pub async fn get_pending_sync_notes(db: State<'_, LocalDB>) -> CmdResult<Vec<LocalNote>> {
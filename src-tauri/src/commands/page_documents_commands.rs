@@ -0,0 +1,98 @@
+//! Page Documents Tauri Commands
+//!
+//! ページ本文（Yjsドキュメント）のTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/db/page_documents.rs (LocalDB)
+//!   └─ src-tauri/src/db/page_document_compaction.rs (LocalDB)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::{
+    DocumentUpdateOrigin, LocalDB, PageDocumentCompaction, PageDocumentDiff, PageDocumentState,
+    PageDocumentStats,
+};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// ページ本文を取得
+#[tauri::command]
+pub async fn get_page_document(
+    db: State<'_, LocalDB>,
+    page_id: String,
+) -> CmdResult<PageDocumentState> {
+    db.get_page_document(&page_id).map_err(|e| e.to_string())
+}
+
+/// ページ本文の状態ベクトルを取得
+#[tauri::command]
+pub async fn get_page_state_vector(db: State<'_, LocalDB>, page_id: String) -> CmdResult<Vec<u8>> {
+    db.get_page_state_vector(&page_id)
+        .map_err(|e| e.to_string())
+}
+
+/// ページ本文に更新を適用（origin: "local" | "server"）
+#[tauri::command]
+pub async fn apply_page_update(
+    db: State<'_, LocalDB>,
+    page_id: String,
+    update: Vec<u8>,
+    origin: DocumentUpdateOrigin,
+) -> CmdResult<()> {
+    db.apply_page_update(&page_id, &update, origin)
+        .map_err(|e| e.to_string())
+}
+
+/// サーバーに送る差分を取得
+#[tauri::command]
+pub async fn get_page_document_diff(
+    db: State<'_, LocalDB>,
+    page_id: String,
+    server_state_vector: Option<Vec<u8>>,
+) -> CmdResult<PageDocumentDiff> {
+    db.get_page_document_diff(&page_id, server_state_vector.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// 差分の送信完了を記録
+#[tauri::command]
+pub async fn mark_page_document_synced(
+    db: State<'_, LocalDB>,
+    page_id: String,
+    server_state_vector: Vec<u8>,
+    up_to_update_id: i64,
+) -> CmdResult<()> {
+    db.mark_page_document_synced(&page_id, &server_state_vector, up_to_update_id)
+        .map_err(|e| e.to_string())
+}
+
+/// 未送信の本文があるページIDを取得
+#[tauri::command]
+pub async fn get_pending_page_documents(db: State<'_, LocalDB>) -> CmdResult<Vec<String>> {
+    db.get_pending_page_documents().map_err(|e| e.to_string())
+}
+
+/// 本文の更新ログの統計を取得（page_id を省略すると全ページ）
+#[tauri::command]
+pub async fn get_page_document_stats(
+    db: State<'_, LocalDB>,
+    page_id: Option<String>,
+) -> CmdResult<Vec<PageDocumentStats>> {
+    db.get_page_document_stats(page_id.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// 閾値を超えたページの更新ログを圧縮
+#[tauri::command]
+pub async fn compact_page_documents(
+    db: State<'_, LocalDB>,
+) -> CmdResult<Vec<PageDocumentCompaction>> {
+    db.compact_page_documents().map_err(|e| e.to_string())
+}
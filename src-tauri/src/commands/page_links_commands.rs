@@ -0,0 +1,58 @@
+//! Page Links Tauri Commands
+//!
+//! ページリンク（バックリンク・未作成リンク・2ホップリンク）のTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/page_links.rs (LocalDB)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::{LocalDB, LocalPage, PageLink, TwoHopLink, UnresolvedLink};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// ページへリンクしているページを取得
+#[tauri::command]
+pub async fn get_backlinks(db: State<'_, LocalDB>, page_id: String) -> CmdResult<Vec<LocalPage>> {
+    db.get_backlinks(&page_id).map_err(|e| e.to_string())
+}
+
+/// ページからのリンクを取得
+#[tauri::command]
+pub async fn get_outgoing_links(
+    db: State<'_, LocalDB>,
+    page_id: String,
+) -> CmdResult<Vec<PageLink>> {
+    db.get_outgoing_links(&page_id).map_err(|e| e.to_string())
+}
+
+/// リンク先のページがまだ無いリンクを取得
+#[tauri::command]
+pub async fn get_unresolved_links(
+    db: State<'_, LocalDB>,
+    user_id: String,
+) -> CmdResult<Vec<UnresolvedLink>> {
+    db.get_unresolved_links(&user_id).map_err(|e| e.to_string())
+}
+
+/// 2ホップリンク（関連ページ）を取得
+#[tauri::command]
+pub async fn get_two_hop_links(
+    db: State<'_, LocalDB>,
+    page_id: String,
+) -> CmdResult<Vec<TwoHopLink>> {
+    db.get_two_hop_links(&page_id).map_err(|e| e.to_string())
+}
+
+/// 全ページのリンクを記録し直す
+#[tauri::command]
+pub async fn rebuild_page_links(db: State<'_, LocalDB>) -> CmdResult<usize> {
+    db.rebuild_page_links().map_err(|e| e.to_string())
+}
@@ -8,13 +8,14 @@
 //!   └─ src-tauri/src/commands/mod.rs
 //!
 //! Dependencies:
-//!   └─ src-tauri/src/db/mod.rs (LocalDB)
+//!   ├─ src-tauri/src/db/mod.rs (LocalDB)
+//!   └─ src-tauri/src/db/page_rename.rs
 //!
 //! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
 //! Issue: https://github.com/otomatty/for-all-learners/issues/192
 
 use crate::db::models::{LocalPage, PageUpdate};
-use crate::db::LocalDB;
+use crate::db::{LocalDB, PageRenameResult};
 use tauri::State;
 
 /// エラー型のエイリアス
@@ -57,6 +58,18 @@ pub async fn update_page(
     db.update_page(&id, updates).map_err(|e| e.to_string())
 }
 
+/// ページ名を変更（`rewrite_links` が true ならリンクも書き換える）
+#[tauri::command]
+pub async fn rename_page(
+    db: State<'_, LocalDB>,
+    id: String,
+    new_title: String,
+    rewrite_links: bool,
+) -> CmdResult<PageRenameResult> {
+    db.rename_page(&id, &new_title, rewrite_links)
+        .map_err(|e| e.to_string())
+}
+
 /// ページを削除（論理削除）
 #[tauri::command]
 pub async fn delete_page(db: State<'_, LocalDB>, id: String) -> CmdResult<bool> {
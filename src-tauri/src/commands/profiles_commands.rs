@@ -0,0 +1,90 @@
+//! Profiles Tauri Commands
+//!
+//! ユーザー別ローカルDB（プロファイル）操作のTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/db/profiles.rs (LocalDB, LocalProfile)
+//!   ├─ src-tauri/src/db/guest.rs (GuestClaimResult)
+//!   ├─ src-tauri/src/db/sign_out.rs (SignOutMode, SignOutResult)
+//!   └─ src-tauri/src/auth.rs (再認証の検証)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::auth::{self, AuthConfig};
+use crate::db::{GuestClaimResult, LocalDB, LocalProfile, SignOutMode, SignOutResult};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// プロファイルを切り替える（ログイン時に呼び出す）
+#[tauri::command]
+pub async fn switch_profile(db: State<'_, LocalDB>, user_id: String) -> CmdResult<LocalProfile> {
+    db.switch_profile(&user_id).map_err(|e| e.to_string())
+}
+
+/// ローカルに存在するプロファイル一覧を取得
+#[tauri::command]
+pub async fn list_local_profiles(db: State<'_, LocalDB>) -> CmdResult<Vec<LocalProfile>> {
+    db.list_local_profiles().map_err(|e| e.to_string())
+}
+
+/// 現在のプロファイルのユーザーIDを取得
+#[tauri::command]
+pub async fn get_active_profile(db: State<'_, LocalDB>) -> CmdResult<Option<String>> {
+    db.active_profile().map_err(|e| e.to_string())
+}
+
+/// ゲストモードを開始（ゲストの仮ユーザーIDはプロファイルの user_id）
+#[tauri::command]
+pub async fn enter_guest_mode(db: State<'_, LocalDB>) -> CmdResult<LocalProfile> {
+    db.enter_guest_mode().map_err(|e| e.to_string())
+}
+
+/// ゲストデータをサインインしたユーザーに引き継ぐ
+#[tauri::command]
+pub async fn claim_guest_data(
+    db: State<'_, LocalDB>,
+    new_user_id: String,
+) -> CmdResult<GuestClaimResult> {
+    db.claim_guest_data(&new_user_id).map_err(|e| e.to_string())
+}
+
+/// サインアウト（mode: "keep" | "wipe" | "seal"）
+///
+/// 未同期データがある状態で "wipe" を指定した場合、`force` が無ければ
+/// 消去せずに未同期件数だけを返す。
+/// "seal" はアプリ内のロックでファイルは暗号化しない（結果の `readable_on_disk` が true）。
+#[tauri::command]
+pub async fn sign_out(
+    db: State<'_, LocalDB>,
+    mode: SignOutMode,
+    force: Option<bool>,
+) -> CmdResult<SignOutResult> {
+    db.sign_out(mode, force.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+/// 封印されたプロファイルを再認証後に開く
+///
+/// `access_token` を Supabase Auth で検証し、トークンの持ち主が `user_id` の場合のみ開く。
+#[tauri::command]
+pub async fn unseal_profile(
+    db: State<'_, LocalDB>,
+    user_id: String,
+    access_token: String,
+) -> CmdResult<LocalProfile> {
+    let config = AuthConfig::from_env().map_err(|e| e.to_string())?;
+    let user = auth::verify_access_token(&config, &access_token)
+        .await
+        .map_err(|e| e.to_string())?;
+    if user.user_id() != user_id {
+        return Err(format!("Access token does not belong to user {}", user_id));
+    }
+    db.unseal_profile(&user).map_err(|e| e.to_string())
+}
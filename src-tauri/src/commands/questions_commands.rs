@@ -0,0 +1,114 @@
+//! Questions Tauri Commands
+//!
+//! 問題（クイズ）のTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/db/questions.rs (LocalDB)
+//!   └─ src-tauri/src/db/quiz.rs (多肢選択クイズの生成・タイピングの採点)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::content::{AnswerGrade, GradeOptions};
+use crate::db::{
+    LocalDB, LocalQuestion, QuestionStats, QuestionUpdate, QuizQuestion, DEFAULT_CHOICE_COUNT,
+};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// カードの問題を取得
+#[tauri::command]
+pub async fn get_questions(
+    db: State<'_, LocalDB>,
+    card_id: String,
+) -> CmdResult<Vec<LocalQuestion>> {
+    db.get_questions_by_card(&card_id)
+        .map_err(|e| e.to_string())
+}
+
+/// IDで問題を取得
+#[tauri::command]
+pub async fn get_question(db: State<'_, LocalDB>, id: String) -> CmdResult<Option<LocalQuestion>> {
+    db.get_question(&id).map_err(|e| e.to_string())
+}
+
+/// 問題を作成
+#[tauri::command]
+pub async fn create_question(db: State<'_, LocalDB>, question: LocalQuestion) -> CmdResult<()> {
+    db.insert_question(&question).map_err(|e| e.to_string())
+}
+
+/// 問題を更新
+#[tauri::command]
+pub async fn update_question(
+    db: State<'_, LocalDB>,
+    id: String,
+    updates: QuestionUpdate,
+) -> CmdResult<Option<LocalQuestion>> {
+    db.update_question(&id, updates).map_err(|e| e.to_string())
+}
+
+/// 問題を削除（論理削除）
+#[tauri::command]
+pub async fn delete_question(db: State<'_, LocalDB>, id: String) -> CmdResult<bool> {
+    db.delete_question(&id).map_err(|e| e.to_string())
+}
+
+/// カードの問題ごとの成績を取得
+#[tauri::command]
+pub async fn get_question_stats(
+    db: State<'_, LocalDB>,
+    card_id: String,
+) -> CmdResult<Vec<QuestionStats>> {
+    db.get_question_stats(&card_id).map_err(|e| e.to_string())
+}
+
+/// カードから多肢選択の問題を作る（同じデッキのカードから誤答を選ぶ）
+///
+/// シードを省略した場合は現在時刻から決める。
+#[tauri::command]
+pub async fn generate_quiz_question(
+    db: State<'_, LocalDB>,
+    card_id: String,
+    choice_count: Option<usize>,
+    seed: Option<u64>,
+) -> CmdResult<QuizQuestion> {
+    let seed = seed.unwrap_or_else(|| chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0) as u64);
+    db.generate_quiz_question(&card_id, choice_count.unwrap_or(DEFAULT_CHOICE_COUNT), seed)
+        .map_err(|e| e.to_string())
+}
+
+/// タイピングの回答を採点する（推奨の quality を学習ログに使う）
+#[tauri::command]
+pub async fn grade_typing_answer(
+    db: State<'_, LocalDB>,
+    card_id: String,
+    user_answer: String,
+    options: Option<GradeOptions>,
+) -> CmdResult<AnswerGrade> {
+    db.grade_typing_answer(&card_id, &user_answer, &options.unwrap_or_default())
+        .map_err(|e| e.to_string())
+}
+
+/// 同期待ちの問題を取得
+#[tauri::command]
+pub async fn get_pending_sync_questions(db: State<'_, LocalDB>) -> CmdResult<Vec<LocalQuestion>> {
+    db.get_pending_sync_questions().map_err(|e| e.to_string())
+}
+
+/// 問題を同期済みにマーク
+#[tauri::command]
+pub async fn mark_question_synced(
+    db: State<'_, LocalDB>,
+    id: String,
+    server_updated_at: String,
+) -> CmdResult<()> {
+    db.mark_question_synced(&id, &server_updated_at)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,138 @@
+//! Tags Tauri Commands
+//!
+//! タグ（ノート・ページ・カードのタグ付け）のTauriコマンド
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/tags.rs (LocalDB)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::{
+    LocalDB, LocalEntityTag, LocalTag, TagEntity, TagMergeResult, TagSummary, TaggedEntity,
+};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// ユーザーのタグを使用数とともに取得
+#[tauri::command]
+pub async fn get_tags(db: State<'_, LocalDB>, user_id: String) -> CmdResult<Vec<TagSummary>> {
+    db.get_tags(&user_id).map_err(|e| e.to_string())
+}
+
+/// データに付いているタグを取得
+#[tauri::command]
+pub async fn get_entity_tags(
+    db: State<'_, LocalDB>,
+    entity_type: TagEntity,
+    entity_id: String,
+) -> CmdResult<Vec<LocalTag>> {
+    db.get_entity_tags(entity_type, &entity_id)
+        .map_err(|e| e.to_string())
+}
+
+/// データにタグを付ける
+#[tauri::command]
+pub async fn add_tag(
+    db: State<'_, LocalDB>,
+    user_id: String,
+    entity_type: TagEntity,
+    entity_id: String,
+    name: String,
+) -> CmdResult<LocalTag> {
+    db.add_tag(&user_id, entity_type, &entity_id, &name)
+        .map_err(|e| e.to_string())
+}
+
+/// データからタグを外す
+#[tauri::command]
+pub async fn remove_tag(
+    db: State<'_, LocalDB>,
+    entity_type: TagEntity,
+    entity_id: String,
+    tag_id: String,
+) -> CmdResult<bool> {
+    db.remove_tag(entity_type, &entity_id, &tag_id)
+        .map_err(|e| e.to_string())
+}
+
+/// タグ名を変更（子孫のタグも変わる）
+#[tauri::command]
+pub async fn rename_tag(
+    db: State<'_, LocalDB>,
+    id: String,
+    new_name: String,
+) -> CmdResult<Vec<LocalTag>> {
+    db.rename_tag(&id, &new_name).map_err(|e| e.to_string())
+}
+
+/// タグを統合
+#[tauri::command]
+pub async fn merge_tags(
+    db: State<'_, LocalDB>,
+    source_id: String,
+    target_id: String,
+) -> CmdResult<TagMergeResult> {
+    db.merge_tags(&source_id, &target_id)
+        .map_err(|e| e.to_string())
+}
+
+/// タグを削除
+#[tauri::command]
+pub async fn delete_tag(db: State<'_, LocalDB>, id: String) -> CmdResult<bool> {
+    db.delete_tag(&id).map_err(|e| e.to_string())
+}
+
+/// タグが付いたデータを取得
+#[tauri::command]
+pub async fn get_entities_by_tag(
+    db: State<'_, LocalDB>,
+    user_id: String,
+    name: String,
+    include_descendants: bool,
+) -> CmdResult<Vec<TaggedEntity>> {
+    db.get_entities_by_tag(&user_id, &name, include_descendants)
+        .map_err(|e| e.to_string())
+}
+
+/// 同期待ちタグを取得
+#[tauri::command]
+pub async fn get_pending_sync_tags(db: State<'_, LocalDB>) -> CmdResult<Vec<LocalTag>> {
+    db.get_pending_sync_tags().map_err(|e| e.to_string())
+}
+
+/// 同期待ちタグ付けを取得
+#[tauri::command]
+pub async fn get_pending_sync_entity_tags(
+    db: State<'_, LocalDB>,
+) -> CmdResult<Vec<LocalEntityTag>> {
+    db.get_pending_sync_entity_tags().map_err(|e| e.to_string())
+}
+
+/// タグを同期済みにマーク
+#[tauri::command]
+pub async fn mark_tag_synced(
+    db: State<'_, LocalDB>,
+    id: String,
+    server_updated_at: String,
+) -> CmdResult<()> {
+    db.mark_tag_synced(&id, &server_updated_at)
+        .map_err(|e| e.to_string())
+}
+
+/// タグ付けを同期済みにマーク
+#[tauri::command]
+pub async fn mark_entity_tag_synced(
+    db: State<'_, LocalDB>,
+    id: String,
+    server_updated_at: String,
+) -> CmdResult<()> {
+    db.mark_entity_tag_synced(&id, &server_updated_at)
+        .map_err(|e| e.to_string())
+}
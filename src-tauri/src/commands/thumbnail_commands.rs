@@ -0,0 +1,125 @@
+//! Thumbnail Tauri Commands
+//!
+//! サムネイル生成のTauriコマンド。
+//! 生成したサムネイルはメディアストアに保存し、元画像ごとにキャッシュする。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/commands/mod.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/thumbnail.rs
+//!   ├─ src-tauri/src/content/images.rs
+//!   ├─ src-tauri/src/db/media.rs (LocalDB)
+//!   └─ src-tauri/src/commands/media_commands.rs (fetch_media)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::media_commands::fetch_media;
+use crate::content::{first_image_url, TiptapNode};
+use crate::db::media::{hash_media_bytes, parse_media_uri};
+use crate::db::{LocalDB, LocalMedia, LocalPage};
+use crate::thumbnail::{generate_thumbnail, ThumbnailOptions};
+use tauri::State;
+
+/// エラー型のエイリアス
+type CmdResult<T> = Result<T, String>;
+
+/// 元画像のサムネイルを取得する（キャッシュが無ければ生成して保存）
+fn thumbnail_for_source(
+    db: &LocalDB,
+    source_hash: &str,
+    bytes: &[u8],
+    options: &ThumbnailOptions,
+) -> CmdResult<LocalMedia> {
+    let variant = options.variant_key();
+    if let Some(cached) = db
+        .get_media_variant(source_hash, &variant)
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(cached);
+    }
+
+    let thumbnail = generate_thumbnail(bytes, options).map_err(|e| e.to_string())?;
+    let media = db
+        .ingest_media(&thumbnail.bytes, Some(thumbnail.mime_type), None)
+        .map_err(|e| e.to_string())?;
+    db.put_media_variant(source_hash, &variant, &media.hash)
+        .map_err(|e| e.to_string())?;
+    Ok(media)
+}
+
+/// 画像URLの内容を読み込む（ハッシュ, 内容）
+///
+/// ローカルのメディアか取り込み済みのURLならそれを使い、
+/// 無ければダウンロードしてメディアストアに取り込む。
+async fn load_image(db: &LocalDB, url: &str) -> CmdResult<(String, Vec<u8>)> {
+    let local_hash = match parse_media_uri(url) {
+        Some(hash) => Some(hash.to_string()),
+        None => db
+            .find_media_by_source_url(url)
+            .map_err(|e| e.to_string())?
+            .map(|media| media.hash),
+    };
+    if let Some(hash) = local_hash {
+        if let Some((media, bytes)) = db.read_media(&hash).map_err(|e| e.to_string())? {
+            return Ok((media.hash, bytes));
+        }
+    }
+    if parse_media_uri(url).is_some() {
+        return Err(format!("Media not found: {}", url));
+    }
+
+    let (bytes, mime_type) = fetch_media(url).await?;
+    let media = db
+        .ingest_media(&bytes, mime_type.as_deref(), Some(url))
+        .map_err(|e| e.to_string())?;
+    Ok((media.hash, bytes))
+}
+
+/// 画像からサムネイルを生成してメディアストアに保存
+#[tauri::command]
+pub async fn generate_thumbnail_media(
+    db: State<'_, LocalDB>,
+    bytes: Vec<u8>,
+    options: Option<ThumbnailOptions>,
+) -> CmdResult<LocalMedia> {
+    let options = options.unwrap_or_default();
+    thumbnail_for_source(&db, &hash_media_bytes(&bytes), &bytes, &options)
+}
+
+/// ページ本文の最初の画像からサムネイルを生成し、ページに設定する
+///
+/// 使える画像が無ければ何もせず None を返す
+#[tauri::command]
+pub async fn update_page_thumbnail_from_content(
+    db: State<'_, LocalDB>,
+    page_id: String,
+    content: TiptapNode,
+    options: Option<ThumbnailOptions>,
+) -> CmdResult<Option<LocalPage>> {
+    let Some(url) = first_image_url(&content) else {
+        return Ok(None);
+    };
+
+    let options = options.unwrap_or_default();
+    let (source_hash, bytes) = load_image(&db, url).await?;
+    let media = thumbnail_for_source(&db, &source_hash, &bytes, &options)?;
+    db.set_page_thumbnail(&page_id, &media)
+        .map_err(|e| e.to_string())
+}
+
+/// 画像からサムネイルを生成し、ページに設定する
+#[tauri::command]
+pub async fn update_page_thumbnail_from_image(
+    db: State<'_, LocalDB>,
+    page_id: String,
+    bytes: Vec<u8>,
+    options: Option<ThumbnailOptions>,
+) -> CmdResult<Option<LocalPage>> {
+    let options = options.unwrap_or_default();
+    let media = thumbnail_for_source(&db, &hash_media_bytes(&bytes), &bytes, &options)?;
+    db.set_page_thumbnail(&page_id, &media)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,208 @@
+//! 穴埋め（クローズ）記法の解析とカードの表示
+//!
+//! `{{c1::答え}}` / `{{c1::答え::ヒント}}` の番号ごとに1枚のカードを作る。
+//! 表面では対象の番号だけを `[...]`（ヒントがあれば `[ヒント]`）に隠し、
+//! 裏面では対象の答えを太字で示す。他の番号の穴埋めは答えをそのまま表示する。
+//! 閉じていない記法や番号の無い記法は通常のテキストとして扱う。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/content/mod.rs
+//!   └─ src-tauri/src/db/card_notes.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/content/tiptap.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::tiptap::{TiptapMark, TiptapNode};
+use serde::{Deserialize, Serialize};
+
+/// 穴埋め
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClozeDeletion {
+    /// `c1` の番号（1以上）
+    pub ordinal: u32,
+    pub answer: String,
+    pub hint: Option<String>,
+}
+
+/// 解析したテキストの断片
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClozeSegment {
+    Text(String),
+    Cloze(ClozeDeletion),
+}
+
+/// `{{c` の直後から穴埋め1つを読む（読めない場合は None）
+///
+/// 戻り値は穴埋めと `}}` の直後の位置。
+fn parse_deletion(text: &str, start: usize) -> Option<(ClozeDeletion, usize)> {
+    let rest = &text[start..];
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let ordinal: u32 = rest[..digits].parse().ok().filter(|n| *n > 0)?;
+    let body_start = digits + "::".len();
+    if !rest[digits..].starts_with("::") {
+        return None;
+    }
+    let body_len = rest[body_start..].find("}}")?;
+    let body = &rest[body_start..body_start + body_len];
+    let (answer, hint) = match body.split_once("::") {
+        Some((answer, hint)) => (answer, Some(hint.to_string())),
+        None => (body, None),
+    };
+    Some((
+        ClozeDeletion {
+            ordinal,
+            answer: answer.to_string(),
+            hint: hint.filter(|hint| !hint.is_empty()),
+        },
+        start + body_start + body_len + "}}".len(),
+    ))
+}
+
+/// テキストを通常の断片と穴埋めに分ける
+pub fn parse_cloze(text: &str) -> Vec<ClozeSegment> {
+    let mut segments = Vec::new();
+    let mut plain = String::new();
+    let mut pos = 0;
+    while let Some(offset) = text[pos..].find("{{c") {
+        let open = pos + offset;
+        match parse_deletion(text, open + "{{c".len()) {
+            Some((deletion, end)) => {
+                plain.push_str(&text[pos..open]);
+                if !plain.is_empty() {
+                    segments.push(ClozeSegment::Text(std::mem::take(&mut plain)));
+                }
+                segments.push(ClozeSegment::Cloze(deletion));
+                pos = end;
+            }
+            None => {
+                plain.push_str(&text[pos..open + "{{c".len()]);
+                pos = open + "{{c".len();
+            }
+        }
+    }
+    plain.push_str(&text[pos..]);
+    if !plain.is_empty() {
+        segments.push(ClozeSegment::Text(plain));
+    }
+    segments
+}
+
+/// テキストに含まれる穴埋めの番号（昇順・重複なし）
+pub fn cloze_ordinals(text: &str) -> Vec<u32> {
+    let mut ordinals: Vec<u32> = parse_cloze(text)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            ClozeSegment::Cloze(deletion) => Some(deletion.ordinal),
+            ClozeSegment::Text(_) => None,
+        })
+        .collect();
+    ordinals.sort_unstable();
+    ordinals.dedup();
+    ordinals
+}
+
+/// 改行で段落を分けながらテキストノードを追加する
+fn push_text(paragraphs: &mut Vec<Vec<TiptapNode>>, text: &str, marks: &[TiptapMark]) {
+    for (index, line) in text.split('\n').enumerate() {
+        if index > 0 {
+            paragraphs.push(Vec::new());
+        }
+        if !line.is_empty() {
+            if let Some(paragraph) = paragraphs.last_mut() {
+                paragraph.push(TiptapNode::text(line, marks.to_vec()));
+            }
+        }
+    }
+}
+
+/// 指定した番号の穴埋めを隠す（`reveal` で答えを示す）ドキュメントを作る
+fn render_cloze(text: &str, ordinal: u32, reveal: bool) -> TiptapNode {
+    let bold = [TiptapMark::new("bold")];
+    let mut paragraphs = vec![Vec::new()];
+    for segment in parse_cloze(text) {
+        match segment {
+            ClozeSegment::Text(text) => push_text(&mut paragraphs, &text, &[]),
+            ClozeSegment::Cloze(deletion) if deletion.ordinal == ordinal => {
+                let shown = if reveal {
+                    deletion.answer
+                } else {
+                    format!("[{}]", deletion.hint.as_deref().unwrap_or("..."))
+                };
+                push_text(&mut paragraphs, &shown, &bold);
+            }
+            ClozeSegment::Cloze(deletion) => push_text(&mut paragraphs, &deletion.answer, &[]),
+        }
+    }
+    TiptapNode::new(
+        "doc",
+        paragraphs
+            .into_iter()
+            .map(|content| TiptapNode::new("paragraph", content))
+            .collect(),
+    )
+}
+
+/// 表面（対象の番号を隠す）
+pub fn render_cloze_front(text: &str, ordinal: u32) -> TiptapNode {
+    render_cloze(text, ordinal, false)
+}
+
+/// 裏面（対象の答えを太字で示す）
+pub fn render_cloze_back(text: &str, ordinal: u32) -> TiptapNode {
+    render_cloze(text, ordinal, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::tiptap_to_plain_text;
+
+    #[test]
+    fn test_parse_cloze_with_hints_and_literal_braces() {
+        let segments = parse_cloze("{{c1::東京::首都}}は{{c2::日本}}の{{c0::x}}{{c3::未完");
+        assert_eq!(
+            segments,
+            vec![
+                ClozeSegment::Cloze(ClozeDeletion {
+                    ordinal: 1,
+                    answer: "東京".to_string(),
+                    hint: Some("首都".to_string()),
+                }),
+                ClozeSegment::Text("は".to_string()),
+                ClozeSegment::Cloze(ClozeDeletion {
+                    ordinal: 2,
+                    answer: "日本".to_string(),
+                    hint: None,
+                }),
+                ClozeSegment::Text("の{{c0::x}}{{c3::未完".to_string()),
+            ]
+        );
+        assert_eq!(cloze_ordinals("{{c2::a}} {{c1::b}} {{c2::c}}"), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_render_cloze_hides_only_target_ordinal() {
+        let text = "{{c1::東京::首都}}は{{c2::日本}}の都市\n人口が多い";
+
+        let front = render_cloze_front(text, 1);
+        assert_eq!(
+            tiptap_to_plain_text(&front),
+            "[首都]は日本の都市\n人口が多い"
+        );
+        let front = render_cloze_front(text, 2);
+        assert_eq!(
+            tiptap_to_plain_text(&front),
+            "東京は[...]の都市\n人口が多い"
+        );
+
+        let back = render_cloze_back(text, 2);
+        assert_eq!(tiptap_to_plain_text(&back), "東京は日本の都市\n人口が多い");
+        let answer = &back.children()[0].children()[2];
+        assert_eq!(answer.text.as_deref(), Some("日本"));
+        assert_eq!(answer.mark_list(), &[TiptapMark::new("bold")]);
+    }
+}
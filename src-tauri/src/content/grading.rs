@@ -0,0 +1,376 @@
+//! タイピング回答の採点
+//!
+//! 回答と正解を正規化（NFKC による全角・半角の統一、小文字化、句読点・記号の除去、
+//! 空白の整理、必要に応じてカタカナをひらがなに寄せる）してから編集距離で比べる。
+//! 正解は改行・`;`・`|`（全角を含む）で区切って複数書ける。最も近い正解で採点し、
+//! 部分点と文字単位の差分、学習ログに使う推奨の quality（0〜5）を返す。
+//! 正規化後の先頭 `MAX_GRADED_CHARS` 文字だけを比べ、差分の表が大きくなる組み合わせは
+//! 前後の一致部分だけを残した粗い差分にする。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/content/mod.rs
+//!   ├─ src-tauri/src/db/quiz.rs
+//!   └─ src-tauri/src/commands/questions_commands.rs
+//!
+//! Dependencies:
+//!   └─ unicode-normalization
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+/// 正解の区切り文字
+const ANSWER_SEPARATORS: [char; 5] = ['\n', ';', '；', '|', '｜'];
+
+/// 採点で比べる文字数の上限（正規化後）
+const MAX_GRADED_CHARS: usize = 1000;
+
+/// 文字単位の差分を求める表の大きさの上限
+const MAX_DIFF_CELLS: usize = 250_000;
+
+/// 採点のオプション
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GradeOptions {
+    /// ひらがなとカタカナを区別しない
+    #[serde(default)]
+    pub kana_insensitive: bool,
+}
+
+/// 差分の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffKind {
+    /// 回答と正解で一致
+    Equal,
+    /// 正解にあって回答に無い
+    Missing,
+    /// 回答にあって正解に無い
+    Extra,
+}
+
+/// 文字単位の差分（正規化後の文字列で計算する）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffSegment {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// 採点結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnswerGrade {
+    /// 推奨の quality が 3 以上
+    pub is_correct: bool,
+    /// 部分点（0〜1、1 - 編集距離 / 長い方の文字数）
+    pub score: f64,
+    /// 学習ログ・復習に使う推奨の quality（0〜5）
+    pub quality: i32,
+    /// 採点に使った正解（書かれたまま）
+    pub expected: Option<String>,
+    pub diff: Vec<DiffSegment>,
+}
+
+/// 正解のテキストを区切って複数の正解にする
+pub fn split_accepted_answers(text: &str) -> Vec<String> {
+    text.split(ANSWER_SEPARATORS)
+        .map(str::trim)
+        .filter(|answer| !answer.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// 句読点・記号（英数字・かな・漢字は含まない）
+fn is_punctuation(c: char) -> bool {
+    !c.is_alphanumeric()
+        && (c.is_ascii_punctuation()
+            || matches!(c, '\u{2000}'..='\u{206F}' | '\u{3000}'..='\u{303F}' | '\u{30FB}'))
+}
+
+/// 比較に使う形にする
+pub fn normalize_answer(text: &str, options: &GradeOptions) -> String {
+    let folded: String = text
+        .nfkc()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !is_punctuation(*c))
+        .map(|c| match c {
+            // カタカナ（ァ〜ヶ）をひらがなに寄せる
+            '\u{30A1}'..='\u{30F6}' if options.kana_insensitive => {
+                char::from_u32(c as u32 - 0x60).unwrap_or(c)
+            }
+            _ => c,
+        })
+        .collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 編集距離（2行分の表で計算する）
+fn edit_distance(expected: &[char], actual: &[char]) -> usize {
+    let mut previous = (0..=actual.len()).collect::<Vec<_>>();
+    let mut current = vec![0usize; actual.len() + 1];
+    for (i, e) in expected.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, a) in actual.iter().enumerate() {
+            let substitution = previous[j] + usize::from(e != a);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[actual.len()]
+}
+
+/// 1文字ずつの操作にする（表が大きすぎるときは前後の一致部分だけを残す）
+fn diff_ops(expected: &[char], actual: &[char]) -> Vec<(DiffKind, char)> {
+    let (n, m) = (expected.len(), actual.len());
+    if (n + 1) * (m + 1) > MAX_DIFF_CELLS {
+        let prefix = expected
+            .iter()
+            .zip(actual)
+            .take_while(|(e, a)| e == a)
+            .count();
+        let suffix = expected[prefix..]
+            .iter()
+            .rev()
+            .zip(actual[prefix..].iter().rev())
+            .take_while(|(e, a)| e == a)
+            .count();
+        return expected[..prefix]
+            .iter()
+            .map(|c| (DiffKind::Equal, *c))
+            .chain(
+                actual[prefix..m - suffix]
+                    .iter()
+                    .map(|c| (DiffKind::Extra, *c)),
+            )
+            .chain(
+                expected[prefix..n - suffix]
+                    .iter()
+                    .map(|c| (DiffKind::Missing, *c)),
+            )
+            .chain(expected[n - suffix..].iter().map(|c| (DiffKind::Equal, *c)))
+            .collect();
+    }
+
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dp[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let substitution = dp[i - 1][j - 1] + usize::from(expected[i - 1] != actual[j - 1]);
+            dp[i][j] = substitution.min(dp[i - 1][j] + 1).min(dp[i][j - 1] + 1);
+        }
+    }
+
+    // 末尾からたどる
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && expected[i - 1] == actual[j - 1] && dp[i][j] == dp[i - 1][j - 1] {
+            ops.push((DiffKind::Equal, expected[i - 1]));
+            (i, j) = (i - 1, j - 1);
+        } else if i > 0 && j > 0 && dp[i][j] == dp[i - 1][j - 1] + 1 {
+            ops.push((DiffKind::Missing, expected[i - 1]));
+            ops.push((DiffKind::Extra, actual[j - 1]));
+            (i, j) = (i - 1, j - 1);
+        } else if i > 0 && dp[i][j] == dp[i - 1][j] + 1 {
+            ops.push((DiffKind::Missing, expected[i - 1]));
+            i -= 1;
+        } else {
+            ops.push((DiffKind::Extra, actual[j - 1]));
+            j -= 1;
+        }
+    }
+    ops.reverse();
+    ops
+}
+
+/// 文字単位の差分を計算する
+fn diff_chars(expected: &[char], actual: &[char]) -> Vec<DiffSegment> {
+    // 一致しない区間は「余分」→「不足」の順にまとめる
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    let mut push = |kind: DiffKind, text: &str| {
+        if text.is_empty() {
+            return;
+        }
+        match segments.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(text),
+            _ => segments.push(DiffSegment {
+                kind,
+                text: text.to_string(),
+            }),
+        }
+    };
+    let (mut extra, mut missing) = (String::new(), String::new());
+    for (kind, c) in diff_ops(expected, actual) {
+        match kind {
+            DiffKind::Extra => extra.push(c),
+            DiffKind::Missing => missing.push(c),
+            DiffKind::Equal => {
+                push(DiffKind::Extra, &std::mem::take(&mut extra));
+                push(DiffKind::Missing, &std::mem::take(&mut missing));
+                push(DiffKind::Equal, &c.to_string());
+            }
+        }
+    }
+    push(DiffKind::Extra, &extra);
+    push(DiffKind::Missing, &missing);
+    segments
+}
+
+/// 部分点を推奨の quality にする
+fn quality_for_score(score: f64) -> i32 {
+    match score {
+        s if s >= 1.0 => 5,
+        s if s >= 0.9 => 4,
+        s if s >= 0.75 => 3,
+        s if s >= 0.5 => 2,
+        s if s > 0.0 => 1,
+        _ => 0,
+    }
+}
+
+/// 回答を採点する（正解のうち最も近いもので採点する）
+pub fn grade_answer(user_answer: &str, accepted: &[String], options: &GradeOptions) -> AnswerGrade {
+    let graded_chars = |text: &str| -> Vec<char> {
+        normalize_answer(text, options)
+            .chars()
+            .take(MAX_GRADED_CHARS)
+            .collect()
+    };
+    let actual = graded_chars(user_answer);
+    let mut best: Option<(f64, &String, Vec<char>)> = None;
+    for answer in accepted {
+        let expected = graded_chars(answer);
+        if expected.is_empty() {
+            continue;
+        }
+        let distance = edit_distance(&expected, &actual);
+        let score = 1.0 - distance as f64 / expected.len().max(actual.len()) as f64;
+        if best
+            .as_ref()
+            .map_or(true, |(best_score, _, _)| score > *best_score)
+        {
+            best = Some((score, answer, expected));
+        }
+    }
+
+    match best {
+        Some((score, answer, expected)) => {
+            let diff = diff_chars(&expected, &actual);
+            let quality = quality_for_score(score);
+            AnswerGrade {
+                is_correct: quality >= 3,
+                score,
+                quality,
+                expected: Some(answer.clone()),
+                diff,
+            }
+        }
+        None => AnswerGrade {
+            is_correct: false,
+            score: 0.0,
+            quality: 0,
+            expected: None,
+            diff: Vec::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_answer_folds_width_case_punctuation_and_kana() {
+        let options = GradeOptions::default();
+        assert_eq!(
+            normalize_answer("  ＴＯＫＹＯ，  Japan! ", &options),
+            "tokyo japan"
+        );
+        assert_eq!(normalize_answer("ﾄｳｷｮｳ。", &options), "トウキョウ");
+        assert_eq!(normalize_answer("「東京」・日本", &options), "東京日本");
+
+        let kana = GradeOptions {
+            kana_insensitive: true,
+        };
+        assert_eq!(normalize_answer("ﾄｳｷｮｳ", &kana), "とうきょう");
+        assert_eq!(normalize_answer("コーヒー", &kana), "こーひー");
+    }
+
+    #[test]
+    fn test_grade_answer_picks_closest_accepted_answer() {
+        let accepted = split_accepted_answers("apple\nりんご；ringo");
+        assert_eq!(accepted, vec!["apple", "りんご", "ringo"]);
+        let options = GradeOptions::default();
+
+        let grade = grade_answer("ＲＩＮＧＯ", &accepted, &options);
+        assert_eq!((grade.quality, grade.is_correct), (5, true));
+        assert_eq!(grade.expected.as_deref(), Some("ringo"));
+
+        let grade = grade_answer("aple", &accepted, &options);
+        assert_eq!(grade.expected.as_deref(), Some("apple"));
+        assert_eq!(grade.score, 0.8);
+        assert_eq!((grade.quality, grade.is_correct), (3, true));
+        assert_eq!(
+            grade.diff,
+            vec![
+                DiffSegment {
+                    kind: DiffKind::Equal,
+                    text: "a".to_string()
+                },
+                DiffSegment {
+                    kind: DiffKind::Missing,
+                    text: "p".to_string()
+                },
+                DiffSegment {
+                    kind: DiffKind::Equal,
+                    text: "ple".to_string()
+                },
+            ]
+        );
+
+        let grade = grade_answer("リンゴ", &accepted, &options);
+        assert_eq!((grade.quality, grade.is_correct), (0, false));
+        let grade = grade_answer(
+            "リンゴ",
+            &accepted,
+            &GradeOptions {
+                kana_insensitive: true,
+            },
+        );
+        assert_eq!(grade.quality, 5);
+
+        assert_eq!(grade_answer("", &accepted, &options).quality, 0);
+        assert_eq!(grade_answer("x", &[], &options).expected, None);
+    }
+
+    #[test]
+    fn test_grade_answer_bounds_long_answers() {
+        let options = GradeOptions::default();
+        let expected = "a".repeat(600) + "b" + &"c".repeat(600);
+        let answer = "a".repeat(600) + "x" + &"c".repeat(600);
+
+        // 先頭 MAX_GRADED_CHARS 文字だけを比べ、差分の表が上限を超えるので前後の一致部分だけを残す
+        let grade = grade_answer(&answer, &[expected], &options);
+        assert_eq!(grade.quality, 4);
+        let kinds = grade
+            .diff
+            .iter()
+            .map(|segment| (segment.kind, segment.text.chars().count()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                (DiffKind::Equal, 600),
+                (DiffKind::Extra, 1),
+                (DiffKind::Missing, 1),
+                (DiffKind::Equal, 399),
+            ]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+//! ページ本文の画像の抽出
+//!
+//! `lib/utils/thumbnailExtractor.ts` と同じく、`image`・`gyazoImage` ノードを
+//! 文書順に探し、許可されたドメインの画像だけをサムネイルの候補にする。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/content/mod.rs
+//!   └─ src-tauri/src/commands/thumbnail_commands.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::tiptap::TiptapNode;
+use crate::db::media::parse_media_uri;
+
+/// サムネイルに使える画像のドメイン（`lib/utils/domainValidation.ts` と同じ）
+pub const ALLOWED_IMAGE_DOMAINS: [&str; 4] =
+    ["scrapbox.io", "gyazo.com", "i.gyazo.com", "i.ytimg.com"];
+
+/// URLのホスト名を取り出す
+fn hostname(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?;
+    host.split(':').next()
+}
+
+/// 画像URLのドメインが許可されているか
+pub fn is_allowed_image_domain(url: &str) -> bool {
+    hostname(url).is_some_and(|host| {
+        ALLOWED_IMAGE_DOMAINS
+            .iter()
+            .any(|allowed| host.eq_ignore_ascii_case(allowed))
+    })
+}
+
+/// サムネイルに使える画像か（許可されたドメイン、またはローカルのメディア）
+fn is_thumbnail_candidate(src: &str) -> bool {
+    is_allowed_image_domain(src) || parse_media_uri(src).is_some()
+}
+
+/// 本文の画像URLを文書順にすべて取得
+pub fn extract_image_urls(node: &TiptapNode) -> Vec<&str> {
+    let mut urls = Vec::new();
+    collect_image_urls(node, &mut urls);
+    urls
+}
+
+fn collect_image_urls<'a>(node: &'a TiptapNode, urls: &mut Vec<&'a str>) {
+    if matches!(node.node_type.as_str(), "image" | "gyazoImage") {
+        if let Some(src) = node.attr_str("src").map(str::trim) {
+            if !src.is_empty() {
+                urls.push(src);
+            }
+        }
+    }
+    for child in node.children() {
+        collect_image_urls(child, urls);
+    }
+}
+
+/// サムネイルに使う最初の画像URLを取得
+pub fn first_image_url(node: &TiptapNode) -> Option<&str> {
+    extract_image_urls(node)
+        .into_iter()
+        .find(|src| is_thumbnail_candidate(src))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_image_url_skips_disallowed_domains() {
+        let doc = TiptapNode::new(
+            "doc",
+            vec![
+                TiptapNode::new("paragraph", vec![TiptapNode::text("intro", vec![])]),
+                TiptapNode::leaf("image").with_attr("src", "https://evil.example.com/a.png"),
+                TiptapNode::new(
+                    "blockquote",
+                    vec![TiptapNode::leaf("gyazoImage")
+                        .with_attr("src", "https://i.gyazo.com/abc.png")],
+                ),
+            ],
+        );
+
+        assert_eq!(extract_image_urls(&doc).len(), 2);
+        assert_eq!(first_image_url(&doc), Some("https://i.gyazo.com/abc.png"));
+        assert!(!is_allowed_image_domain(
+            "https://gyazo.com.evil.example/a.png"
+        ));
+        assert!(is_allowed_image_domain(
+            "https://scrapbox.io:443/files/a.png"
+        ));
+    }
+}
@@ -0,0 +1,277 @@
+//! ページ本文のリンクの抽出
+//!
+//! `lib/utils/extractLinksFromContent.ts` と同じく、`unilink` マークから
+//! ページリンク（`[タイトル]`）とタグ（`#タグ`）を文書順に取り出す。
+//! ページ名の変更に合わせてリンクを書き換える処理もここに置く。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/content/mod.rs
+//!   ├─ src-tauri/src/db/page_links.rs
+//!   └─ src-tauri/src/db/page_rename.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::tiptap::{normalize_title_to_key, TiptapMark, TiptapNode};
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+
+/// リンクの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkVariant {
+    /// `[タイトル]`
+    Bracket,
+    /// `#タグ`
+    Tag,
+}
+
+impl LinkVariant {
+    /// `page_links.variant` に保存する値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkVariant::Bracket => "bracket",
+            LinkVariant::Tag => "tag",
+        }
+    }
+
+    /// 保存された値から復元
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "bracket" => Some(LinkVariant::Bracket),
+            "tag" => Some(LinkVariant::Tag),
+            _ => None,
+        }
+    }
+}
+
+/// 本文から取り出したリンク
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractedLink {
+    /// 正規化したタイトル（ページの照合に使う）
+    pub key: String,
+    /// 書かれたままのタイトル（タグは `#` を除く）
+    pub title: String,
+    pub variant: LinkVariant,
+    /// 文書内の出現順
+    pub position: usize,
+}
+
+/// `unilink` マークのリンク先（種類, タイトル）を取得
+pub fn link_target(mark: &TiptapMark) -> Option<(LinkVariant, String)> {
+    if mark.mark_type != "unilink" {
+        return None;
+    }
+    let variant = LinkVariant::parse(mark.attr_str("variant")?)?;
+    let title = mark
+        .attr_str("raw")
+        .filter(|raw| !raw.trim().is_empty())
+        .or_else(|| mark.attr_str("text"))?;
+    let title = match variant {
+        LinkVariant::Tag => title.trim_start_matches('#'),
+        LinkVariant::Bracket => title,
+    };
+    if title.trim().is_empty() {
+        return None;
+    }
+    Some((variant, title.to_string()))
+}
+
+/// 本文のリンクを文書順に取り出す
+///
+/// 書式の途中で分割されたリンク（同じマークが続くテキスト）は1つとして数える。
+pub fn extract_links(node: &TiptapNode) -> Vec<ExtractedLink> {
+    let mut links = Vec::new();
+    collect_links(node, &mut links);
+    links
+}
+
+fn collect_links(node: &TiptapNode, links: &mut Vec<ExtractedLink>) {
+    let mut previous: Option<&TiptapMark> = None;
+    for child in node.children() {
+        if !child.is_text() {
+            previous = None;
+            collect_links(child, links);
+            continue;
+        }
+
+        let mark = child
+            .mark_list()
+            .iter()
+            .find(|mark| mark.mark_type == "unilink");
+        if let Some(mark) = mark {
+            if previous != Some(mark) {
+                if let Some((variant, title)) = link_target(mark) {
+                    links.push(ExtractedLink {
+                        key: normalize_title_to_key(&title),
+                        title,
+                        variant,
+                        position: links.len(),
+                    });
+                }
+            }
+        }
+        previous = mark;
+    }
+}
+
+/// リンク先が `key` の `unilink` マークなら、その種類を返す
+pub fn link_matches(mark: &TiptapMark, key: &str) -> Option<LinkVariant> {
+    let (variant, title) = link_target(mark)?;
+    (normalize_title_to_key(&title) == key).then_some(variant)
+}
+
+/// リンク先のタイトルから本文に表示するテキストを作る
+///
+/// タグは空白を含められないため `_` に置き換える（キーの正規化で空白に戻る）
+pub fn link_text(variant: LinkVariant, title: &str) -> String {
+    match variant {
+        LinkVariant::Bracket => title.to_string(),
+        LinkVariant::Tag => format!("#{}", tag_name(title)),
+    }
+}
+
+fn tag_name(title: &str) -> String {
+    title.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+/// リンク先を `new_title` に変えた `unilink` マークを作る
+///
+/// `pageId`・`markId` などの他の属性はそのまま残す。
+pub fn retarget_link_mark(mark: &TiptapMark, variant: LinkVariant, new_title: &str) -> TiptapMark {
+    let key = normalize_title_to_key(new_title);
+    let raw = match variant {
+        LinkVariant::Bracket => new_title.to_string(),
+        LinkVariant::Tag => tag_name(new_title),
+    };
+
+    let mut mark = mark.clone();
+    let attrs = mark.attrs.get_or_insert_with(Map::new);
+    if variant == LinkVariant::Bracket && attrs.contains_key("href") {
+        attrs.insert("href".to_string(), format!("#{}", key).into());
+    }
+    attrs.insert("raw".to_string(), raw.into());
+    attrs.insert("text".to_string(), link_text(variant, new_title).into());
+    attrs.insert("key".to_string(), key.into());
+    mark
+}
+
+/// リンク先が `old_key` のリンクを `new_title` へ書き換える（書き換えた数を返す）
+///
+/// 書式の途中で分割されたリンクは、最初のテキストにまとめる。
+pub fn rename_links(node: &mut TiptapNode, old_key: &str, new_title: &str) -> usize {
+    let Some(children) = node.content.as_mut() else {
+        return 0;
+    };
+
+    let mut count = 0;
+    let mut renamed = Vec::with_capacity(children.len());
+    let mut previous: Option<TiptapMark> = None;
+    for mut child in children.drain(..) {
+        if !child.is_text() {
+            previous = None;
+            count += rename_links(&mut child, old_key, new_title);
+            renamed.push(child);
+            continue;
+        }
+
+        let matched = child
+            .mark_list()
+            .iter()
+            .find_map(|mark| Some((mark.clone(), link_matches(mark, old_key)?)));
+        let Some((mark, variant)) = matched else {
+            previous = None;
+            renamed.push(child);
+            continue;
+        };
+        // 分割されたリンクの続きは捨てる
+        if previous.as_ref() == Some(&mark) {
+            continue;
+        }
+
+        child.text = Some(link_text(variant, new_title));
+        for child_mark in child.marks.iter_mut().flatten() {
+            if *child_mark == mark {
+                *child_mark = retarget_link_mark(&mark, variant, new_title);
+            }
+        }
+        previous = Some(mark);
+        count += 1;
+        renamed.push(child);
+    }
+    *children = renamed;
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::tiptap::{bracket_link_mark, tag_link_mark};
+
+    #[test]
+    fn test_extract_links_in_document_order() {
+        let rust = bracket_link_mark("Rust  言語");
+        let doc = TiptapNode::new(
+            "doc",
+            vec![
+                TiptapNode::new(
+                    "paragraph",
+                    vec![
+                        TiptapNode::text("see ", vec![]),
+                        TiptapNode::text("Rust ", vec![rust.clone()]),
+                        TiptapNode::text(" 言語", vec![rust, TiptapMark::new("bold")]),
+                    ],
+                ),
+                TiptapNode::new(
+                    "bulletList",
+                    vec![TiptapNode::new(
+                        "listItem",
+                        vec![TiptapNode::new(
+                            "paragraph",
+                            vec![TiptapNode::text("#memo", vec![tag_link_mark("memo")])],
+                        )],
+                    )],
+                ),
+            ],
+        );
+
+        let links = extract_links(&doc);
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].key, "Rust 言語");
+        assert_eq!(links[0].variant, LinkVariant::Bracket);
+        assert_eq!(links[1].title, "memo");
+        assert_eq!(links[1].variant, LinkVariant::Tag);
+        assert_eq!(links[1].position, 1);
+    }
+
+    #[test]
+    fn test_rename_links() {
+        let mut doc = TiptapNode::new(
+            "doc",
+            vec![TiptapNode::new(
+                "paragraph",
+                vec![
+                    TiptapNode::text("Old Page", vec![bracket_link_mark("Old_Page")]),
+                    TiptapNode::text(" and ", vec![]),
+                    TiptapNode::text("#Old_Page", vec![tag_link_mark("Old_Page")]),
+                    TiptapNode::text("Other", vec![bracket_link_mark("Other")]),
+                ],
+            )],
+        );
+
+        assert_eq!(rename_links(&mut doc, "Old Page", "New Page"), 2);
+        let links = extract_links(&doc);
+        assert_eq!(links[0].key, "New Page");
+        assert_eq!(links[1].title, "New_Page");
+        assert_eq!(links[2].key, "Other");
+
+        let paragraph = &doc.children()[0];
+        assert_eq!(paragraph.children()[0].text.as_deref(), Some("New Page"));
+        assert_eq!(paragraph.children()[2].text.as_deref(), Some("#New_Page"));
+        assert_eq!(
+            paragraph.children()[0].mark_list()[0].attr_str("href"),
+            Some("#New Page")
+        );
+    }
+}
@@ -0,0 +1,925 @@
+//! Tiptap ドキュメント ⇔ Markdown 変換
+//!
+//! 書き出しはエクスポートやカード生成用、読み込みはMarkdownインポート用。
+//! アプリ独自の記法は次のように対応させる。
+//!
+//! - ページリンク（unilink, variant = bracket）: `[タイトル]`
+//! - タグ（unilink, variant = tag）: `#タグ`
+//! - ハイライト: `<mark>テキスト</mark>`
+//! - インライン数式（latexInlineNode）: `$数式$`
+//! - 画像（gyazoImage）: `![alt](src "title")`
+//!
+//! テロメア（ブロックの `updatedAt` 属性）は表示用のメタデータのため書き出さない。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/content/mod.rs
+//!   └─ src-tauri/src/commands/content_commands.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/content/tiptap.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::tiptap::{bracket_link_mark, tag_link_mark, TiptapMark, TiptapNode};
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use serde_json::Value;
+use std::ops::Range;
+
+// ============================================================================
+// Tiptap → Markdown
+// ============================================================================
+
+/// Tiptap ドキュメントを Markdown に変換する
+pub fn tiptap_to_markdown(doc: &TiptapNode) -> String {
+    let mut markdown = render_blocks(doc.children(), "\n\n");
+    markdown.push('\n');
+    markdown
+}
+
+/// ブロックを連結して書き出す
+fn render_blocks(nodes: &[TiptapNode], separator: &str) -> String {
+    nodes
+        .iter()
+        .map(render_block)
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// 各行の先頭に文字列を付ける（2行目以降は `rest` を使う）
+fn prefix_lines(text: &str, first: &str, rest: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let prefix = if i == 0 { first } else { rest };
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// ブロックノードを書き出す
+fn render_block(node: &TiptapNode) -> String {
+    match node.node_type.as_str() {
+        "paragraph" => escape_block_start(&render_inline(node.children())),
+        "heading" => {
+            let level = node
+                .attr("level")
+                .and_then(Value::as_u64)
+                .unwrap_or(1)
+                .clamp(1, 6) as usize;
+            format!("{} {}", "#".repeat(level), render_inline(node.children()))
+        }
+        "bulletList" => node
+            .children()
+            .iter()
+            .map(|item| render_list_item(item, "- "))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "orderedList" => {
+            let start = node.attr("start").and_then(Value::as_u64).unwrap_or(1);
+            node.children()
+                .iter()
+                .enumerate()
+                .map(|(i, item)| render_list_item(item, &format!("{}. ", start + i as u64)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        "blockquote" => prefix_lines(&render_blocks(node.children(), "\n\n"), "> ", "> "),
+        "codeBlock" => {
+            let code = plain_text_of(node.children());
+            let fence = if code.contains("```") { "~~~" } else { "```" };
+            format!(
+                "{}{}\n{}\n{}",
+                fence,
+                node.attr_str("language").unwrap_or_default(),
+                code,
+                fence
+            )
+        }
+        "horizontalRule" => "---".to_string(),
+        "gyazoImage" | "image" => render_image(node),
+        "table" => render_table(node),
+        // 未知のブロックは中身だけ書き出す
+        _ if node.is_text() => render_inline(std::slice::from_ref(node)),
+        _ => {
+            if node.children().iter().all(is_inline) {
+                render_inline(node.children())
+            } else {
+                render_blocks(node.children(), "\n\n")
+            }
+        }
+    }
+}
+
+/// インラインノードか
+fn is_inline(node: &TiptapNode) -> bool {
+    matches!(
+        node.node_type.as_str(),
+        "text" | "hardBreak" | "latexInlineNode"
+    )
+}
+
+/// リストの項目を書き出す（2行目以降はマーカーの幅だけ字下げする）
+fn render_list_item(item: &TiptapNode, marker: &str) -> String {
+    let indent = " ".repeat(marker.len());
+    let mut body = String::new();
+    for (i, child) in item.children().iter().enumerate() {
+        if i > 0 {
+            // 段落が続く場合は空行で区切らないと前の段落に連結されてしまう
+            body.push_str(if child.node_type == "paragraph" {
+                "\n\n"
+            } else {
+                "\n"
+            });
+        }
+        body.push_str(&render_block(child));
+    }
+    prefix_lines(&body, marker, &indent)
+}
+
+/// 画像を書き出す
+fn render_image(node: &TiptapNode) -> String {
+    let src = node.attr_str("src").unwrap_or_default();
+    let alt = escape_text(node.attr_str("alt").unwrap_or_default());
+    match node.attr_str("title") {
+        Some(title) if !title.is_empty() => {
+            format!("![{}]({} \"{}\")", alt, src, title.replace('"', "\\\""))
+        }
+        _ => format!("![{}]({})", alt, src),
+    }
+}
+
+/// 表を書き出す（1行目を見出し行とする）
+fn render_table(node: &TiptapNode) -> String {
+    let rows = node.children();
+    let Some(header) = rows.first() else {
+        return String::new();
+    };
+
+    let render_row = |row: &TiptapNode| {
+        let cells = row
+            .children()
+            .iter()
+            .map(|cell| {
+                cell.children()
+                    .iter()
+                    .map(|block| render_inline(block.children()))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .replace('|', "\\|")
+            })
+            .collect::<Vec<_>>();
+        format!("| {} |", cells.join(" | "))
+    };
+
+    let delimiter = header
+        .children()
+        .iter()
+        .map(|cell| match cell.attr_str("textAlign") {
+            Some("left") => ":---",
+            Some("center") => ":---:",
+            Some("right") => "---:",
+            _ => "---",
+        })
+        .collect::<Vec<_>>();
+
+    let mut lines = vec![render_row(header), format!("| {} |", delimiter.join(" | "))];
+    lines.extend(rows.iter().skip(1).map(render_row));
+    lines.join("\n")
+}
+
+/// ノード以下のテキストを連結する（コードブロック用）
+fn plain_text_of(nodes: &[TiptapNode]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node.text.as_deref() {
+            Some(text) => text.to_string(),
+            None if node.node_type == "hardBreak" => "\n".to_string(),
+            None => plain_text_of(node.children()),
+        })
+        .collect()
+}
+
+/// Markdownの記号として解釈される文字をエスケープする
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '#' | '~' | '<' | '$'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 段落の先頭がリスト・引用・見出しなどとして解釈されないようにする
+fn escape_block_start(text: &str) -> String {
+    let trimmed = text.trim_start();
+    let is_ordered_marker = {
+        let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+        digits > 0 && matches!(trimmed[digits..].chars().next(), Some('.') | Some(')'))
+    };
+    let starts_with_marker = trimmed.starts_with("- ")
+        || trimmed.starts_with("+ ")
+        || trimmed.starts_with('>')
+        || trimmed.starts_with('=')
+        || trimmed == "-"
+        || trimmed == "+";
+
+    if is_ordered_marker {
+        let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+        format!("{}\\{}", &trimmed[..digits], &trimmed[digits..])
+    } else if starts_with_marker {
+        format!("\\{}", trimmed)
+    } else {
+        text.to_string()
+    }
+}
+
+/// マークの種類を取得
+fn find_mark<'a>(node: &'a TiptapNode, mark_type: &str) -> Option<&'a TiptapMark> {
+    node.mark_list()
+        .iter()
+        .find(|mark| mark.mark_type == mark_type)
+}
+
+/// ページリンクのマークか（variant = bracket のunilink、または旧形式のpageLink）
+fn is_bracket_link(node: &TiptapNode) -> bool {
+    node.mark_list().iter().any(|mark| {
+        mark.mark_type == "pageLink"
+            || (mark.mark_type == "unilink" && mark.attr_str("variant") != Some("tag"))
+    })
+}
+
+/// インラインノードを書き出す
+fn render_inline(nodes: &[TiptapNode]) -> String {
+    let nodes = merge_adjacent_text(nodes);
+
+    // ページリンクはエディタ上で `[` `タイトル` `]` の3つのテキストノードに分かれているので、
+    // 前後の角括弧を取り除いてリンク記法としてまとめて書き出す
+    let mut texts: Vec<Option<String>> = nodes.iter().map(|node| node.text.clone()).collect();
+    let mut bracketed = vec![false; nodes.len()];
+    for i in 0..nodes.len() {
+        if !nodes[i].is_text() || !is_bracket_link(&nodes[i]) {
+            continue;
+        }
+        let has_open = i > 0
+            && !is_bracket_link(&nodes[i - 1])
+            && texts[i - 1].as_deref().is_some_and(|t| t.ends_with('['));
+        let has_close = i + 1 < nodes.len()
+            && !is_bracket_link(&nodes[i + 1])
+            && texts[i + 1].as_deref().is_some_and(|t| t.starts_with(']'));
+        if has_open && has_close {
+            if let Some(prev) = texts[i - 1].as_mut() {
+                prev.pop();
+            }
+            if let Some(next) = texts[i + 1].as_mut() {
+                next.remove(0);
+            }
+        }
+        bracketed[i] = true;
+    }
+
+    let mut markdown = String::new();
+    for (i, node) in nodes.iter().enumerate() {
+        match node.node_type.as_str() {
+            "text" => {
+                let text = texts[i].as_deref().unwrap_or_default();
+                if text.is_empty() {
+                    continue;
+                }
+                markdown.push_str(&render_text(node, text, bracketed[i]));
+            }
+            "hardBreak" => markdown.push_str("\\\n"),
+            "latexInlineNode" => {
+                markdown.push('$');
+                markdown.push_str(node.attr_str("content").unwrap_or_default());
+                markdown.push('$');
+            }
+            "gyazoImage" | "image" => markdown.push_str(&render_image(node)),
+            _ => markdown.push_str(&render_inline(node.children())),
+        }
+    }
+    markdown
+}
+
+/// 同じマークを持つ隣接したテキストノードを結合する
+fn merge_adjacent_text(nodes: &[TiptapNode]) -> Vec<TiptapNode> {
+    let mut merged: Vec<TiptapNode> = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        if let Some(last) = merged.last_mut() {
+            if last.is_text() && node.is_text() && last.marks == node.marks {
+                let text = node.text.as_deref().unwrap_or_default();
+                last.text.get_or_insert_with(String::new).push_str(text);
+                continue;
+            }
+        }
+        merged.push(node.clone());
+    }
+    merged
+}
+
+/// マーク付きのテキストを書き出す
+///
+/// 前後の空白は区切り記号の外に出す（`** a **` は強調として解釈されないため）
+fn render_text(node: &TiptapNode, text: &str, bracketed: bool) -> String {
+    if find_mark(node, "code").is_some() {
+        let fence = if text.contains('`') { "`` " } else { "`" };
+        let closing = if text.contains('`') { " ``" } else { "`" };
+        return format!("{}{}{}", fence, text, closing);
+    }
+
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+    let core = text.trim();
+    if core.is_empty() {
+        return text.to_string();
+    }
+
+    let is_tag =
+        find_mark(node, "unilink").is_some_and(|mark| mark.attr_str("variant") == Some("tag"));
+    let mut inner = if bracketed {
+        format!("[{}]", core.replace(['[', ']'], ""))
+    } else if is_tag {
+        // タグはテキストに `#` を含んでいる
+        format!("#{}", escape_text(core.trim_start_matches('#')))
+    } else {
+        escape_text(core)
+    };
+
+    if find_mark(node, "highlight").is_some() {
+        inner = format!("<mark>{}</mark>", inner);
+    }
+    if find_mark(node, "strike").is_some() {
+        inner = format!("~~{}~~", inner);
+    }
+    if find_mark(node, "italic").is_some() {
+        inner = format!("*{}*", inner);
+    }
+    if find_mark(node, "bold").is_some() {
+        inner = format!("**{}**", inner);
+    }
+    if let Some(link) = find_mark(node, "link") {
+        let href = link.attr_str("href").unwrap_or_default();
+        inner = format!("[{}]({})", inner, href.replace(' ', "%20"));
+    }
+
+    format!("{}{}{}", leading, inner, trailing)
+}
+
+// ============================================================================
+// Markdown → Tiptap
+// ============================================================================
+
+/// 構築中のノード
+struct OpenNode {
+    node: TiptapNode,
+    /// Markdownに段落が無い位置（リスト項目・表のセル）で補った段落か
+    implicit: bool,
+}
+
+/// Markdownのイベントから Tiptap ドキュメントを組み立てる
+struct DocumentBuilder<'a> {
+    source: &'a str,
+    stack: Vec<OpenNode>,
+    marks: Vec<TiptapMark>,
+    /// エスケープされていない連続したテキスト（リンク記法の検出用）
+    pending_text: String,
+    /// 表の列の揃え
+    alignments: Vec<Alignment>,
+    in_table_head: bool,
+    column: usize,
+    /// 画像の代替テキストを読み込み中
+    image: Option<TiptapNode>,
+    /// コードブロックを読み込み中
+    in_code_block: bool,
+}
+
+/// Markdown を Tiptap ドキュメントに変換する
+pub fn markdown_to_tiptap(markdown: &str) -> TiptapNode {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_MATH;
+    let parser = Parser::new_ext(markdown, options);
+
+    let mut builder = DocumentBuilder {
+        source: markdown,
+        stack: vec![OpenNode {
+            node: TiptapNode::new("doc", Vec::new()),
+            implicit: false,
+        }],
+        marks: Vec::new(),
+        pending_text: String::new(),
+        alignments: Vec::new(),
+        in_table_head: false,
+        column: 0,
+        image: None,
+        in_code_block: false,
+    };
+
+    for (event, range) in parser.into_offset_iter() {
+        builder.handle(event, range);
+    }
+    builder.finish()
+}
+
+impl DocumentBuilder<'_> {
+    fn handle(&mut self, event: Event<'_>, range: Range<usize>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                if self.in_code_block {
+                    self.append_code(&text);
+                } else if let Some(image) = self.image.as_mut() {
+                    let alt = image.attr_str("alt").unwrap_or_default().to_string() + &text;
+                    *image = image.clone().with_attr("alt", alt);
+                } else if range.start > 0 && self.source.as_bytes()[range.start - 1] == b'\\' {
+                    // バックスラッシュでエスケープされた先頭の文字はリンク記法として扱わない
+                    let mut chars = text.chars();
+                    if let Some(first) = chars.next() {
+                        self.push_inline(TiptapNode::text(&first.to_string(), self.marks.clone()));
+                    }
+                    self.pending_text.push_str(chars.as_str());
+                } else {
+                    self.pending_text.push_str(&text);
+                }
+            }
+            Event::Code(code) => {
+                let mut marks = self.marks.clone();
+                marks.push(TiptapMark::new("code"));
+                self.push_inline(TiptapNode::text(&code, marks));
+            }
+            Event::InlineMath(math) | Event::DisplayMath(math) => {
+                self.push_inline(
+                    TiptapNode::leaf("latexInlineNode").with_attr("content", math.as_ref()),
+                );
+            }
+            Event::InlineHtml(html) => match html.trim() {
+                "<mark>" => {
+                    self.flush_text();
+                    self.marks.push(TiptapMark::new("highlight"));
+                }
+                "</mark>" => {
+                    self.flush_text();
+                    if let Some(index) = self.marks.iter().rposition(|m| m.mark_type == "highlight")
+                    {
+                        self.marks.remove(index);
+                    }
+                }
+                other => self.pending_text.push_str(other),
+            },
+            Event::Html(html) => self.pending_text.push_str(html.trim_end()),
+            Event::SoftBreak => self.pending_text.push(' '),
+            Event::HardBreak => self.push_inline(TiptapNode::leaf("hardBreak")),
+            Event::Rule => self.push_block(TiptapNode::leaf("horizontalRule")),
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph => self.open_block(TiptapNode::new("paragraph", Vec::new())),
+            Tag::Heading { level, .. } => {
+                let level = match level {
+                    HeadingLevel::H1 => 1,
+                    HeadingLevel::H2 => 2,
+                    HeadingLevel::H3 => 3,
+                    HeadingLevel::H4 => 4,
+                    HeadingLevel::H5 => 5,
+                    HeadingLevel::H6 => 6,
+                };
+                self.open_block(TiptapNode::new("heading", Vec::new()).with_attr("level", level));
+            }
+            Tag::BlockQuote(_) => self.open_block(TiptapNode::new("blockquote", Vec::new())),
+            Tag::CodeBlock(kind) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(lang) if !lang.is_empty() => Value::from(lang.as_ref()),
+                    _ => Value::Null,
+                };
+                self.open_block(
+                    TiptapNode::new("codeBlock", Vec::new()).with_attr("language", language),
+                );
+                self.in_code_block = true;
+            }
+            Tag::List(Some(start)) => self
+                .open_block(TiptapNode::new("orderedList", Vec::new()).with_attr("start", start)),
+            Tag::List(None) => self.open_block(TiptapNode::new("bulletList", Vec::new())),
+            Tag::Item => self.open_block(TiptapNode::new("listItem", Vec::new())),
+            Tag::Table(alignments) => {
+                self.alignments = alignments;
+                self.open_block(TiptapNode::new("table", Vec::new()));
+            }
+            Tag::TableHead => {
+                self.in_table_head = true;
+                self.column = 0;
+                self.open_block(TiptapNode::new("tableRow", Vec::new()));
+            }
+            Tag::TableRow => {
+                self.column = 0;
+                self.open_block(TiptapNode::new("tableRow", Vec::new()));
+            }
+            Tag::TableCell => {
+                let cell_type = if self.in_table_head {
+                    "tableHeader"
+                } else {
+                    "tableCell"
+                };
+                let mut cell = TiptapNode::new(cell_type, Vec::new());
+                let align = match self.alignments.get(self.column) {
+                    Some(Alignment::Left) => Some("left"),
+                    Some(Alignment::Center) => Some("center"),
+                    Some(Alignment::Right) => Some("right"),
+                    _ => None,
+                };
+                if let Some(align) = align {
+                    cell = cell.with_attr("textAlign", align);
+                }
+                self.column += 1;
+                self.open_block(cell);
+            }
+            Tag::Emphasis => self.push_mark(TiptapMark::new("italic")),
+            Tag::Strong => self.push_mark(TiptapMark::new("bold")),
+            Tag::Strikethrough => self.push_mark(TiptapMark::new("strike")),
+            Tag::Link { dest_url, .. } => {
+                self.push_mark(TiptapMark::new("link").with_attr("href", dest_url.as_ref()))
+            }
+            Tag::Image {
+                dest_url, title, ..
+            } => {
+                self.flush_text();
+                let mut image = TiptapNode::leaf("gyazoImage")
+                    .with_attr("src", dest_url.as_ref())
+                    .with_attr("alt", "");
+                if !title.is_empty() {
+                    image = image.with_attr("title", title.as_ref());
+                }
+                self.image = Some(image);
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph
+            | TagEnd::Heading(_)
+            | TagEnd::BlockQuote(..)
+            | TagEnd::List(_)
+            | TagEnd::Item
+            | TagEnd::TableRow
+            | TagEnd::TableCell => self.close_block(),
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                if let Some(open) = self.stack.last_mut() {
+                    // 末尾の改行はフェンスの一部
+                    if let Some(text) = open
+                        .node
+                        .content
+                        .as_mut()
+                        .and_then(|content| content.last_mut())
+                        .and_then(|node| node.text.as_mut())
+                    {
+                        if text.ends_with('\n') {
+                            text.pop();
+                        }
+                    }
+                }
+                self.close_block();
+            }
+            TagEnd::Table => {
+                self.close_block();
+                self.alignments.clear();
+            }
+            TagEnd::TableHead => {
+                self.close_block();
+                self.in_table_head = false;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link => {
+                self.flush_text();
+                self.marks.pop();
+            }
+            TagEnd::Image => {
+                if let Some(image) = self.image.take() {
+                    self.push_inline(image);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// コードブロックのテキストを追加する
+    fn append_code(&mut self, text: &str) {
+        let Some(open) = self.stack.last_mut() else {
+            return;
+        };
+        let content = open.node.content.get_or_insert_with(Vec::new);
+        match content.last_mut().and_then(|node| node.text.as_mut()) {
+            Some(existing) => existing.push_str(text),
+            None => content.push(TiptapNode::text(text, Vec::new())),
+        }
+    }
+
+    fn push_mark(&mut self, mark: TiptapMark) {
+        self.flush_text();
+        self.marks.push(mark);
+    }
+
+    /// ブロックを開く（補った段落は閉じる）
+    fn open_block(&mut self, node: TiptapNode) {
+        self.close_implicit_paragraph();
+        self.stack.push(OpenNode {
+            node,
+            implicit: false,
+        });
+    }
+
+    /// 子を持たないブロックを追加する
+    fn push_block(&mut self, node: TiptapNode) {
+        self.close_implicit_paragraph();
+        self.append_child(node);
+    }
+
+    fn close_block(&mut self) {
+        self.close_implicit_paragraph();
+        if self.stack.len() <= 1 {
+            return;
+        }
+        if let Some(open) = self.stack.pop() {
+            self.append_block(open.node);
+        }
+    }
+
+    fn close_implicit_paragraph(&mut self) {
+        self.flush_text();
+        if self.stack.last().is_some_and(|open| open.implicit) {
+            if let Some(open) = self.stack.pop() {
+                self.append_block(open.node);
+            }
+        }
+    }
+
+    /// 閉じたブロックを親に追加する
+    ///
+    /// 画像だけの段落は画像ブロックに置き換える（gyazoImage はブロックノード）
+    fn append_block(&mut self, mut node: TiptapNode) {
+        if node.node_type == "paragraph" {
+            let (images, rest): (Vec<_>, Vec<_>) = node
+                .content
+                .take()
+                .unwrap_or_default()
+                .into_iter()
+                .partition(|child| child.node_type == "gyazoImage");
+            let has_text = rest
+                .iter()
+                .any(|child| child.text.as_deref().map_or(true, |t| !t.trim().is_empty()));
+            if has_text || images.is_empty() {
+                node.content = Some(rest);
+                self.append_child(node);
+            }
+            for image in images {
+                self.append_child(image);
+            }
+            return;
+        }
+        self.append_child(node);
+    }
+
+    fn append_child(&mut self, node: TiptapNode) {
+        if let Some(parent) = self.stack.last_mut() {
+            parent.node.content.get_or_insert_with(Vec::new).push(node);
+        }
+    }
+
+    /// インラインノードを追加する（段落が無ければ補う）
+    fn push_inline(&mut self, node: TiptapNode) {
+        // 先にたまっているテキストを出力して順序を保つ
+        self.flush_text();
+
+        let needs_paragraph = self.stack.last().is_some_and(|open| {
+            !matches!(
+                open.node.node_type.as_str(),
+                "paragraph" | "heading" | "codeBlock"
+            )
+        });
+        if needs_paragraph {
+            self.stack.push(OpenNode {
+                node: TiptapNode::new("paragraph", Vec::new()),
+                implicit: true,
+            });
+        }
+
+        let Some(open) = self.stack.last_mut() else {
+            return;
+        };
+        let content = open.node.content.get_or_insert_with(Vec::new);
+        if let Some(last) = content.last_mut() {
+            if last.is_text() && node.is_text() && last.marks == node.marks && !has_link_mark(&node)
+            {
+                let text = node.text.as_deref().unwrap_or_default();
+                last.text.get_or_insert_with(String::new).push_str(text);
+                return;
+            }
+        }
+        content.push(node);
+    }
+
+    /// たまったテキストからページリンク・タグを検出してノードにする
+    fn flush_text(&mut self) {
+        if self.pending_text.is_empty() {
+            return;
+        }
+        let text = std::mem::take(&mut self.pending_text);
+        for (segment, link) in split_links(&text) {
+            match link {
+                Some(mark) => {
+                    let mut marks = self.marks.clone();
+                    marks.push(mark);
+                    self.push_inline(TiptapNode::text(&segment, marks));
+                }
+                None => self.push_inline(TiptapNode::text(&segment, self.marks.clone())),
+            }
+        }
+    }
+
+    fn finish(mut self) -> TiptapNode {
+        self.close_implicit_paragraph();
+        while self.stack.len() > 1 {
+            self.close_block();
+        }
+        self.stack
+            .pop()
+            .map(|open| open.node)
+            .unwrap_or_else(|| TiptapNode::new("doc", Vec::new()))
+    }
+}
+
+/// ページリンク・タグのマークを持つか
+fn has_link_mark(node: &TiptapNode) -> bool {
+    node.mark_list()
+        .iter()
+        .any(|mark| mark.mark_type == "unilink")
+}
+
+/// タグに使える文字か
+fn is_tag_char(c: char) -> bool {
+    !c.is_whitespace()
+        && !matches!(
+            c,
+            '#' | '[' | ']' | '(' | ')' | ',' | '.' | '!' | '?' | '、' | '。'
+        )
+}
+
+/// テキストをページリンク・タグとそれ以外に分割する
+///
+/// ページリンクはエディタと同じく `[`・タイトル・`]` の3つに分ける。
+fn split_links(text: &str) -> Vec<(String, Option<TiptapMark>)> {
+    let mut segments: Vec<(String, Option<TiptapMark>)> = Vec::new();
+    let mut plain = String::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+
+    let push_plain = |segments: &mut Vec<(String, Option<TiptapMark>)>, plain: &mut String| {
+        if !plain.is_empty() {
+            segments.push((std::mem::take(plain), None));
+        }
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '[' {
+            if let Some(close) = chars[i + 1..].iter().position(|&c| c == ']' || c == '[') {
+                let close = i + 1 + close;
+                let title: String = chars[i + 1..close].iter().collect();
+                if chars[close] == ']' && !title.trim().is_empty() {
+                    plain.push('[');
+                    push_plain(&mut segments, &mut plain);
+                    segments.push((title.clone(), Some(bracket_link_mark(&title))));
+                    plain.push(']');
+                    i = close + 1;
+                    continue;
+                }
+            }
+        } else if c == '#' && (i == 0 || chars[i - 1].is_whitespace()) {
+            let len = chars[i + 1..]
+                .iter()
+                .take_while(|&&c| is_tag_char(c))
+                .count();
+            if len > 0 {
+                let tag: String = chars[i + 1..i + 1 + len].iter().collect();
+                push_plain(&mut segments, &mut plain);
+                segments.push((format!("#{}", tag), Some(tag_link_mark(&tag))));
+                i += 1 + len;
+                continue;
+            }
+        }
+        plain.push(c);
+        i += 1;
+    }
+    push_plain(&mut segments, &mut plain);
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paragraph(content: Vec<TiptapNode>) -> TiptapNode {
+        TiptapNode::new("paragraph", content)
+    }
+
+    fn text(value: &str) -> TiptapNode {
+        TiptapNode::text(value, Vec::new())
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let markdown = r#"# Title
+
+Some **bold**, *italic*, ~~strike~~, `code` and <mark>highlight</mark> with a [link](https://example.com).
+
+Link to [Other Page] and #tag here, with $e^x$ inline.\
+Next line
+
+- one
+- two
+  1. nested
+  2. list
+
+> quoted
+
+```rust
+fn main() {}
+```
+
+| Name | Value |
+| :--- | ---: |
+| a | 1 |
+
+![diagram](https://example.com/a.png "Diagram")
+
+---
+
+Escaped \[not a link\] and \#not-a-tag
+"#;
+
+        let doc = markdown_to_tiptap(markdown);
+        assert_eq!(tiptap_to_markdown(&doc), markdown);
+        assert_eq!(markdown_to_tiptap(&tiptap_to_markdown(&doc)), doc);
+    }
+
+    #[test]
+    fn test_page_links_match_editor_structure() {
+        let doc = markdown_to_tiptap("See [Rust Book] #rust_lang");
+        let nodes = doc.children()[0].children();
+
+        assert_eq!(nodes[0].text.as_deref(), Some("See ["));
+        assert_eq!(nodes[1].text.as_deref(), Some("Rust Book"));
+        let mark = &nodes[1].mark_list()[0];
+        assert_eq!(mark.attr_str("variant"), Some("bracket"));
+        assert_eq!(mark.attr_str("key"), Some("Rust Book"));
+        assert_eq!(nodes[2].text.as_deref(), Some("] "));
+        assert_eq!(nodes[3].text.as_deref(), Some("#rust_lang"));
+        assert_eq!(nodes[3].mark_list()[0].attr_str("key"), Some("rust lang"));
+    }
+
+    #[test]
+    fn test_editor_document_to_markdown() {
+        // エディタが保存する形式（テロメア属性付き・旧形式のpageLink）
+        let doc: TiptapNode = serde_json::from_value(serde_json::json!({
+            "type": "doc",
+            "content": [
+                {
+                    "type": "heading",
+                    "attrs": { "level": 2, "updatedAt": "2025-01-01T00:00:00Z" },
+                    "content": [{ "type": "text", "text": "Heading" }]
+                },
+                {
+                    "type": "paragraph",
+                    "attrs": { "updatedAt": "2025-01-01T00:00:00Z" },
+                    "content": [
+                        { "type": "text", "text": "1. not a list * star " },
+                        { "type": "text", "text": "legacy", "marks": [{ "type": "pageLink", "attrs": { "pageName": "legacy" } }] }
+                    ]
+                }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            tiptap_to_markdown(&doc),
+            "## Heading\n\n1\\. not a list \\* star [legacy]\n"
+        );
+
+        let empty = TiptapNode::new("doc", vec![paragraph(vec![text("")])]);
+        assert_eq!(tiptap_to_markdown(&empty), "\n");
+    }
+}
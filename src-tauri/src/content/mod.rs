@@ -0,0 +1,45 @@
+//! ページ本文（Tiptapドキュメント）の変換モジュール
+//!
+//! 検索インデックス・エクスポート・カード生成のため、エディタのJSONを
+//! Markdown・プレーンテキストに変換する。Markdownからの読み込みにも対応する。
+//! サムネイル用の画像やリンクの取り出し、Yjsドキュメントとの相互変換も行う。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/lib.rs
+//!
+//! Children (Modules in this module):
+//!   ├─ tiptap.rs - ドキュメントの型定義
+//!   ├─ cloze.rs - 穴埋め記法の解析とカードの表示
+//!   ├─ grading.rs - タイピング回答の採点
+//!   ├─ images.rs - 本文の画像の抽出
+//!   ├─ links.rs - 本文のリンクの抽出と書き換え
+//!   ├─ yjs.rs - Yjsドキュメントとの相互変換
+//!   ├─ markdown.rs - Markdown との相互変換
+//!   └─ plain_text.rs - プレーンテキストへの変換
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+pub mod cloze;
+pub mod grading;
+pub mod images;
+pub mod links;
+pub mod markdown;
+pub mod plain_text;
+pub mod tiptap;
+pub mod yjs;
+
+pub use cloze::{
+    cloze_ordinals, parse_cloze, render_cloze_back, render_cloze_front, ClozeDeletion, ClozeSegment,
+};
+pub use grading::{
+    grade_answer, normalize_answer, split_accepted_answers, AnswerGrade, DiffKind, DiffSegment,
+    GradeOptions,
+};
+pub use images::{extract_image_urls, first_image_url};
+pub use links::{extract_links, rename_links, ExtractedLink, LinkVariant};
+pub use markdown::{markdown_to_tiptap, tiptap_to_markdown};
+pub use plain_text::{plain_text_to_tiptap, tiptap_to_plain_text};
+pub use tiptap::{normalize_title_to_key, TiptapMark, TiptapNode};
+pub use yjs::{rename_links_in_yjs, tiptap_to_yjs_update, yjs_update_to_tiptap, YjsError};
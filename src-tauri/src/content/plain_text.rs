@@ -0,0 +1,120 @@
+//! Tiptap ドキュメント → プレーンテキスト変換
+//!
+//! 検索インデックスやカード生成で使うため、記法を含まない本文だけを取り出す。
+//! ブロックは改行、表のセルはタブで区切る。
+//! カードノートの入力欄のテキストから段落だけのドキュメントを作る逆変換も行う。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/content/mod.rs
+//!   ├─ src-tauri/src/commands/content_commands.rs
+//!   └─ src-tauri/src/db/card_notes.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/content/tiptap.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::tiptap::TiptapNode;
+
+/// Tiptap ドキュメントをプレーンテキストに変換する
+pub fn tiptap_to_plain_text(doc: &TiptapNode) -> String {
+    let mut lines = Vec::new();
+    collect_blocks(doc.children(), &mut lines);
+    lines.join("\n")
+}
+
+/// プレーンテキストを Tiptap ドキュメントに変換する（1行を1段落にする）
+pub fn plain_text_to_tiptap(text: &str) -> TiptapNode {
+    TiptapNode::new(
+        "doc",
+        text.lines()
+            .map(|line| {
+                let content = if line.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![TiptapNode::text(line, Vec::new())]
+                };
+                TiptapNode::new("paragraph", content)
+            })
+            .collect(),
+    )
+}
+
+/// ブロックを1行以上のテキストとして集める
+fn collect_blocks(nodes: &[TiptapNode], lines: &mut Vec<String>) {
+    for node in nodes {
+        match node.node_type.as_str() {
+            "horizontalRule" => {}
+            "gyazoImage" | "image" => {
+                if let Some(alt) = node.attr_str("alt").filter(|alt| !alt.is_empty()) {
+                    lines.push(alt.to_string());
+                }
+            }
+            "table" => {
+                for row in node.children() {
+                    let cells = row
+                        .children()
+                        .iter()
+                        .map(|cell| {
+                            let mut cell_lines = Vec::new();
+                            collect_blocks(cell.children(), &mut cell_lines);
+                            cell_lines.join(" ")
+                        })
+                        .collect::<Vec<_>>();
+                    lines.push(cells.join("\t"));
+                }
+            }
+            "codeBlock" => lines.push(inline_text(node.children())),
+            _ if node.children().iter().any(|child| !is_inline(child)) => {
+                collect_blocks(node.children(), lines)
+            }
+            _ => {
+                let text = inline_text(node.children());
+                if !text.trim().is_empty() {
+                    lines.push(text);
+                }
+            }
+        }
+    }
+}
+
+/// インラインノードか
+fn is_inline(node: &TiptapNode) -> bool {
+    matches!(
+        node.node_type.as_str(),
+        "text" | "hardBreak" | "latexInlineNode"
+    )
+}
+
+/// インラインノードのテキストを連結する
+fn inline_text(nodes: &[TiptapNode]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node.node_type.as_str() {
+            "text" => node.text.clone().unwrap_or_default(),
+            "hardBreak" => "\n".to_string(),
+            "latexInlineNode" => node.attr_str("content").unwrap_or_default().to_string(),
+            _ => inline_text(node.children()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::markdown_to_tiptap;
+
+    #[test]
+    fn test_plain_text_strips_markup() {
+        let doc = markdown_to_tiptap(
+            "# Title\n\nSee **[Rust Book]** and #rust\n\n- one\n- two\n\n| a | b |\n| --- | --- |\n| 1 | 2 |\n\n![alt text](x.png)\n",
+        );
+
+        assert_eq!(
+            tiptap_to_plain_text(&doc),
+            "Title\nSee [Rust Book] and #rust\none\ntwo\na\tb\n1\t2\nalt text"
+        );
+    }
+}
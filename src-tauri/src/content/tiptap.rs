@@ -0,0 +1,169 @@
+//! Tiptap（ProseMirror）ドキュメントの型定義
+//!
+//! エディタの `editor.getJSON()` が返すJSONをそのまま読み書きする。
+//! 未知のノード・マーク・属性も失わないよう、属性は JSON のまま保持する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/content/mod.rs
+//!   ├─ src-tauri/src/content/cloze.rs
+//!   ├─ src-tauri/src/content/markdown.rs
+//!   └─ src-tauri/src/content/plain_text.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use unicode_normalization::UnicodeNormalization;
+
+/// ノード
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TiptapNode {
+    #[serde(rename = "type")]
+    pub node_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attrs: Option<Map<String, Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<Vec<TiptapNode>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marks: Option<Vec<TiptapMark>>,
+}
+
+/// マーク
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TiptapMark {
+    #[serde(rename = "type")]
+    pub mark_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attrs: Option<Map<String, Value>>,
+}
+
+impl TiptapNode {
+    /// 子ノードを持つノードを作成
+    pub fn new(node_type: &str, content: Vec<TiptapNode>) -> Self {
+        TiptapNode {
+            node_type: node_type.to_string(),
+            content: Some(content),
+            ..Default::default()
+        }
+    }
+
+    /// 子ノードを持たないノードを作成（hardBreak・画像など）
+    pub fn leaf(node_type: &str) -> Self {
+        TiptapNode {
+            node_type: node_type.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// テキストノードを作成
+    pub fn text(text: &str, marks: Vec<TiptapMark>) -> Self {
+        TiptapNode {
+            node_type: "text".to_string(),
+            text: Some(text.to_string()),
+            marks: if marks.is_empty() { None } else { Some(marks) },
+            ..Default::default()
+        }
+    }
+
+    /// 属性を設定する
+    pub fn with_attr(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.attrs
+            .get_or_insert_with(Map::new)
+            .insert(key.to_string(), value.into());
+        self
+    }
+
+    /// 属性を取得
+    pub fn attr(&self, key: &str) -> Option<&Value> {
+        self.attrs.as_ref().and_then(|attrs| attrs.get(key))
+    }
+
+    /// 文字列の属性を取得
+    pub fn attr_str(&self, key: &str) -> Option<&str> {
+        self.attr(key).and_then(Value::as_str)
+    }
+
+    /// 子ノード（無ければ空）
+    pub fn children(&self) -> &[TiptapNode] {
+        self.content.as_deref().unwrap_or_default()
+    }
+
+    /// マーク（無ければ空）
+    pub fn mark_list(&self) -> &[TiptapMark] {
+        self.marks.as_deref().unwrap_or_default()
+    }
+
+    /// テキストノードか
+    pub fn is_text(&self) -> bool {
+        self.node_type == "text"
+    }
+}
+
+impl TiptapMark {
+    /// マークを作成
+    pub fn new(mark_type: &str) -> Self {
+        TiptapMark {
+            mark_type: mark_type.to_string(),
+            attrs: None,
+        }
+    }
+
+    /// 属性を設定する
+    pub fn with_attr(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.attrs
+            .get_or_insert_with(Map::new)
+            .insert(key.to_string(), value.into());
+        self
+    }
+
+    /// 文字列の属性を取得
+    pub fn attr_str(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .as_ref()
+            .and_then(|attrs| attrs.get(key))
+            .and_then(Value::as_str)
+    }
+}
+
+/// ページタイトルをリンクのキーに正規化する
+///
+/// `lib/unilink/utils.ts` の `normalizeTitleToKey` と同じ規則
+pub fn normalize_title_to_key(raw: &str) -> String {
+    raw.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('_', " ")
+        .nfc()
+        .collect()
+}
+
+/// ページリンク（`[タイトル]`）のマークを作成
+pub fn bracket_link_mark(title: &str) -> TiptapMark {
+    let key = normalize_title_to_key(title);
+    TiptapMark::new("unilink")
+        .with_attr("variant", "bracket")
+        .with_attr("raw", title)
+        .with_attr("text", title)
+        .with_attr("href", format!("#{}", key))
+        .with_attr("key", key)
+        .with_attr("pageId", Value::Null)
+        .with_attr("state", "exists")
+        .with_attr("exists", true)
+}
+
+/// タグ（`#タグ`）のマークを作成
+pub fn tag_link_mark(tag: &str) -> TiptapMark {
+    TiptapMark::new("unilink")
+        .with_attr("variant", "tag")
+        .with_attr("raw", tag)
+        .with_attr("text", format!("#{}", tag))
+        .with_attr("key", normalize_title_to_key(tag))
+        .with_attr("pageId", Value::Null)
+        .with_attr("href", "#")
+        .with_attr("state", "pending")
+        .with_attr("exists", false)
+}
@@ -0,0 +1,427 @@
+//! Yjsドキュメントと Tiptap JSON の相互変換
+//!
+//! ページ本文は y-prosemirror と同じ構造で保存する。
+//! - ルートは `default` という名前の XmlFragment
+//! - ノードは XmlElement（タグ名がノード名、属性がノードの属性）
+//! - 連続するテキストは1つの XmlText にまとめ、マークは書式属性
+//!   （キーがマーク名、値がマークの属性）として持つ
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/content/mod.rs
+//!   ├─ src-tauri/src/db/page_links.rs
+//!   └─ src-tauri/src/db/page_rename.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::links::{link_matches, link_text, retarget_link_mark, LinkVariant};
+use super::tiptap::{TiptapMark, TiptapNode};
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+use yrs::types::text::YChange;
+use yrs::types::Attrs;
+use yrs::updates::decoder::Decode;
+use yrs::{
+    Any, Doc, Out, ReadTxn, StateVector, Text, Transact, TransactionMut, Update, Xml,
+    XmlElementPrelim, XmlElementRef, XmlFragment, XmlOut, XmlTextPrelim, XmlTextRef,
+};
+
+/// 本文を保存する XmlFragment の名前（Tiptap の Collaboration 拡張のデフォルト）
+pub const YJS_FRAGMENT_NAME: &str = "default";
+
+/// Yjsドキュメントの読み書きのエラー
+#[derive(Debug, Error)]
+#[error("Yjs document error: {0}")]
+pub struct YjsError(String);
+
+/// Yjs v1 の更新からドキュメントを復元する
+pub fn load_yjs_doc(update: &[u8]) -> Result<Doc, YjsError> {
+    let doc = Doc::new();
+    {
+        let mut txn = doc.transact_mut();
+        let update = Update::decode_v1(update).map_err(|e| YjsError(e.to_string()))?;
+        txn.apply_update(update)
+            .map_err(|e| YjsError(e.to_string()))?;
+    }
+    Ok(doc)
+}
+
+/// Yjs v1 の更新（ドキュメント全体）を Tiptap JSON に変換する
+pub fn yjs_update_to_tiptap(update: &[u8]) -> Result<TiptapNode, YjsError> {
+    let doc = load_yjs_doc(update)?;
+    let fragment = doc.get_or_insert_xml_fragment(YJS_FRAGMENT_NAME);
+    let txn = doc.transact();
+    Ok(TiptapNode::new("doc", fragment_to_nodes(&fragment, &txn)))
+}
+
+/// Tiptap JSON から新しいドキュメントを作り、Yjs v1 の更新として返す
+///
+/// Markdownの読み込みなど、Yjsの履歴が無い本文を取り込むときに使う。
+pub fn tiptap_to_yjs_update(doc: &TiptapNode) -> Vec<u8> {
+    let ydoc = Doc::new();
+    let fragment = ydoc.get_or_insert_xml_fragment(YJS_FRAGMENT_NAME);
+    let mut txn = ydoc.transact_mut();
+    write_nodes(&fragment, &mut txn, doc.children());
+    txn.encode_state_as_update_v1(&StateVector::default())
+}
+
+/// リンク先が `old_key` のリンクを `new_title` へ書き換える
+///
+/// 書き換えたときは（既存の更新に追記する差分の更新, 書き換えた数）を返す。
+/// 差分として追記するため、他の端末の編集とも CRDT としてマージできる。
+pub fn rename_links_in_yjs(
+    update: &[u8],
+    old_key: &str,
+    new_title: &str,
+) -> Result<Option<(Vec<u8>, usize)>, YjsError> {
+    let doc = load_yjs_doc(update)?;
+    let fragment = doc.get_or_insert_xml_fragment(YJS_FRAGMENT_NAME);
+    let mut texts = Vec::new();
+    {
+        let txn = doc.transact();
+        collect_texts(&fragment, &txn, &mut texts);
+    }
+
+    let mut txn = doc.transact_mut();
+    let before = txn.state_vector();
+    let count = texts
+        .iter()
+        .map(|text| rename_links_in_text(text, &mut txn, old_key, new_title))
+        .sum::<usize>();
+    if count == 0 {
+        return Ok(None);
+    }
+    Ok(Some((txn.encode_state_as_update_v1(&before), count)))
+}
+
+fn collect_texts<F: XmlFragment, T: ReadTxn>(fragment: &F, txn: &T, texts: &mut Vec<XmlTextRef>) {
+    for child in fragment.children(txn) {
+        match child {
+            XmlOut::Element(element) => collect_texts(&element, txn, texts),
+            XmlOut::Fragment(fragment) => collect_texts(&fragment, txn, texts),
+            XmlOut::Text(text) => texts.push(text),
+        }
+    }
+}
+
+/// 書き換えるリンクの範囲（位置はバイト単位）
+struct LinkRun {
+    start: u32,
+    len: u32,
+    attrs: Attrs,
+    key: Arc<str>,
+    mark: TiptapMark,
+    variant: LinkVariant,
+}
+
+fn rename_links_in_text(
+    text: &XmlTextRef,
+    txn: &mut TransactionMut,
+    old_key: &str,
+    new_title: &str,
+) -> usize {
+    // 位置を先に求め、後ろから書き換えて位置がずれないようにする
+    let mut runs: Vec<LinkRun> = Vec::new();
+    let mut offset = 0;
+    let mut previous: Option<TiptapMark> = None;
+    for diff in text.diff(&*txn, YChange::identity) {
+        let len = match &diff.insert {
+            Out::Any(Any::String(chunk)) => chunk.len() as u32,
+            _ => 1,
+        };
+        let matched = diff.attributes.as_deref().and_then(|attrs| {
+            attrs.iter().find_map(|(key, value)| {
+                if key.split("--").next() != Some("unilink") {
+                    return None;
+                }
+                let mark = TiptapMark {
+                    mark_type: "unilink".to_string(),
+                    attrs: match any_to_json(value) {
+                        Value::Object(map) => Some(map),
+                        _ => None,
+                    },
+                };
+                let variant = link_matches(&mark, old_key)?;
+                Some((key.clone(), mark, variant, attrs.clone()))
+            })
+        });
+
+        match matched {
+            // 書式の途中で分割されたリンクの続き
+            Some((_, mark, _, _)) if previous.as_ref() == Some(&mark) => {
+                if let Some(run) = runs.last_mut() {
+                    run.len += len;
+                }
+            }
+            Some((key, mark, variant, attrs)) => {
+                runs.push(LinkRun {
+                    start: offset,
+                    len,
+                    attrs,
+                    key,
+                    mark: mark.clone(),
+                    variant,
+                });
+                previous = Some(mark);
+            }
+            None => previous = None,
+        }
+        offset += len;
+    }
+
+    for run in runs.iter().rev() {
+        let mark = retarget_link_mark(&run.mark, run.variant, new_title);
+        let mut attrs = run.attrs.clone();
+        attrs.insert(
+            run.key.clone(),
+            json_to_any(&Value::Object(mark.attrs.unwrap_or_default())),
+        );
+        text.remove_range(txn, run.start, run.len);
+        text.insert_with_attributes(txn, run.start, &link_text(run.variant, new_title), attrs);
+    }
+    runs.len()
+}
+
+// ----------------------------------------------------------------------------
+// Yjs → Tiptap
+// ----------------------------------------------------------------------------
+
+fn fragment_to_nodes<F: XmlFragment, T: ReadTxn>(fragment: &F, txn: &T) -> Vec<TiptapNode> {
+    let mut nodes = Vec::new();
+    for child in fragment.children(txn) {
+        match child {
+            XmlOut::Element(element) => nodes.push(element_to_node(&element, txn)),
+            XmlOut::Fragment(fragment) => nodes.extend(fragment_to_nodes(&fragment, txn)),
+            XmlOut::Text(text) => nodes.extend(text_to_nodes(&text, txn)),
+        }
+    }
+    nodes
+}
+
+fn element_to_node<T: ReadTxn>(element: &XmlElementRef, txn: &T) -> TiptapNode {
+    let mut node = TiptapNode::leaf(element.tag());
+
+    let mut attributes = element.attributes(txn).collect::<Vec<_>>();
+    attributes.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, value) in attributes {
+        let value = out_to_json(&value);
+        if !value.is_null() {
+            node = node.with_attr(name, value);
+        }
+    }
+
+    let children = fragment_to_nodes(element, txn);
+    if !children.is_empty() {
+        node.content = Some(children);
+    }
+    node
+}
+
+/// XmlText を書式ごとのテキストノードに分ける
+fn text_to_nodes<T: ReadTxn>(text: &XmlTextRef, txn: &T) -> Vec<TiptapNode> {
+    text.diff(txn, YChange::identity)
+        .into_iter()
+        .filter_map(|diff| {
+            let Out::Any(Any::String(chunk)) = &diff.insert else {
+                return None;
+            };
+            let marks = diff
+                .attributes
+                .map(|attrs| attrs_to_marks(&attrs))
+                .unwrap_or_default();
+            Some(TiptapNode::text(chunk, marks))
+        })
+        .collect()
+}
+
+/// 書式属性をマークに変換する
+///
+/// 重なりを許すマークは y-prosemirror が `名前--ハッシュ` というキーで保存する
+pub(crate) fn attrs_to_marks(attrs: &Attrs) -> Vec<TiptapMark> {
+    let mut entries = attrs
+        .iter()
+        .filter(|(_, value)| !matches!(value, Any::Null | Any::Undefined))
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+        .into_iter()
+        .map(|(key, value)| {
+            let mark_type = key.split("--").next().unwrap_or(key);
+            let attrs = match any_to_json(value) {
+                Value::Object(map) if !map.is_empty() => Some(map),
+                _ => None,
+            };
+            TiptapMark {
+                mark_type: mark_type.to_string(),
+                attrs,
+            }
+        })
+        .collect()
+}
+
+fn out_to_json(value: &Out) -> Value {
+    match value {
+        Out::Any(any) => any_to_json(any),
+        // ノードの属性に共有型は使わない
+        _ => Value::Null,
+    }
+}
+
+pub(crate) fn any_to_json(value: &Any) -> Value {
+    match value {
+        Any::Null | Any::Undefined => Value::Null,
+        Any::Bool(value) => Value::Bool(*value),
+        // 整数は整数のまま返す（見出しのレベルなど）
+        Any::Number(value) if value.fract() == 0.0 && value.abs() < i64::MAX as f64 => {
+            Value::from(*value as i64)
+        }
+        Any::Number(value) => Number::from_f64(*value).map_or(Value::Null, Value::Number),
+        Any::BigInt(value) => Value::from(*value),
+        Any::String(value) => Value::String(value.to_string()),
+        Any::Buffer(value) => Value::Array(value.iter().map(|b| Value::from(*b)).collect()),
+        Any::Array(values) => Value::Array(values.iter().map(any_to_json).collect()),
+        Any::Map(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), any_to_json(value)))
+                .collect::<Map<_, _>>(),
+        ),
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Tiptap → Yjs
+// ----------------------------------------------------------------------------
+
+pub(crate) fn json_to_any(value: &Value) -> Any {
+    match value {
+        Value::Null => Any::Null,
+        Value::Bool(value) => Any::Bool(*value),
+        Value::Number(value) => Any::Number(value.as_f64().unwrap_or_default()),
+        Value::String(value) => Any::String(Arc::from(value.as_str())),
+        Value::Array(values) => Any::Array(values.iter().map(json_to_any).collect()),
+        Value::Object(map) => Any::Map(Arc::new(
+            map.iter()
+                .map(|(key, value)| (key.clone(), json_to_any(value)))
+                .collect::<HashMap<_, _>>(),
+        )),
+    }
+}
+
+/// マークを書式属性に変換する
+pub(crate) fn marks_to_attrs(marks: &[TiptapMark]) -> Attrs {
+    marks
+        .iter()
+        .map(|mark| {
+            let attrs = mark
+                .attrs
+                .as_ref()
+                .map(|attrs| json_to_any(&Value::Object(attrs.clone())))
+                .unwrap_or_else(|| Any::Map(Arc::new(HashMap::new())));
+            (Arc::from(mark.mark_type.as_str()), attrs)
+        })
+        .collect()
+}
+
+fn write_nodes<F: XmlFragment>(parent: &F, txn: &mut TransactionMut, nodes: &[TiptapNode]) {
+    let mut index = 0;
+    // 連続するテキストは1つの XmlText に書き込む（位置はバイト単位）
+    let mut current_text: Option<(XmlTextRef, u32)> = None;
+
+    for node in nodes {
+        if node.is_text() {
+            let chunk = node.text.as_deref().unwrap_or_default();
+            if chunk.is_empty() {
+                continue;
+            }
+            let (text, offset) = current_text.get_or_insert_with(|| {
+                let text = parent.insert(txn, index, XmlTextPrelim::new(""));
+                index += 1;
+                (text, 0)
+            });
+            text.insert_with_attributes(txn, *offset, chunk, marks_to_attrs(node.mark_list()));
+            *offset += chunk.len() as u32;
+            continue;
+        }
+
+        current_text = None;
+        let element = parent.insert(txn, index, XmlElementPrelim::empty(node.node_type.as_str()));
+        index += 1;
+        if let Some(attrs) = &node.attrs {
+            for (name, value) in attrs {
+                if !value.is_null() {
+                    element.insert_attribute(txn, name.as_str(), json_to_any(value));
+                }
+            }
+        }
+        write_nodes(&element, txn, node.children());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::tiptap::bracket_link_mark;
+
+    #[test]
+    fn test_yjs_round_trip_keeps_marks_and_attrs() {
+        let doc = TiptapNode::new(
+            "doc",
+            vec![
+                TiptapNode::new("heading", vec![TiptapNode::text("見出し", vec![])])
+                    .with_attr("level", 2),
+                TiptapNode::new(
+                    "paragraph",
+                    vec![
+                        TiptapNode::text("see ", vec![]),
+                        TiptapNode::text("Rust", vec![bracket_link_mark("Rust")]),
+                        TiptapNode::leaf("hardBreak"),
+                        TiptapNode::text("bold", vec![TiptapMark::new("bold")]),
+                    ],
+                ),
+            ],
+        );
+
+        let update = tiptap_to_yjs_update(&doc);
+        let restored = yjs_update_to_tiptap(&update).unwrap();
+        assert_eq!(restored, doc);
+    }
+
+    #[test]
+    fn test_yjs_rename_links_appends_update() {
+        let doc = TiptapNode::new(
+            "doc",
+            vec![TiptapNode::new(
+                "paragraph",
+                vec![
+                    TiptapNode::text("see ", vec![]),
+                    TiptapNode::text("Old", vec![bracket_link_mark("Old")]),
+                    TiptapNode::text(" end", vec![]),
+                ],
+            )],
+        );
+        let update = tiptap_to_yjs_update(&doc);
+
+        let (diff, count) = rename_links_in_yjs(&update, "Old", "New Title")
+            .unwrap()
+            .unwrap();
+        assert_eq!(count, 1);
+        let merged = yrs::merge_updates_v1(&[&update, &diff]).unwrap();
+        let renamed = yjs_update_to_tiptap(&merged).unwrap();
+        let paragraph = &renamed.children()[0];
+        assert_eq!(paragraph.children()[1].text.as_deref(), Some("New Title"));
+        assert_eq!(paragraph.children()[2].text.as_deref(), Some(" end"));
+
+        assert!(rename_links_in_yjs(&update, "Missing", "New")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_yjs_empty_update_is_empty_document() {
+        let restored = yjs_update_to_tiptap(&[0, 0]).unwrap();
+        assert_eq!(restored, TiptapNode::new("doc", vec![]));
+    }
+}
@@ -0,0 +1,692 @@
+//! カードノートと兄弟カードの生成
+//!
+//! 1つの元テキスト（カードノート）から複数のカード（兄弟カード）を生成する。
+//! 穴埋めカードでは `{{c1::...}}` の番号ごとに1枚のカードを作る。
+//! 表裏のカードでは1つの項目（表・裏）から順方向（番号 1）・逆方向（番号 2）のカードを作る。
+//! 元テキストを変更したときは番号で既存のカードと突き合わせ、残った番号のカードは
+//! 表裏の内容だけを書き換えて復習のスケジュールと学習ログを引き継ぐ。
+//! 増えた番号のカードは新規に作成し、無くなった番号のカードは論理削除する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/card_notes_commands.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/content/cloze.rs
+//!   └─ src-tauri/src/db/history.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::history::{self, HistoryEntity, RevisionSource};
+use super::journal::JournalTx;
+use super::{CardNoteUpdate, DbError, LocalCard, LocalCardNote, LocalDB, CARD_COLUMNS};
+use crate::content::{
+    cloze_ordinals, plain_text_to_tiptap, render_cloze_back, render_cloze_front, TiptapNode,
+};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+const CARD_NOTE_COLUMNS: &str = "id, deck_id, user_id, kind, source_text, extra,
+    created_at, updated_at, sync_status, synced_at, local_updated_at, server_updated_at,
+    back_text";
+
+/// 順方向（表 → 裏）のカードの番号
+const FORWARD_ORDINAL: i32 = 1;
+/// 逆方向（裏 → 表）のカードの番号
+const REVERSE_ORDINAL: i32 = 2;
+
+/// カードノートの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CardNoteKind {
+    /// 穴埋め（番号ごとに1枚）
+    Cloze,
+    /// 表 → 裏の1枚
+    Forward,
+    /// 裏 → 表の1枚
+    Reverse,
+    /// 表 → 裏と裏 → 表の2枚
+    Bidirectional,
+}
+
+impl CardNoteKind {
+    /// `card_notes.kind` に保存する値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CardNoteKind::Cloze => "cloze",
+            CardNoteKind::Forward => "forward",
+            CardNoteKind::Reverse => "reverse",
+            CardNoteKind::Bidirectional => "bidirectional",
+        }
+    }
+
+    /// 保存された値から復元
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "cloze" => Some(CardNoteKind::Cloze),
+            "forward" => Some(CardNoteKind::Forward),
+            "reverse" => Some(CardNoteKind::Reverse),
+            "bidirectional" => Some(CardNoteKind::Bidirectional),
+            _ => None,
+        }
+    }
+
+    /// 表裏の入力欄から生成する種類か
+    pub fn is_directional(&self) -> bool {
+        !matches!(self, CardNoteKind::Cloze)
+    }
+}
+
+/// 保存された種類を読む
+fn parse_kind(value: &str) -> Result<CardNoteKind, DbError> {
+    CardNoteKind::parse(value)
+        .ok_or_else(|| DbError::ValidationError(format!("Unknown card note kind: {}", value)))
+}
+
+/// カードノートと兄弟カード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardNoteWithCards {
+    pub note: LocalCardNote,
+    /// 番号順の兄弟カード（削除済みを除く）
+    pub cards: Vec<LocalCard>,
+}
+
+/// 元テキストから生成するカード
+#[derive(Debug, Clone, PartialEq)]
+struct GeneratedCard {
+    ordinal: i32,
+    /// `cards.card_kind` に保存する値
+    card_kind: &'static str,
+    front_content: String,
+    back_content: String,
+}
+
+impl GeneratedCard {
+    fn new(
+        ordinal: i32,
+        card_kind: &'static str,
+        front: &TiptapNode,
+        back: &TiptapNode,
+    ) -> Result<Self, DbError> {
+        Ok(Self {
+            ordinal,
+            card_kind,
+            front_content: serde_json::to_string(front)?,
+            back_content: serde_json::to_string(back)?,
+        })
+    }
+}
+
+/// 裏面の末尾に補足の段落を追加する
+fn append_extra(mut doc: TiptapNode, extra: Option<&str>) -> TiptapNode {
+    let Some(extra) = extra.filter(|extra| !extra.trim().is_empty()) else {
+        return doc;
+    };
+    let paragraphs = plain_text_to_tiptap(extra).content.unwrap_or_default();
+    doc.content.get_or_insert_with(Vec::new).extend(paragraphs);
+    doc
+}
+
+/// カードノートから生成するカードを求める（番号順）
+fn generate_cards(note: &LocalCardNote) -> Result<Vec<GeneratedCard>, DbError> {
+    let kind = parse_kind(&note.kind)?;
+    let extra = note.extra.as_deref();
+    if kind == CardNoteKind::Cloze {
+        let ordinals = cloze_ordinals(&note.source_text);
+        if ordinals.is_empty() {
+            return Err(DbError::ValidationError(
+                "Cloze text must contain at least one {{c1::...}} deletion".to_string(),
+            ));
+        }
+        return ordinals
+            .into_iter()
+            .map(|ordinal| {
+                GeneratedCard::new(
+                    ordinal as i32,
+                    kind.as_str(),
+                    &render_cloze_front(&note.source_text, ordinal),
+                    &append_extra(render_cloze_back(&note.source_text, ordinal), extra),
+                )
+            })
+            .collect();
+    }
+
+    let back_text = note.back_text.as_deref().unwrap_or_default();
+    if note.source_text.trim().is_empty() || back_text.trim().is_empty() {
+        return Err(DbError::ValidationError(
+            "Both the front and back of the entry are required".to_string(),
+        ));
+    }
+    let front = plain_text_to_tiptap(&note.source_text);
+    let back = plain_text_to_tiptap(back_text);
+    let mut cards = Vec::new();
+    if matches!(kind, CardNoteKind::Forward | CardNoteKind::Bidirectional) {
+        cards.push(GeneratedCard::new(
+            FORWARD_ORDINAL,
+            "forward",
+            &front,
+            &append_extra(back.clone(), extra),
+        )?);
+    }
+    if matches!(kind, CardNoteKind::Reverse | CardNoteKind::Bidirectional) {
+        cards.push(GeneratedCard::new(
+            REVERSE_ORDINAL,
+            "reverse",
+            &back,
+            &append_extra(front, extra),
+        )?);
+    }
+    Ok(cards)
+}
+
+/// 既存の兄弟カードと生成するカードの突き合わせ結果
+#[derive(Debug, Default)]
+struct SiblingPlan {
+    /// 番号が残ったカード（内容だけを書き換える）
+    keep: Vec<(LocalCard, GeneratedCard)>,
+    /// 新しい番号のカード（作成するカードのIDと内容）
+    add: Vec<(String, GeneratedCard)>,
+    /// 番号が無くなったカード（論理削除する）
+    remove: Vec<LocalCard>,
+}
+
+impl SiblingPlan {
+    /// 変更の対象になるカードをジャーナルの対象にする
+    fn track(&self, tx: &JournalTx<'_>) -> Result<(), DbError> {
+        let ids = self
+            .keep
+            .iter()
+            .map(|(card, _)| card.id.as_str())
+            .chain(self.add.iter().map(|(id, _)| id.as_str()))
+            .chain(self.remove.iter().map(|card| card.id.as_str()));
+        for id in ids {
+            tx.track("cards", id)?;
+        }
+        Ok(())
+    }
+}
+
+/// 番号で既存の兄弟カードと突き合わせる（同じ番号が重複した場合は古い方を残す）
+fn plan_siblings(mut existing: Vec<LocalCard>, generated: Vec<GeneratedCard>) -> SiblingPlan {
+    let mut plan = SiblingPlan::default();
+    for generated in generated {
+        match existing
+            .iter()
+            .position(|card| card.ordinal == Some(generated.ordinal))
+        {
+            Some(index) => plan.keep.push((existing.remove(index), generated)),
+            None => plan.add.push((uuid::Uuid::new_v4().to_string(), generated)),
+        }
+    }
+    plan.remove = existing;
+    plan
+}
+
+/// IDでカードノートを取得
+fn find_card_note(conn: &Connection, id: &str) -> Result<Option<LocalCardNote>, DbError> {
+    let note = conn
+        .query_row(
+            &format!("SELECT {} FROM card_notes WHERE id = ?1", CARD_NOTE_COLUMNS),
+            [id],
+            LocalCardNote::from_row,
+        )
+        .optional()?;
+    Ok(note)
+}
+
+/// カードノートの兄弟カード（削除済みを除く、番号順・作成順）を取得
+fn sibling_cards(conn: &Connection, card_note_id: &str) -> Result<Vec<LocalCard>, DbError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM cards
+         WHERE card_note_id = ?1 AND sync_status != 'deleted'
+         ORDER BY ordinal, created_at",
+        CARD_COLUMNS
+    ))?;
+    let cards = stmt
+        .query_map([card_note_id], LocalCard::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(cards)
+}
+
+/// 突き合わせ結果を反映する
+///
+/// 番号が残ったカードは内容が変わる場合だけ変更前の内容を履歴に残し、
+/// スケジュールの項目には触れない。
+fn apply_sibling_plan(
+    conn: &Connection,
+    note: &LocalCardNote,
+    plan: SiblingPlan,
+    now: &str,
+) -> Result<(), DbError> {
+    for (card, generated) in plan.keep {
+        if card.front_content == generated.front_content
+            && card.back_content == generated.back_content
+        {
+            continue;
+        }
+        history::record_revision(
+            conn,
+            HistoryEntity::Card,
+            &card.id,
+            &card,
+            RevisionSource::LocalEdit,
+            Some(&card.user_id),
+        )?;
+        conn.execute(
+            r#"
+            UPDATE cards SET
+                front_content = ?1, back_content = ?2,
+                updated_at = ?3, local_updated_at = ?3, sync_status = 'pending'
+            WHERE id = ?4
+            "#,
+            rusqlite::params![
+                generated.front_content,
+                generated.back_content,
+                now,
+                card.id
+            ],
+        )?;
+    }
+
+    for (id, generated) in plan.add {
+        conn.execute(
+            r#"
+            INSERT INTO cards (
+                id, deck_id, user_id, front_content, back_content,
+                created_at, updated_at, local_updated_at, sync_status,
+                card_kind, card_note_id, ordinal
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6, ?6, 'pending', ?7, ?8, ?9)
+            "#,
+            rusqlite::params![
+                id,
+                note.deck_id,
+                note.user_id,
+                generated.front_content,
+                generated.back_content,
+                now,
+                generated.card_kind,
+                note.id,
+                generated.ordinal,
+            ],
+        )?;
+    }
+
+    for card in plan.remove {
+        conn.execute(
+            "UPDATE cards SET sync_status = 'deleted', local_updated_at = ?1 WHERE id = ?2",
+            rusqlite::params![now, card.id],
+        )?;
+    }
+    Ok(())
+}
+
+impl LocalDB {
+    /// IDでカードノートを取得
+    pub fn get_card_note(&self, id: &str) -> Result<Option<LocalCardNote>, DbError> {
+        let conn = self.connection()?;
+        find_card_note(&conn, id)
+    }
+
+    /// デッキのカードノートを取得
+    pub fn get_card_notes_by_deck(&self, deck_id: &str) -> Result<Vec<LocalCardNote>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM card_notes
+             WHERE deck_id = ?1 AND sync_status != 'deleted'
+             ORDER BY created_at DESC",
+            CARD_NOTE_COLUMNS
+        ))?;
+        let notes = stmt
+            .query_map([deck_id], LocalCardNote::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(notes)
+    }
+
+    /// カードノートの兄弟カードを取得
+    pub fn get_card_note_cards(&self, card_note_id: &str) -> Result<Vec<LocalCard>, DbError> {
+        let conn = self.connection()?;
+        sibling_cards(&conn, card_note_id)
+    }
+
+    /// カードノートを作成し、兄弟カードを生成する
+    ///
+    /// 穴埋めでは `source_text` だけを、表裏のカードでは `source_text`（表）と
+    /// `back_text`（裏）を使う。
+    pub fn create_card_note(
+        &self,
+        deck_id: &str,
+        user_id: &str,
+        kind: CardNoteKind,
+        source_text: &str,
+        back_text: Option<&str>,
+        extra: Option<&str>,
+    ) -> Result<CardNoteWithCards, DbError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let note = LocalCardNote {
+            id: uuid::Uuid::new_v4().to_string(),
+            deck_id: deck_id.to_string(),
+            user_id: user_id.to_string(),
+            kind: kind.as_str().to_string(),
+            source_text: source_text.to_string(),
+            back_text: back_text.map(str::to_string),
+            extra: extra.map(str::to_string),
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            sync_status: "pending".to_string(),
+            synced_at: None,
+            local_updated_at: now.clone(),
+            server_updated_at: None,
+        };
+        let plan = plan_siblings(Vec::new(), generate_cards(&note)?);
+        let note_id = note.id.clone();
+
+        self.with_journal("create_card_note", &[("card_notes", &note_id)], |tx| {
+            let deck_exists = tx
+                .query_row(
+                    "SELECT 1 FROM decks WHERE id = ?1 AND sync_status != 'deleted'",
+                    [deck_id],
+                    |_| Ok(()),
+                )
+                .optional()?
+                .is_some();
+            if !deck_exists {
+                return Err(DbError::NotFound(format!("Deck {}", deck_id)));
+            }
+
+            plan.track(tx)?;
+            tx.execute(
+                r#"
+                INSERT INTO card_notes (
+                    id, deck_id, user_id, kind, source_text, extra,
+                    created_at, updated_at,
+                    sync_status, synced_at, local_updated_at, server_updated_at, back_text
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+                "#,
+                rusqlite::params![
+                    note.id,
+                    note.deck_id,
+                    note.user_id,
+                    note.kind,
+                    note.source_text,
+                    note.extra,
+                    note.created_at,
+                    note.updated_at,
+                    note.sync_status,
+                    note.synced_at,
+                    note.local_updated_at,
+                    note.server_updated_at,
+                    note.back_text,
+                ],
+            )?;
+            apply_sibling_plan(tx, &note, plan, &now)?;
+            let cards = sibling_cards(tx, &note.id)?;
+            Ok(CardNoteWithCards { note, cards })
+        })
+    }
+
+    /// カードノートを更新し、兄弟カードを作り直す
+    ///
+    /// 向きを変えた場合も、残った向きのカードは復習のスケジュールを引き継ぐ。
+    pub fn update_card_note(
+        &self,
+        id: &str,
+        updates: CardNoteUpdate,
+    ) -> Result<Option<CardNoteWithCards>, DbError> {
+        self.with_journal("update_card_note", &[("card_notes", id)], |tx| {
+            let Some(current) =
+                find_card_note(tx, id)?.filter(|note| note.sync_status != "deleted")
+            else {
+                return Ok(None);
+            };
+
+            let kind = match updates.kind {
+                Some(kind) => {
+                    let (from, to) = (parse_kind(&current.kind)?, parse_kind(&kind)?);
+                    if from.is_directional() != to.is_directional() {
+                        return Err(DbError::ValidationError(format!(
+                            "Cannot change card note kind from {} to {}",
+                            current.kind, kind
+                        )));
+                    }
+                    kind
+                }
+                None => current.kind.clone(),
+            };
+
+            let now = chrono::Utc::now().to_rfc3339();
+            let updated = LocalCardNote {
+                kind,
+                source_text: updates.source_text.unwrap_or(current.source_text),
+                back_text: updates.back_text.unwrap_or(current.back_text),
+                extra: updates.extra.unwrap_or(current.extra),
+                updated_at: now.clone(),
+                local_updated_at: now.clone(),
+                sync_status: "pending".to_string(),
+                ..current
+            };
+            let plan = plan_siblings(sibling_cards(tx, id)?, generate_cards(&updated)?);
+            plan.track(tx)?;
+
+            tx.execute(
+                r#"
+                UPDATE card_notes SET
+                    kind = ?1, source_text = ?2, back_text = ?3, extra = ?4,
+                    updated_at = ?5, local_updated_at = ?6, sync_status = ?7
+                WHERE id = ?8
+                "#,
+                rusqlite::params![
+                    updated.kind,
+                    updated.source_text,
+                    updated.back_text,
+                    updated.extra,
+                    updated.updated_at,
+                    updated.local_updated_at,
+                    updated.sync_status,
+                    id,
+                ],
+            )?;
+            apply_sibling_plan(tx, &updated, plan, &now)?;
+            let cards = sibling_cards(tx, id)?;
+            Ok(Some(CardNoteWithCards {
+                note: updated,
+                cards,
+            }))
+        })
+    }
+
+    /// カードノートと兄弟カードを削除（論理削除）
+    pub fn delete_card_note(&self, id: &str) -> Result<bool, DbError> {
+        self.with_journal("delete_card_note", &[("card_notes", id)], |tx| {
+            for card in sibling_cards(tx, id)? {
+                tx.track("cards", &card.id)?;
+            }
+
+            let now = chrono::Utc::now().to_rfc3339();
+            let rows_affected = tx.execute(
+                "UPDATE card_notes SET sync_status = 'deleted', local_updated_at = ?1
+                 WHERE id = ?2 AND sync_status != 'deleted'",
+                rusqlite::params![now, id],
+            )?;
+            tx.execute(
+                "UPDATE cards SET sync_status = 'deleted', local_updated_at = ?1
+                 WHERE card_note_id = ?2 AND sync_status != 'deleted'",
+                rusqlite::params![now, id],
+            )?;
+            Ok(rows_affected > 0)
+        })
+    }
+
+    /// 同期待ちのカードノートを取得（削除したカードノートも含む）
+    pub fn get_pending_sync_card_notes(&self) -> Result<Vec<LocalCardNote>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM card_notes WHERE sync_status IN ('pending', 'deleted')",
+            CARD_NOTE_COLUMNS
+        ))?;
+        let notes = stmt
+            .query_map([], LocalCardNote::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(notes)
+    }
+
+    /// カードノートを同期済みにマーク（削除したカードノートは行を消す）
+    pub fn mark_card_note_synced(&self, id: &str, server_updated_at: &str) -> Result<(), DbError> {
+        let conn = self.connection()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "DELETE FROM card_notes WHERE id = ?1 AND sync_status = 'deleted'",
+            [id],
+        )?;
+        conn.execute(
+            "UPDATE card_notes SET sync_status = 'synced', synced_at = ?1, server_updated_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![now, server_updated_at, id],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures;
+    use tempfile::TempDir;
+
+    fn setup() -> (LocalDB, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        (db, temp_dir)
+    }
+
+    #[test]
+    fn test_create_cloze_note_generates_sibling_per_ordinal() {
+        let (db, _temp_dir) = setup();
+        let created = db
+            .create_card_note(
+                "deck-1",
+                "user-1",
+                CardNoteKind::Cloze,
+                "{{c1::東京::首都}}は{{c2::日本}}の{{c1::都市}}",
+                None,
+                Some("人口は約1400万人"),
+            )
+            .unwrap();
+
+        let ordinals: Vec<_> = created.cards.iter().map(|card| card.ordinal).collect();
+        assert_eq!(ordinals, vec![Some(1), Some(2)]);
+        assert!(created.cards.iter().all(|card| card.card_kind == "cloze"
+            && card.card_note_id.as_deref() == Some(created.note.id.as_str())));
+        assert!(created.cards[0].front_content.contains("[首都]"));
+        assert!(created.cards[0].back_content.contains("人口は約1400万人"));
+
+        assert!(matches!(
+            db.create_card_note(
+                "deck-1",
+                "user-1",
+                CardNoteKind::Cloze,
+                "穴埋め無し",
+                None,
+                None
+            ),
+            Err(DbError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_update_cloze_note_preserves_schedule_of_remaining_ordinals() {
+        let (db, _temp_dir) = setup();
+        let created = db
+            .create_card_note(
+                "deck-1",
+                "user-1",
+                CardNoteKind::Cloze,
+                "{{c1::東京}}は{{c2::日本}}の首都",
+                None,
+                None,
+            )
+            .unwrap();
+        let first = created.cards[0].clone();
+        let second = created.cards[1].clone();
+        let reviewed = db.review_card(&first.id, 5, "flashcard").unwrap();
+
+        let updated = db
+            .update_card_note(
+                &created.note.id,
+                CardNoteUpdate {
+                    source_text: Some("{{c1::東京}}は{{c3::日本国}}の首都".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .unwrap();
+
+        let ordinals: Vec<_> = updated.cards.iter().map(|card| card.ordinal).collect();
+        assert_eq!(ordinals, vec![Some(1), Some(3)]);
+        let kept = &updated.cards[0];
+        assert_eq!(kept.id, first.id);
+        assert_eq!(kept.next_review_at, reviewed.card.next_review_at);
+        assert_eq!(kept.repetition_count, reviewed.card.repetition_count);
+        assert!(kept.front_content.contains("日本国"));
+
+        let removed = db.get_card_by_id(&second.id).unwrap().unwrap();
+        assert_eq!(removed.sync_status, "deleted");
+        assert!(updated.cards.iter().all(|card| card.id != second.id));
+    }
+
+    #[test]
+    fn test_bidirectional_note_updates_both_cards_and_buries_sibling() {
+        let (db, _temp_dir) = setup();
+        let created = db
+            .create_card_note(
+                "deck-1",
+                "user-1",
+                CardNoteKind::Bidirectional,
+                "apple",
+                Some("りんご"),
+                None,
+            )
+            .unwrap();
+        let kinds: Vec<_> = created
+            .cards
+            .iter()
+            .map(|card| card.card_kind.as_str())
+            .collect();
+        assert_eq!(kinds, vec!["forward", "reverse"]);
+        assert!(created.cards[1].front_content.contains("りんご"));
+
+        // 新規の兄弟カードは1枚だけ出題し、復習した後はもう1枚を翌日に延期する
+        assert_eq!(db.get_study_cards("deck-1").unwrap().len(), 1);
+        db.review_card(&created.cards[0].id, 4, "flashcard")
+            .unwrap();
+        assert!(db.get_study_cards("deck-1").unwrap().is_empty());
+
+        let updated = db
+            .update_card_note(
+                &created.note.id,
+                CardNoteUpdate {
+                    kind: Some("forward".to_string()),
+                    back_text: Some(Some("林檎".to_string())),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.cards.len(), 1);
+        assert_eq!(updated.cards[0].id, created.cards[0].id);
+        assert!(updated.cards[0].back_content.contains("林檎"));
+
+        assert!(matches!(
+            db.update_card_note(
+                &created.note.id,
+                CardNoteUpdate {
+                    kind: Some("cloze".to_string()),
+                    ..Default::default()
+                },
+            ),
+            Err(DbError::ValidationError(_))
+        ));
+    }
+}
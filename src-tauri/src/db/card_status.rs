@@ -0,0 +1,185 @@
+//! カードの一時停止・延期・フラグ
+//!
+//! カードを削除せずに出題から外すための状態を変更する。
+//!
+//! - 一時停止（`is_suspended`）は解除するまで復習・学習に出題しない
+//! - 延期（`buried_until`）は次の学習日の始まりまで出題せず、日付が変わると自動的に戻る
+//! - フラグ（`flag`）は出題には影響しない色の目印
+//!
+//! いずれも複数のカードをまとめて変更でき、同期の対象になる。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/cards_commands.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/db/journal.rs
+//!   └─ src-tauri/src/db/review.rs (学習日の境界)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::review::next_day_start;
+use super::{DbError, LocalDB};
+use rusqlite::ToSql;
+
+/// カードに付けられる色のフラグ
+pub const CARD_FLAGS: [&str; 7] = [
+    "red",
+    "orange",
+    "green",
+    "blue",
+    "pink",
+    "turquoise",
+    "purple",
+];
+
+impl LocalDB {
+    /// カードの状態の列をまとめて変更する（値が変わったカードの数を返す）
+    fn update_card_state(
+        &self,
+        operation: &str,
+        ids: &[String],
+        column: &str,
+        value: &dyn ToSql,
+    ) -> Result<usize, DbError> {
+        let targets: Vec<(&str, &str)> = ids.iter().map(|id| ("cards", id.as_str())).collect();
+        self.with_journal(operation, &targets, |tx| {
+            let now = chrono::Utc::now().to_rfc3339();
+            let mut changed = 0;
+            {
+                let mut stmt = tx.prepare(&format!(
+                    r#"
+                    UPDATE cards SET
+                        {column} = ?1,
+                        updated_at = ?2, local_updated_at = ?2, sync_status = 'pending'
+                    WHERE id = ?3 AND sync_status != 'deleted' AND {column} IS NOT ?1
+                    "#
+                ))?;
+                for id in ids {
+                    changed += stmt.execute(rusqlite::params![value, now, id])?;
+                }
+            }
+            Ok(changed)
+        })
+    }
+
+    /// カードを一時停止する
+    pub fn suspend_cards(&self, ids: &[String]) -> Result<usize, DbError> {
+        self.update_card_state("suspend_cards", ids, "is_suspended", &true)
+    }
+
+    /// カードの一時停止を解除する
+    pub fn unsuspend_cards(&self, ids: &[String]) -> Result<usize, DbError> {
+        self.update_card_state("unsuspend_cards", ids, "is_suspended", &false)
+    }
+
+    /// カードを次の学習日まで延期する
+    pub fn bury_cards(&self, ids: &[String]) -> Result<usize, DbError> {
+        let until = next_day_start();
+        self.update_card_state("bury_cards", ids, "buried_until", &until)
+    }
+
+    /// カードの延期を取り消す
+    pub fn unbury_cards(&self, ids: &[String]) -> Result<usize, DbError> {
+        self.update_card_state("unbury_cards", ids, "buried_until", &None::<String>)
+    }
+
+    /// カードのフラグを変更する（None で外す）
+    pub fn set_card_flags(&self, ids: &[String], flag: Option<&str>) -> Result<usize, DbError> {
+        if let Some(flag) = flag.filter(|flag| !CARD_FLAGS.contains(flag)) {
+            return Err(DbError::ValidationError(format!(
+                "Unknown card flag: {}",
+                flag
+            )));
+        }
+        self.update_card_state("set_card_flags", ids, "flag", &flag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{fixtures, LocalCard};
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, LocalDB) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        let yesterday = (chrono::Utc::now() - chrono::Duration::days(1)).to_rfc3339();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        for id in ["a", "b", "c"] {
+            db.insert_card(&LocalCard {
+                repetition_count: 1,
+                review_interval: 1,
+                next_review_at: Some(yesterday.clone()),
+                last_reviewed_at: Some(yesterday.clone()),
+                ..fixtures::card(id, "deck-1")
+            })
+            .unwrap();
+        }
+        (temp_dir, db)
+    }
+
+    fn due_ids(db: &LocalDB) -> Vec<String> {
+        let mut ids: Vec<String> = db
+            .get_due_cards("user-1")
+            .unwrap()
+            .into_iter()
+            .map(|card| card.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_suspended_and_buried_cards_are_not_studied_until_released() {
+        let (_temp_dir, db) = setup();
+
+        assert_eq!(db.suspend_cards(&["a".to_string()]).unwrap(), 1);
+        assert_eq!(db.suspend_cards(&["a".to_string()]).unwrap(), 0);
+        assert_eq!(db.bury_cards(&["b".to_string()]).unwrap(), 1);
+        assert_eq!(due_ids(&db), vec!["c"]);
+        let study: Vec<String> = db
+            .get_study_cards("deck-1")
+            .unwrap()
+            .into_iter()
+            .map(|card| card.id)
+            .collect();
+        assert_eq!(study, vec!["c"]);
+
+        let buried = db.get_card_by_id("b").unwrap().unwrap();
+        assert_eq!(buried.sync_status, "pending");
+        assert!(buried.buried_until.unwrap() > chrono::Utc::now().to_rfc3339());
+
+        // 次の学習日になると延期は自動的に終わる
+        let past = (chrono::Utc::now() - chrono::Duration::minutes(1)).to_rfc3339();
+        db.connection()
+            .unwrap()
+            .execute("UPDATE cards SET buried_until = ?1 WHERE id = 'b'", [&past])
+            .unwrap();
+        assert_eq!(due_ids(&db), vec!["b", "c"]);
+
+        assert_eq!(db.unsuspend_cards(&["a".to_string()]).unwrap(), 1);
+        assert_eq!(due_ids(&db), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_set_card_flags_validates_colour() {
+        let (_temp_dir, db) = setup();
+        let ids = vec!["a".to_string(), "b".to_string()];
+
+        assert_eq!(db.set_card_flags(&ids, Some("red")).unwrap(), 2);
+        assert_eq!(
+            db.get_card_by_id("a").unwrap().unwrap().flag.as_deref(),
+            Some("red")
+        );
+        assert!(matches!(
+            db.set_card_flags(&ids, Some("gold")),
+            Err(DbError::ValidationError(_))
+        ));
+        assert_eq!(db.set_card_flags(&ids, None).unwrap(), 2);
+        assert_eq!(db.get_card_by_id("b").unwrap().unwrap().flag, None);
+    }
+}
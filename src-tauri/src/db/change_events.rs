@@ -0,0 +1,416 @@
+//! DB変更イベント
+//!
+//! SQLiteの update / commit / rollback フックで変更行を収集し、
+//! コミット後にまとめて（デバウンスして）通知する。
+//! フロントエンドはポーリングの代わりに `db://changed` イベントを購読し、
+//! `subscribe_query` で登録したクエリのうち影響を受けたものだけを再実行する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   ├─ src-tauri/src/commands/change_events_commands.rs
+//!   └─ src-tauri/src/lib.rs (イベント送信)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{DbError, LocalDB};
+use rusqlite::hooks::Action;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+/// 変更通知のイベント名
+pub const DB_CHANGED_EVENT: &str = "db://changed";
+
+/// 最後の変更からこの時間だけ待って通知をまとめる
+const DEBOUNCE_WINDOW: Duration = Duration::from_millis(100);
+
+/// 変更が続いても、最初の変更からこの時間が経過したら通知する
+const MAX_DEBOUNCE_DELAY: Duration = Duration::from_millis(500);
+
+/// 変更の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeOperation {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// テーブル・操作ごとにまとめた変更
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableChange {
+    pub table: String,
+    pub operation: ChangeOperation,
+    /// SQLiteの rowid
+    pub row_ids: Vec<i64>,
+    /// 主キー（id）。削除された行は解決できないため含まれない
+    pub ids: Vec<String>,
+}
+
+/// 影響を受けた購読クエリ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvalidatedQuery {
+    pub subscription_id: String,
+    pub query_key: String,
+}
+
+/// `db://changed` イベントのペイロード
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbChangedEvent {
+    pub changes: Vec<TableChange>,
+    pub invalidated_queries: Vec<InvalidatedQuery>,
+}
+
+/// 購読中のクエリ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuerySubscription {
+    pub subscription_id: String,
+    /// フロントエンド側でクエリを識別するキー
+    pub query_key: String,
+    /// クエリが参照するテーブル
+    pub tables: Vec<String>,
+    /// 特定の行だけを参照する場合のID（None なら全行）
+    pub ids: Option<Vec<String>>,
+}
+
+/// フックから受け取った生の変更
+#[derive(Debug, Clone)]
+struct RawChange {
+    generation: u64,
+    table: String,
+    operation: ChangeOperation,
+    row_id: i64,
+}
+
+/// 変更の収集とクエリ購読の管理
+#[derive(Default)]
+pub(crate) struct ChangeTracker {
+    /// コミット前の変更
+    pending: Mutex<Vec<RawChange>>,
+    /// 通知スレッドへの送信口（通知開始前は None）
+    sender: Mutex<Option<mpsc::Sender<Vec<RawChange>>>>,
+    /// 接続ごとの世代（プロファイル切り替え前の変更を捨てるため）
+    generation: AtomicU64,
+    subscriptions: Mutex<HashMap<String, QuerySubscription>>,
+}
+
+impl ChangeTracker {
+    /// 接続にフックを登録する
+    pub(crate) fn install_hooks(self: &Arc<Self>, conn: &Connection) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+
+        let tracker = Arc::clone(self);
+        conn.update_hook(Some(
+            move |action: Action, _db: &str, table: &str, row_id: i64| {
+                let operation = match action {
+                    Action::SQLITE_INSERT => ChangeOperation::Insert,
+                    Action::SQLITE_UPDATE => ChangeOperation::Update,
+                    Action::SQLITE_DELETE => ChangeOperation::Delete,
+                    _ => return,
+                };
+                if table.starts_with("sqlite_") || table.starts_with('_') {
+                    return;
+                }
+                if let Ok(mut pending) = tracker.pending.lock() {
+                    pending.push(RawChange {
+                        generation,
+                        table: table.to_string(),
+                        operation,
+                        row_id,
+                    });
+                }
+            },
+        ));
+
+        let tracker = Arc::clone(self);
+        conn.commit_hook(Some(move || {
+            tracker.flush_pending();
+            // false を返すとコミットを続行する
+            false
+        }));
+
+        let tracker = Arc::clone(self);
+        conn.rollback_hook(Some(move || {
+            if let Ok(mut pending) = tracker.pending.lock() {
+                pending.clear();
+            }
+        }));
+    }
+
+    /// コミットされた変更を通知スレッドに送る
+    fn flush_pending(&self) {
+        let changes = match self.pending.lock() {
+            Ok(mut pending) if !pending.is_empty() => std::mem::take(&mut *pending),
+            _ => return,
+        };
+        if let Ok(sender) = self.sender.lock() {
+            if let Some(sender) = sender.as_ref() {
+                let _ = sender.send(changes);
+            }
+        }
+    }
+
+    /// 生の変更をイベントに変換する
+    fn build_event(&self, conn: &Connection, batch: Vec<RawChange>) -> DbChangedEvent {
+        let current = self.generation.load(Ordering::SeqCst);
+
+        let mut grouped: Vec<(String, ChangeOperation, BTreeSet<i64>)> = Vec::new();
+        for change in batch.into_iter().filter(|c| c.generation == current) {
+            match grouped
+                .iter_mut()
+                .find(|(table, op, _)| *table == change.table && *op == change.operation)
+            {
+                Some((_, _, row_ids)) => {
+                    row_ids.insert(change.row_id);
+                }
+                None => grouped.push((
+                    change.table,
+                    change.operation,
+                    BTreeSet::from([change.row_id]),
+                )),
+            }
+        }
+
+        let changes = grouped
+            .into_iter()
+            .map(|(table, operation, row_ids)| {
+                let ids = if operation == ChangeOperation::Delete {
+                    Vec::new()
+                } else {
+                    resolve_ids(conn, &table, &row_ids)
+                };
+                TableChange {
+                    table,
+                    operation,
+                    row_ids: row_ids.into_iter().collect(),
+                    ids,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let invalidated_queries = self
+            .subscriptions
+            .lock()
+            .map(|subscriptions| {
+                subscriptions
+                    .values()
+                    .filter(|subscription| is_affected(subscription, &changes))
+                    .map(|subscription| InvalidatedQuery {
+                        subscription_id: subscription.subscription_id.clone(),
+                        query_key: subscription.query_key.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        DbChangedEvent {
+            changes,
+            invalidated_queries,
+        }
+    }
+}
+
+/// rowid から主キーを解決する（id カラムが無いテーブルは空）
+fn resolve_ids(conn: &Connection, table: &str, row_ids: &BTreeSet<i64>) -> Vec<String> {
+    let Ok(mut stmt) = conn.prepare(&format!("SELECT id FROM {} WHERE rowid = ?1", table)) else {
+        return Vec::new();
+    };
+    row_ids
+        .iter()
+        .filter_map(|row_id| stmt.query_row([row_id], |row| row.get(0)).ok())
+        .collect()
+}
+
+/// 購読クエリが変更の影響を受けるか
+fn is_affected(subscription: &QuerySubscription, changes: &[TableChange]) -> bool {
+    changes.iter().any(|change| {
+        if !subscription.tables.contains(&change.table) {
+            return false;
+        }
+        match &subscription.ids {
+            None => true,
+            // 削除は主キーが解決できないため、同じテーブルなら無効化する
+            Some(_) if change.operation == ChangeOperation::Delete => true,
+            Some(ids) => change.ids.iter().any(|id| ids.contains(id)),
+        }
+    })
+}
+
+impl LocalDB {
+    /// 変更通知を開始する
+    ///
+    /// コミットされた変更をデバウンスしてから `listener` に渡す。
+    /// Tauri側では `db://changed` イベントとして送信する（lib.rs）。
+    pub fn start_change_events<F>(&self, listener: F) -> Result<(), DbError>
+    where
+        F: Fn(DbChangedEvent) + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel::<Vec<RawChange>>();
+        {
+            let mut current = self
+                .changes
+                .sender
+                .lock()
+                .map_err(|e| DbError::LockError(e.to_string()))?;
+            *current = Some(sender);
+        }
+
+        // LocalDB が破棄されたらスレッドも終了するよう弱参照で持つ
+        let conn: Weak<Mutex<Connection>> = Arc::downgrade(&self.conn);
+        let tracker: Weak<ChangeTracker> = Arc::downgrade(&self.changes);
+
+        std::thread::Builder::new()
+            .name("db-change-events".to_string())
+            .spawn(move || {
+                while let Ok(mut batch) = receiver.recv() {
+                    let started = Instant::now();
+                    while started.elapsed() < MAX_DEBOUNCE_DELAY {
+                        match receiver.recv_timeout(DEBOUNCE_WINDOW) {
+                            Ok(more) => batch.extend(more),
+                            Err(RecvTimeoutError::Timeout) => break,
+                            Err(RecvTimeoutError::Disconnected) => break,
+                        }
+                    }
+
+                    let (Some(conn), Some(tracker)) = (conn.upgrade(), tracker.upgrade()) else {
+                        break;
+                    };
+                    let event = match conn.lock() {
+                        Ok(conn) => tracker.build_event(&conn, batch),
+                        Err(_) => break,
+                    };
+                    if !event.changes.is_empty() {
+                        listener(event);
+                    }
+                }
+            })
+            .map_err(|e| DbError::IoError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// クエリを購読する
+    pub fn subscribe_query(
+        &self,
+        query_key: &str,
+        tables: Vec<String>,
+        ids: Option<Vec<String>>,
+    ) -> Result<QuerySubscription, DbError> {
+        if tables.is_empty() {
+            return Err(DbError::ValidationError(
+                "A query subscription needs at least one table".to_string(),
+            ));
+        }
+
+        let subscription = QuerySubscription {
+            subscription_id: uuid::Uuid::new_v4().to_string(),
+            query_key: query_key.to_string(),
+            tables,
+            ids,
+        };
+
+        self.changes
+            .subscriptions
+            .lock()
+            .map_err(|e| DbError::LockError(e.to_string()))?
+            .insert(subscription.subscription_id.clone(), subscription.clone());
+
+        Ok(subscription)
+    }
+
+    /// クエリの購読を解除する
+    pub fn unsubscribe_query(&self, subscription_id: &str) -> Result<bool, DbError> {
+        let removed = self
+            .changes
+            .subscriptions
+            .lock()
+            .map_err(|e| DbError::LockError(e.to_string()))?
+            .remove(subscription_id);
+        Ok(removed.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures;
+    use tempfile::TempDir;
+
+    fn wait_for_events(events: &Arc<Mutex<Vec<DbChangedEvent>>>, count: usize) {
+        let started = Instant::now();
+        while events.lock().unwrap().len() < count && started.elapsed() < Duration::from_secs(3) {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_committed_changes_are_debounced_into_one_event() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&events);
+        db.start_change_events(move |event| received.lock().unwrap().push(event))
+            .unwrap();
+
+        let deck_sub = db
+            .subscribe_query("decks:list", vec!["decks".to_string()], None)
+            .unwrap();
+        db.subscribe_query("cards:list", vec!["cards".to_string()], None)
+            .unwrap();
+
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        db.insert_deck(&fixtures::deck("deck-2")).unwrap();
+        wait_for_events(&events, 1);
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let change = &events[0].changes[0];
+        assert_eq!(change.table, "decks");
+        assert_eq!(change.operation, ChangeOperation::Insert);
+        assert_eq!(change.ids, vec!["deck-1", "deck-2"]);
+        assert_eq!(events[0].invalidated_queries.len(), 1);
+        assert_eq!(
+            events[0].invalidated_queries[0].subscription_id,
+            deck_sub.subscription_id
+        );
+    }
+
+    #[test]
+    fn test_rolled_back_changes_are_not_emitted() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&events);
+        db.start_change_events(move |event| received.lock().unwrap().push(event))
+            .unwrap();
+
+        {
+            let mut conn = db.connection().unwrap();
+            let tx = conn.transaction().unwrap();
+            tx.execute(
+                r#"
+                INSERT INTO decks (id, user_id, title, created_at, updated_at, local_updated_at)
+                VALUES ('rolled-back', 'user-1', 'Deck', '', '', '')
+                "#,
+                [],
+            )
+            .unwrap();
+            tx.rollback().unwrap();
+        }
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        wait_for_events(&events, 1);
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].changes.len(), 1);
+        assert_eq!(events[0].changes[0].ids, vec!["deck-1"]);
+    }
+}
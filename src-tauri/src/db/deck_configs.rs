@@ -0,0 +1,371 @@
+//! デッキの学習オプション
+//!
+//! 1日の新規カード数・最大復習数・学習ステップ・目標保持率・最大間隔・兄弟カードの延期を
+//! プリセット（`deck_configs`）として保存し、複数のデッキで共有する。
+//! プリセットを割り当てていないデッキ、削除したプリセットを割り当てたデッキは既定値を使う。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   ├─ src-tauri/src/db/review.rs
+//!   └─ src-tauri/src/commands/deck_configs_commands.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{find_deck, DbError, DeckConfigUpdate, LocalDB, LocalDeck, LocalDeckConfig};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+const DECK_CONFIG_COLUMNS: &str = "id, user_id, name, new_cards_per_day, max_reviews_per_day,
+    learning_steps, desired_retention, maximum_interval, bury_siblings, created_at, updated_at,
+    sync_status, synced_at, local_updated_at, server_updated_at";
+
+/// 学習で使うオプション
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeckStudyOptions {
+    /// 割り当てたプリセット（既定値の場合は None）
+    pub config_id: Option<String>,
+    pub new_cards_per_day: i32,
+    pub max_reviews_per_day: i32,
+    /// 学習ステップ（分）
+    pub learning_steps: Vec<u32>,
+    pub desired_retention: f64,
+    /// 最大間隔（日）
+    pub maximum_interval: i32,
+    pub bury_siblings: bool,
+}
+
+impl Default for DeckStudyOptions {
+    fn default() -> Self {
+        Self {
+            config_id: None,
+            new_cards_per_day: 20,
+            max_reviews_per_day: 200,
+            learning_steps: vec![1, 10],
+            desired_retention: 0.9,
+            maximum_interval: 36500,
+            bury_siblings: true,
+        }
+    }
+}
+
+impl DeckStudyOptions {
+    /// プリセットから作る（学習ステップが読めない場合は既定値）
+    fn from_config(config: &LocalDeckConfig) -> Self {
+        Self {
+            config_id: Some(config.id.clone()),
+            new_cards_per_day: config.new_cards_per_day,
+            max_reviews_per_day: config.max_reviews_per_day,
+            learning_steps: parse_learning_steps(&config.learning_steps)
+                .unwrap_or_else(|_| Self::default().learning_steps),
+            desired_retention: config.desired_retention,
+            maximum_interval: config.maximum_interval,
+            bury_siblings: config.bury_siblings,
+        }
+    }
+}
+
+/// 学習ステップ（分のJSON配列）を読む
+pub fn parse_learning_steps(raw: &str) -> Result<Vec<u32>, DbError> {
+    let steps: Vec<u32> = serde_json::from_str(raw).map_err(|_| {
+        DbError::ValidationError(format!(
+            "learning_steps must be a JSON array of minutes: {}",
+            raw
+        ))
+    })?;
+    if steps.contains(&0) {
+        return Err(DbError::ValidationError(
+            "learning_steps must be positive".to_string(),
+        ));
+    }
+    Ok(steps)
+}
+
+/// プリセットの値を確かめる
+fn validate_deck_config(config: &LocalDeckConfig) -> Result<(), DbError> {
+    if config.name.trim().is_empty() {
+        return Err(DbError::ValidationError(
+            "Deck config name must not be empty".to_string(),
+        ));
+    }
+    if config.new_cards_per_day < 0 || config.max_reviews_per_day < 0 {
+        return Err(DbError::ValidationError(
+            "Daily limits must not be negative".to_string(),
+        ));
+    }
+    if !(config.desired_retention > 0.0 && config.desired_retention < 1.0) {
+        return Err(DbError::ValidationError(
+            "desired_retention must be between 0 and 1".to_string(),
+        ));
+    }
+    if config.maximum_interval < 1 {
+        return Err(DbError::ValidationError(
+            "maximum_interval must be at least 1 day".to_string(),
+        ));
+    }
+    parse_learning_steps(&config.learning_steps)?;
+    Ok(())
+}
+
+/// IDでプリセットを取得
+fn find_deck_config(conn: &Connection, id: &str) -> Result<Option<LocalDeckConfig>, DbError> {
+    let config = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM deck_configs WHERE id = ?1",
+                DECK_CONFIG_COLUMNS
+            ),
+            [id],
+            LocalDeckConfig::from_row,
+        )
+        .optional()?;
+    Ok(config)
+}
+
+/// デッキの学習オプションを取得（プリセットが無ければ既定値）
+pub(crate) fn deck_study_options(
+    conn: &Connection,
+    deck_id: &str,
+) -> Result<DeckStudyOptions, DbError> {
+    let config = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM deck_configs
+                 WHERE id = (SELECT config_id FROM decks WHERE id = ?1)
+                   AND sync_status != 'deleted'",
+                DECK_CONFIG_COLUMNS
+            ),
+            [deck_id],
+            LocalDeckConfig::from_row,
+        )
+        .optional()?;
+    Ok(config
+        .as_ref()
+        .map(DeckStudyOptions::from_config)
+        .unwrap_or_default())
+}
+
+/// 同期状態でプリセットを絞り込む
+fn deck_configs_with_status(
+    conn: &Connection,
+    sync_status: &str,
+) -> Result<Vec<LocalDeckConfig>, DbError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM deck_configs WHERE sync_status = ?1",
+        DECK_CONFIG_COLUMNS
+    ))?;
+    let configs = stmt
+        .query_map([sync_status], LocalDeckConfig::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(configs)
+}
+
+impl LocalDB {
+    /// ユーザーのプリセットを取得
+    pub fn get_deck_configs(&self, user_id: &str) -> Result<Vec<LocalDeckConfig>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM deck_configs
+             WHERE user_id = ?1 AND sync_status != 'deleted'
+             ORDER BY name",
+            DECK_CONFIG_COLUMNS
+        ))?;
+        let configs = stmt
+            .query_map([user_id], LocalDeckConfig::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(configs)
+    }
+
+    /// IDでプリセットを取得
+    pub fn get_deck_config(&self, id: &str) -> Result<Option<LocalDeckConfig>, DbError> {
+        let conn = self.connection()?;
+        find_deck_config(&conn, id)
+    }
+
+    /// プリセットを作成
+    pub fn insert_deck_config(&self, config: &LocalDeckConfig) -> Result<(), DbError> {
+        validate_deck_config(config)?;
+        let conn = self.connection()?;
+        conn.execute(
+            r#"
+            INSERT INTO deck_configs (
+                id, user_id, name, new_cards_per_day, max_reviews_per_day,
+                learning_steps, desired_retention, maximum_interval, bury_siblings,
+                created_at, updated_at,
+                sync_status, synced_at, local_updated_at, server_updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+            "#,
+            rusqlite::params![
+                config.id,
+                config.user_id,
+                config.name,
+                config.new_cards_per_day,
+                config.max_reviews_per_day,
+                config.learning_steps,
+                config.desired_retention,
+                config.maximum_interval,
+                config.bury_siblings,
+                config.created_at,
+                config.updated_at,
+                config.sync_status,
+                config.synced_at,
+                config.local_updated_at,
+                config.server_updated_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// プリセットを更新
+    pub fn update_deck_config(
+        &self,
+        id: &str,
+        updates: DeckConfigUpdate,
+    ) -> Result<Option<LocalDeckConfig>, DbError> {
+        self.with_journal("update_deck_config", &[("deck_configs", id)], |conn| {
+            let Some(current) =
+                find_deck_config(conn, id)?.filter(|config| config.sync_status != "deleted")
+            else {
+                return Ok(None);
+            };
+
+            let now = chrono::Utc::now().to_rfc3339();
+            let updated = LocalDeckConfig {
+                name: updates.name.unwrap_or(current.name),
+                new_cards_per_day: updates
+                    .new_cards_per_day
+                    .unwrap_or(current.new_cards_per_day),
+                max_reviews_per_day: updates
+                    .max_reviews_per_day
+                    .unwrap_or(current.max_reviews_per_day),
+                learning_steps: updates.learning_steps.unwrap_or(current.learning_steps),
+                desired_retention: updates
+                    .desired_retention
+                    .unwrap_or(current.desired_retention),
+                maximum_interval: updates.maximum_interval.unwrap_or(current.maximum_interval),
+                bury_siblings: updates.bury_siblings.unwrap_or(current.bury_siblings),
+                updated_at: now.clone(),
+                local_updated_at: now,
+                sync_status: "pending".to_string(),
+                ..current
+            };
+            validate_deck_config(&updated)?;
+
+            conn.execute(
+                r#"
+                UPDATE deck_configs SET
+                    name = ?1, new_cards_per_day = ?2, max_reviews_per_day = ?3,
+                    learning_steps = ?4, desired_retention = ?5, maximum_interval = ?6,
+                    bury_siblings = ?7, updated_at = ?8, local_updated_at = ?9, sync_status = ?10
+                WHERE id = ?11
+                "#,
+                rusqlite::params![
+                    updated.name,
+                    updated.new_cards_per_day,
+                    updated.max_reviews_per_day,
+                    updated.learning_steps,
+                    updated.desired_retention,
+                    updated.maximum_interval,
+                    updated.bury_siblings,
+                    updated.updated_at,
+                    updated.local_updated_at,
+                    updated.sync_status,
+                    id,
+                ],
+            )?;
+            Ok(Some(updated))
+        })
+    }
+
+    /// プリセットを削除（論理削除）
+    ///
+    /// 割り当てていたデッキは既定値に戻る。
+    pub fn delete_deck_config(&self, id: &str) -> Result<bool, DbError> {
+        self.with_journal("delete_deck_config", &[("deck_configs", id)], |conn| {
+            let now = chrono::Utc::now().to_rfc3339();
+            let rows_affected = conn.execute(
+                "UPDATE deck_configs SET sync_status = 'deleted', local_updated_at = ?1
+                 WHERE id = ?2 AND sync_status != 'deleted'",
+                rusqlite::params![now, id],
+            )?;
+            Ok(rows_affected > 0)
+        })
+    }
+
+    /// デッキにプリセットを割り当てる（None で既定値に戻す）
+    pub fn set_deck_config(
+        &self,
+        deck_id: &str,
+        config_id: Option<&str>,
+    ) -> Result<LocalDeck, DbError> {
+        self.with_journal("set_deck_config", &[("decks", deck_id)], |conn| {
+            let deck = find_deck(conn, deck_id)?
+                .filter(|deck| deck.sync_status != "deleted")
+                .ok_or_else(|| DbError::NotFound(format!("Deck {}", deck_id)))?;
+            if let Some(config_id) = config_id {
+                find_deck_config(conn, config_id)?
+                    .filter(|config| {
+                        config.sync_status != "deleted" && config.user_id == deck.user_id
+                    })
+                    .ok_or_else(|| DbError::NotFound(format!("Deck config {}", config_id)))?;
+            }
+
+            let now = chrono::Utc::now().to_rfc3339();
+            conn.execute(
+                r#"
+                UPDATE decks SET
+                    config_id = ?1, updated_at = ?2, local_updated_at = ?2,
+                    sync_status = 'pending'
+                WHERE id = ?3
+                "#,
+                rusqlite::params![config_id, now, deck_id],
+            )?;
+            Ok(LocalDeck {
+                config_id: config_id.map(str::to_string),
+                updated_at: now.clone(),
+                local_updated_at: now,
+                sync_status: "pending".to_string(),
+                ..deck
+            })
+        })
+    }
+
+    /// デッキの学習オプションを取得（プリセットが無ければ既定値）
+    pub fn get_deck_study_options(&self, deck_id: &str) -> Result<DeckStudyOptions, DbError> {
+        let conn = self.connection()?;
+        deck_study_options(&conn, deck_id)
+    }
+
+    /// 同期待ちのプリセットを取得
+    pub fn get_pending_sync_deck_configs(&self) -> Result<Vec<LocalDeckConfig>, DbError> {
+        let conn = self.connection()?;
+        deck_configs_with_status(&conn, "pending")
+    }
+
+    /// 削除済みのプリセットを取得
+    pub fn get_deleted_deck_configs(&self) -> Result<Vec<LocalDeckConfig>, DbError> {
+        let conn = self.connection()?;
+        deck_configs_with_status(&conn, "deleted")
+    }
+
+    /// プリセットを同期済みにマーク（削除したプリセットは行を消す）
+    pub fn mark_deck_config_synced(
+        &self,
+        id: &str,
+        server_updated_at: &str,
+    ) -> Result<(), DbError> {
+        let conn = self.connection()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "DELETE FROM deck_configs WHERE id = ?1 AND sync_status = 'deleted'",
+            [id],
+        )?;
+        conn.execute(
+            "UPDATE deck_configs SET sync_status = 'synced', synced_at = ?1, server_updated_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![now, server_updated_at, id],
+        )?;
+        Ok(())
+    }
+}
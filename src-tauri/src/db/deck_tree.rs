@@ -0,0 +1,446 @@
+//! デッキの階層
+//!
+//! `decks.parent_deck_id` でデッキを入れ子にする（例: `Japanese::N3::Vocabulary`）。
+//! 親デッキを学習するときは子孫のデッキのカードも対象にし、
+//! デッキツリーのカード数・復習数・新規数も子孫を含めて集計する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   ├─ src-tauri/src/db/review.rs
+//!   └─ src-tauri/src/commands/decks_commands.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/review.rs (1日の上限)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{find_deck, review, DbError, LocalCard, LocalDB, LocalDeck, CARD_COLUMNS};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// デッキのパスの区切り
+pub const DECK_PATH_SEPARATOR: &str = "::";
+
+/// デッキツリーのノード（件数は子孫のデッキを含む）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeckTreeNode {
+    pub deck: LocalDeck,
+    /// ルートからのタイトルを `::` でつないだもの
+    pub path: String,
+    pub card_count: i64,
+    /// 復習期限を過ぎたカード数（停止・延期中のカードを除く）
+    pub due_count: i64,
+    /// まだ一度も復習していないカード数（停止・延期中のカードを除く）
+    pub new_count: i64,
+    pub children: Vec<DeckTreeNode>,
+}
+
+/// デッキ単体のカード数
+#[derive(Debug, Clone, Copy, Default)]
+struct DeckCounts {
+    cards: i64,
+    due: i64,
+    new: i64,
+}
+
+/// デッキと子孫のデッキのID（削除済みを除く）
+pub(crate) fn subtree_deck_ids(conn: &Connection, deck_id: &str) -> Result<Vec<String>, DbError> {
+    let mut stmt = conn.prepare(
+        r#"
+        WITH RECURSIVE subtree(id) AS (
+            SELECT id FROM decks WHERE id = ?1 AND sync_status != 'deleted'
+            UNION
+            SELECT d.id FROM decks d
+            JOIN subtree s ON d.parent_deck_id = s.id
+            WHERE d.sync_status != 'deleted'
+        )
+        SELECT id FROM subtree
+        "#,
+    )?;
+    let ids = stmt
+        .query_map([deck_id], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ids)
+}
+
+/// デッキごとのカード数を集計
+///
+/// 復習数・新規数は学習で出題されるカードだけを数える（`get_due_cards` と同じ条件）。
+fn deck_counts(
+    conn: &Connection,
+    user_id: &str,
+    now: &str,
+) -> Result<HashMap<String, DeckCounts>, DbError> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT deck_id,
+               COUNT(*),
+               SUM(CASE WHEN studyable AND last_reviewed_at IS NOT NULL AND next_review_at <= ?2
+                        THEN 1 ELSE 0 END),
+               SUM(CASE WHEN studyable AND last_reviewed_at IS NULL THEN 1 ELSE 0 END)
+        FROM (
+            SELECT deck_id, last_reviewed_at, next_review_at,
+                   is_suspended = 0 AND (buried_until IS NULL OR buried_until <= ?2) AS studyable
+            FROM cards
+            WHERE user_id = ?1 AND sync_status != 'deleted'
+        )
+        GROUP BY deck_id
+        "#,
+    )?;
+    let counts = stmt
+        .query_map([user_id, now], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                DeckCounts {
+                    cards: row.get(1)?,
+                    due: row.get(2)?,
+                    new: row.get(3)?,
+                },
+            ))
+        })?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    Ok(counts)
+}
+
+/// 子のデッキからツリーのノードを組み立てる（件数は子孫の合計）
+fn build_node(
+    deck: LocalDeck,
+    parent_path: Option<&str>,
+    children_of: &mut HashMap<String, Vec<LocalDeck>>,
+    counts: &HashMap<String, DeckCounts>,
+) -> DeckTreeNode {
+    let path = match parent_path {
+        Some(parent_path) => format!("{}{}{}", parent_path, DECK_PATH_SEPARATOR, deck.title),
+        None => deck.title.clone(),
+    };
+    let own = counts.get(&deck.id).copied().unwrap_or_default();
+
+    let children = children_of
+        .remove(&deck.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|child| build_node(child, Some(&path), children_of, counts))
+        .collect::<Vec<_>>();
+
+    DeckTreeNode {
+        card_count: own.cards + children.iter().map(|c| c.card_count).sum::<i64>(),
+        due_count: own.due + children.iter().map(|c| c.due_count).sum::<i64>(),
+        new_count: own.new + children.iter().map(|c| c.new_count).sum::<i64>(),
+        deck,
+        path,
+        children,
+    }
+}
+
+/// 親をたどると自分に戻るか
+fn is_in_cycle(deck_id: &str, parent_of: &HashMap<String, String>) -> bool {
+    let mut current = parent_of.get(deck_id);
+    for _ in 0..parent_of.len() {
+        match current {
+            Some(parent) if parent == deck_id => return true,
+            Some(parent) => current = parent_of.get(parent),
+            None => return false,
+        }
+    }
+    false
+}
+
+impl LocalDB {
+    /// ユーザーのデッキを階層で取得
+    ///
+    /// 親デッキが見つからない（削除済み・未同期）デッキや、
+    /// 別々の端末での移動が同期されて親をたどると循環するデッキはルートとして扱う。
+    /// 同じ階層のデッキはタイトル順に並べる。
+    pub fn get_deck_tree(&self, user_id: &str) -> Result<Vec<DeckTreeNode>, DbError> {
+        let mut decks = self.get_decks_by_user(user_id)?;
+        decks.sort_by(|a, b| a.title.cmp(&b.title));
+
+        let counts = {
+            let conn = self.connection()?;
+            deck_counts(&conn, user_id, &chrono::Utc::now().to_rfc3339())?
+        };
+
+        let ids = decks
+            .iter()
+            .map(|deck| deck.id.as_str())
+            .collect::<HashSet<_>>();
+        let parent_of = decks
+            .iter()
+            .filter_map(|deck| {
+                deck.parent_deck_id
+                    .as_deref()
+                    .filter(|parent| ids.contains(parent))
+                    .map(|parent| (deck.id.clone(), parent.to_string()))
+            })
+            .collect::<HashMap<_, _>>();
+        let mut roots = Vec::new();
+        let mut children_of: HashMap<String, Vec<LocalDeck>> = HashMap::new();
+        for deck in decks {
+            match parent_of
+                .get(&deck.id)
+                .filter(|_| !is_in_cycle(&deck.id, &parent_of))
+                .cloned()
+            {
+                Some(parent) => children_of.entry(parent).or_default().push(deck),
+                None => roots.push(deck),
+            }
+        }
+
+        Ok(roots
+            .into_iter()
+            .map(|deck| build_node(deck, None, &mut children_of, &counts))
+            .collect())
+    }
+
+    /// デッキを別のデッキの下に移動する（`parent_deck_id` が None ならルートへ）
+    ///
+    /// 自分自身や子孫のデッキの下には移動できない。
+    pub fn move_deck(&self, id: &str, parent_deck_id: Option<&str>) -> Result<LocalDeck, DbError> {
+        self.with_journal("move_deck", &[("decks", id)], |conn| {
+            let deck = find_deck(conn, id)?
+                .filter(|deck| deck.sync_status != "deleted")
+                .ok_or_else(|| DbError::NotFound(format!("Deck {}", id)))?;
+            if deck.parent_deck_id.as_deref() == parent_deck_id {
+                return Ok(deck);
+            }
+
+            if let Some(parent_id) = parent_deck_id {
+                let parent = find_deck(conn, parent_id)?
+                    .filter(|parent| {
+                        parent.sync_status != "deleted" && parent.user_id == deck.user_id
+                    })
+                    .ok_or_else(|| DbError::NotFound(format!("Deck {}", parent_id)))?;
+                if subtree_deck_ids(conn, id)?.contains(&parent.id) {
+                    return Err(DbError::ValidationError(format!(
+                        "Deck {} cannot be moved under itself or its descendant {}",
+                        id, parent.id
+                    )));
+                }
+            }
+
+            let now = chrono::Utc::now().to_rfc3339();
+            conn.execute(
+                r#"
+                UPDATE decks SET
+                    parent_deck_id = ?1, updated_at = ?2, local_updated_at = ?2,
+                    sync_status = 'pending'
+                WHERE id = ?3
+                "#,
+                rusqlite::params![parent_deck_id, now, id],
+            )?;
+            Ok(LocalDeck {
+                parent_deck_id: parent_deck_id.map(str::to_string),
+                updated_at: now.clone(),
+                local_updated_at: now,
+                sync_status: "pending".to_string(),
+                ..deck
+            })
+        })
+    }
+
+    /// デッキを学習するときのカード（子孫のデッキを含む）
+    ///
+    /// 復習期限を過ぎたカードを期限順に、続けて新規カードを作成順に返す。
+    /// 一時停止中・延期中のカードは含めない。
+    /// 1日の新規カード数・最大復習数と兄弟カードの延期はデッキの学習オプションに従う。
+    pub fn get_study_cards(&self, deck_id: &str) -> Result<Vec<LocalCard>, DbError> {
+        let conn = self.connection()?;
+        let deck_ids = subtree_deck_ids(&conn, deck_id)?;
+        if deck_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let placeholders = vec!["?"; deck_ids.len()].join(", ");
+        let now_param = deck_ids.len() + 1;
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT {}
+            FROM cards
+            WHERE deck_id IN ({}) AND sync_status != 'deleted'
+              AND (last_reviewed_at IS NULL OR next_review_at <= ?{now_param})
+              AND is_suspended = 0
+              AND (buried_until IS NULL OR buried_until <= ?{now_param})
+            ORDER BY last_reviewed_at IS NULL, next_review_at, created_at
+            "#,
+            CARD_COLUMNS, placeholders
+        ))?;
+
+        let mut params = deck_ids;
+        params.push(now);
+        let cards = stmt
+            .query_map(rusqlite::params_from_iter(params), LocalCard::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // 兄弟カードを延期し、子孫のデッキの上限に加え、学習するデッキの上限も適用する
+        let cards = review::bury_siblings(&conn, cards)?;
+        review::apply_daily_limits(&conn, cards, Some(deck_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_deck_tree_counts_and_study_cards() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        let yesterday = (chrono::Utc::now() - chrono::Duration::days(1)).to_rfc3339();
+        db.insert_deck(&LocalDeck {
+            title: "Japanese".to_string(),
+            ..fixtures::deck("ja")
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            title: "N3".to_string(),
+            parent_deck_id: Some("ja".to_string()),
+            ..fixtures::deck("n3")
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            title: "Vocabulary".to_string(),
+            parent_deck_id: Some("n3".to_string()),
+            ..fixtures::deck("vocab")
+        })
+        .unwrap();
+        db.insert_card(&LocalCard {
+            repetition_count: 1,
+            next_review_at: Some(yesterday.clone()),
+            last_reviewed_at: Some(yesterday.clone()),
+            ..fixtures::card("c1", "ja")
+        })
+        .unwrap();
+        db.insert_card(&LocalCard {
+            repetition_count: 1,
+            next_review_at: Some(yesterday.clone()),
+            last_reviewed_at: Some(yesterday.clone()),
+            ..fixtures::card("c2", "vocab")
+        })
+        .unwrap();
+        db.insert_card(&fixtures::card("c3", "vocab")).unwrap();
+        // 停止中・延期中のカードは復習数・新規数に数えない
+        db.insert_card(&LocalCard {
+            is_suspended: true,
+            repetition_count: 1,
+            next_review_at: Some(yesterday.clone()),
+            last_reviewed_at: Some(yesterday.clone()),
+            ..fixtures::card("c4", "vocab")
+        })
+        .unwrap();
+        db.insert_card(&LocalCard {
+            buried_until: Some((chrono::Utc::now() + chrono::Duration::days(1)).to_rfc3339()),
+            ..fixtures::card("c5", "vocab")
+        })
+        .unwrap();
+
+        let tree = db.get_deck_tree("user-1").unwrap();
+        assert_eq!(tree.len(), 1);
+        let root = &tree[0];
+        assert_eq!((root.card_count, root.due_count, root.new_count), (5, 2, 1));
+        let vocab = &root.children[0].children[0];
+        assert_eq!(vocab.path, "Japanese::N3::Vocabulary");
+        assert_eq!(
+            (vocab.card_count, vocab.due_count, vocab.new_count),
+            (4, 1, 1)
+        );
+
+        // 親デッキの学習には子孫のカードも含まれる（復習 → 新規の順）
+        let cards = db.get_study_cards("ja").unwrap();
+        assert_eq!(cards.len(), 3);
+        assert_eq!(cards[2].id, "c3");
+        assert_eq!(db.get_study_cards("n3").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_move_deck_prevents_cycles() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_deck(&LocalDeck {
+            title: "A".to_string(),
+            ..fixtures::deck("a")
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            title: "B".to_string(),
+            parent_deck_id: Some("a".to_string()),
+            ..fixtures::deck("b")
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            title: "C".to_string(),
+            parent_deck_id: Some("b".to_string()),
+            ..fixtures::deck("c")
+        })
+        .unwrap();
+
+        assert!(db.move_deck("a", Some("c")).is_err());
+        assert!(db.move_deck("a", Some("a")).is_err());
+
+        let moved = db.move_deck("c", None).unwrap();
+        assert_eq!(moved.parent_deck_id, None);
+        assert_eq!(db.get_deck_tree("user-1").unwrap().len(), 2);
+
+        db.move_deck("a", Some("c")).unwrap();
+        let tree = db.get_deck_tree("user-1").unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].children[0].path, "C::A");
+    }
+
+    #[test]
+    fn test_deck_tree_treats_cyclic_and_missing_parents_as_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        // 別々の端末で互いの下に移動したデッキが同期された状態
+        db.insert_deck(&LocalDeck {
+            title: "A".to_string(),
+            parent_deck_id: Some("b".to_string()),
+            ..fixtures::deck("a")
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            title: "B".to_string(),
+            parent_deck_id: Some("a".to_string()),
+            ..fixtures::deck("b")
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            title: "C".to_string(),
+            parent_deck_id: Some("a".to_string()),
+            ..fixtures::deck("c")
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            title: "D".to_string(),
+            parent_deck_id: Some("missing".to_string()),
+            ..fixtures::deck("d")
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            title: "E".to_string(),
+            parent_deck_id: Some("gone".to_string()),
+            ..fixtures::deck("e")
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            title: "Gone".to_string(),
+            ..fixtures::deck("gone")
+        })
+        .unwrap();
+        db.delete_deck("gone").unwrap();
+
+        let tree = db.get_deck_tree("user-1").unwrap();
+        let roots = tree
+            .iter()
+            .map(|node| node.deck.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(roots, vec!["a", "b", "d", "e"]);
+        assert_eq!(tree[0].children[0].path, "A::C");
+        assert!(tree[1].children.is_empty());
+    }
+}
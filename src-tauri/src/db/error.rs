@@ -37,6 +37,22 @@ pub enum DbError {
     /// バリデーションエラー
     #[error("Validation error: {0}")]
     ValidationError(String),
+
+    /// 既存のデータと衝突する（同じタイトルのページなど）
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// 権限が無い（閲覧者による編集など）
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    /// 封印されたプロファイル（再認証が必要）
+    #[error("Profile is sealed, re-authentication required: {0}")]
+    ProfileSealed(String),
+
+    /// Yjsドキュメントのエンコード・デコードエラー
+    #[error("Document error: {0}")]
+    DocumentError(String),
 }
 
 impl From<serde_json::Error> for DbError {
@@ -0,0 +1,119 @@
+//! テスト用のデータ
+//!
+//! 各モジュールのテストで使うノート・ページ・デッキ・カードの既定値。
+//! テストに必要な項目だけを構造体更新構文で上書きして使う。
+//!
+//! ```ignore
+//! let page = LocalPage {
+//!     title: "Rust".to_string(),
+//!     ..fixtures::page("rust")
+//! };
+//! ```
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/db/*.rs (テスト)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{LocalCard, LocalDeck, LocalNote, LocalPage};
+
+/// テストで使うユーザー
+pub(crate) const USER_ID: &str = "user-1";
+
+/// `user-1` が所有する同期済みの非公開ノート（スラッグ・タイトルはID）
+pub(crate) fn note(id: &str) -> LocalNote {
+    let now = chrono::Utc::now().to_rfc3339();
+    LocalNote {
+        id: id.to_string(),
+        owner_id: USER_ID.to_string(),
+        slug: id.to_string(),
+        title: id.to_string(),
+        description: None,
+        visibility: "private".to_string(),
+        created_at: now.clone(),
+        updated_at: now.clone(),
+        is_trashed: false,
+        trashed_at: None,
+        sync_status: "synced".to_string(),
+        synced_at: None,
+        local_updated_at: now,
+        server_updated_at: None,
+    }
+}
+
+/// `user-1` の同期済みのページ（ノートに属さない。タイトルはID）
+pub(crate) fn page(id: &str) -> LocalPage {
+    let now = chrono::Utc::now().to_rfc3339();
+    LocalPage {
+        id: id.to_string(),
+        user_id: USER_ID.to_string(),
+        note_id: None,
+        title: id.to_string(),
+        thumbnail_url: None,
+        is_public: false,
+        scrapbox_page_id: None,
+        scrapbox_page_list_synced_at: None,
+        scrapbox_page_content_synced_at: None,
+        created_at: now.clone(),
+        updated_at: now.clone(),
+        sync_status: "synced".to_string(),
+        synced_at: None,
+        local_updated_at: now,
+        server_updated_at: None,
+    }
+}
+
+/// `user-1` の同期済みのルートのデッキ（タイトルはID）
+pub(crate) fn deck(id: &str) -> LocalDeck {
+    let now = chrono::Utc::now().to_rfc3339();
+    LocalDeck {
+        id: id.to_string(),
+        user_id: USER_ID.to_string(),
+        parent_deck_id: None,
+        config_id: None,
+        title: id.to_string(),
+        description: None,
+        is_public: false,
+        created_at: now.clone(),
+        updated_at: now.clone(),
+        sync_status: "synced".to_string(),
+        synced_at: None,
+        local_updated_at: now,
+        server_updated_at: None,
+    }
+}
+
+/// `user-1` の同期済みの未学習のカード（表裏は空）
+pub(crate) fn card(id: &str, deck_id: &str) -> LocalCard {
+    let now = chrono::Utc::now().to_rfc3339();
+    LocalCard {
+        id: id.to_string(),
+        deck_id: deck_id.to_string(),
+        user_id: USER_ID.to_string(),
+        front_content: "{}".to_string(),
+        back_content: "{}".to_string(),
+        source_audio_url: None,
+        source_ocr_image_url: None,
+        created_at: now.clone(),
+        updated_at: now.clone(),
+        ease_factor: 2.5,
+        repetition_count: 0,
+        review_interval: 0,
+        next_review_at: None,
+        stability: 0.0,
+        difficulty: 1.0,
+        last_reviewed_at: None,
+        sync_status: "synced".to_string(),
+        synced_at: None,
+        local_updated_at: now,
+        server_updated_at: None,
+        card_kind: "basic".to_string(),
+        card_note_id: None,
+        ordinal: None,
+        is_suspended: false,
+        buried_until: None,
+        flag: None,
+    }
+}
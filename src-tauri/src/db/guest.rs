@@ -0,0 +1,718 @@
+//! ゲストモード
+//!
+//! アカウント作成前の学習データを `guest` プロファイルに保存し、
+//! サインイン後にそのユーザーのプロファイルへ引き継ぐ。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/profiles_commands.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/db/media.rs (メディアのファイル)
+//!   ├─ src-tauri/src/db/note_slugs.rs
+//!   ├─ src-tauri/src/db/page_links.rs (引き継いだページのリンク)
+//!   └─ src-tauri/src/db/profiles.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::note_slugs::next_available_slug;
+use super::profiles::{self, LocalProfile};
+use super::{page_links, DbError, LocalDB};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// ゲストプロファイルのID（ゲストが作成したデータの仮の所有者IDを兼ねる）
+pub const GUEST_USER_ID: &str = "guest";
+
+/// 引き継ぎ対象テーブルの定義
+struct ClaimTable {
+    name: &'static str,
+    /// 所有者を表すカラム（無い場合は親テーブル経由で所有される）
+    owner_column: Option<&'static str>,
+    /// 親レコードが引き継がれた行だけを対象にする条件
+    parent_filter: Option<&'static str>,
+    /// 値を書き換えるカラムとSQL式
+    column_exprs: &'static [(&'static str, &'static str)],
+}
+
+/// ノート以外の引き継ぎ対象（外部キーの依存順）
+const CLAIM_TABLES: [ClaimTable; 14] = [
+    ClaimTable {
+        name: "pages",
+        owner_column: Some("user_id"),
+        parent_filter: None,
+        column_exprs: &[],
+    },
+    ClaimTable {
+        name: "deck_configs",
+        owner_column: Some("user_id"),
+        parent_filter: None,
+        column_exprs: &[],
+    },
+    ClaimTable {
+        name: "decks",
+        owner_column: Some("user_id"),
+        parent_filter: None,
+        column_exprs: &[],
+    },
+    ClaimTable {
+        name: "card_notes",
+        owner_column: Some("user_id"),
+        parent_filter: Some("deck_id IN (SELECT id FROM main.decks)"),
+        column_exprs: &[],
+    },
+    ClaimTable {
+        name: "cards",
+        owner_column: Some("user_id"),
+        parent_filter: Some("deck_id IN (SELECT id FROM main.decks)"),
+        column_exprs: &[],
+    },
+    ClaimTable {
+        name: "questions",
+        owner_column: Some("user_id"),
+        parent_filter: Some("card_id IN (SELECT id FROM main.cards)"),
+        column_exprs: &[],
+    },
+    ClaimTable {
+        name: "learning_logs",
+        owner_column: Some("user_id"),
+        parent_filter: Some("card_id IN (SELECT id FROM main.cards)"),
+        // 引き継がなかった（削除済みの）問題は、まだ届いていない問題として扱う
+        column_exprs: &[
+            (
+                "question_id",
+                "CASE WHEN question_id IN (SELECT id FROM main.questions) THEN question_id END",
+            ),
+            (
+                "pending_question_id",
+                "COALESCE(pending_question_id, CASE WHEN question_id NOT IN (SELECT id FROM main.questions) THEN question_id END)",
+            ),
+        ],
+    },
+    ClaimTable {
+        name: "study_goals",
+        owner_column: Some("user_id"),
+        parent_filter: None,
+        column_exprs: &[],
+    },
+    ClaimTable {
+        name: "milestones",
+        owner_column: None,
+        parent_filter: Some("goal_id IN (SELECT id FROM main.study_goals)"),
+        column_exprs: &[],
+    },
+    ClaimTable {
+        name: "note_pages",
+        owner_column: None,
+        parent_filter: Some(
+            "note_id IN (SELECT id FROM main.notes) AND page_id IN (SELECT id FROM main.pages)",
+        ),
+        column_exprs: &[],
+    },
+    // メンバーは招待したユーザー（ゲストのノートでは所有者のゲスト）を書き換える
+    ClaimTable {
+        name: "note_members",
+        owner_column: Some("invited_by"),
+        parent_filter: Some("note_id IN (SELECT id FROM main.notes)"),
+        column_exprs: &[],
+    },
+    ClaimTable {
+        name: "note_share_links",
+        owner_column: Some("created_by"),
+        parent_filter: Some("note_id IN (SELECT id FROM main.notes)"),
+        column_exprs: &[],
+    },
+    // 同じ名前のタグが既にある場合はそちらを優先する（タグ付けは `copy_guest_entity_tags`）
+    ClaimTable {
+        name: "tags",
+        owner_column: Some("user_id"),
+        parent_filter: None,
+        column_exprs: &[],
+    },
+    // 既存の設定がある場合はそちらを優先する（user_id は UNIQUE）
+    ClaimTable {
+        name: "user_settings",
+        owner_column: Some("user_id"),
+        parent_filter: None,
+        column_exprs: &[],
+    },
+];
+
+/// スラッグを変更したノート
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenamedSlug {
+    pub note_id: String,
+    pub old_slug: String,
+    pub new_slug: String,
+}
+
+/// ゲストデータ引き継ぎ結果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GuestClaimResult {
+    pub user_id: String,
+    /// テーブル名ごとの引き継ぎ件数
+    pub claimed_rows: BTreeMap<String, usize>,
+    /// 既存ノートと衝突したためスラッグを変更したノート
+    pub renamed_slugs: Vec<RenamedSlug>,
+}
+
+/// ATTACHしたゲストDBのカラム一覧を取得
+fn guest_columns(conn: &Connection, table: &str) -> Result<Vec<String>, DbError> {
+    let mut stmt = conn.prepare(&format!("PRAGMA guest.table_info({})", table))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(columns)
+}
+
+/// 所有者と同期メタデータを書き換えてゲストの行をコピーする
+///
+/// `?1` = 新しい所有者ID, `?2` = 現在時刻
+fn copy_guest_table(
+    conn: &Connection,
+    table: &ClaimTable,
+    user_id: &str,
+    now: &str,
+) -> Result<usize, DbError> {
+    let columns = guest_columns(conn, table.name)?;
+    let select_exprs = columns
+        .iter()
+        .map(|column| {
+            if let Some((_, expr)) = table.column_exprs.iter().find(|(name, _)| name == column) {
+                return expr.to_string();
+            }
+            match column.as_str() {
+                c if Some(c) == table.owner_column => "?1".to_string(),
+                "sync_status" => "'pending'".to_string(),
+                "local_updated_at" => "?2".to_string(),
+                "synced_at" | "server_updated_at" => "NULL".to_string(),
+                c => c.to_string(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut sql = format!(
+        "INSERT OR IGNORE INTO main.{table} ({columns}) SELECT {exprs} FROM guest.{table} WHERE sync_status != 'deleted'",
+        table = table.name,
+        columns = columns.join(", "),
+        exprs = select_exprs.join(", "),
+    );
+    if let Some(filter) = table.parent_filter {
+        sql.push_str(" AND ");
+        sql.push_str(filter);
+    }
+
+    let rows = conn.execute(&sql, rusqlite::params![user_id, now])?;
+    Ok(rows)
+}
+
+/// ゲストのタグ付けをコピーする
+///
+/// 同じ名前のタグが既にあってゲストのタグを引き継がなかった場合は、既存のタグに付け替える。
+fn copy_guest_entity_tags(conn: &Connection, user_id: &str, now: &str) -> Result<usize, DbError> {
+    let rows = conn.execute(
+        r#"
+        INSERT OR IGNORE INTO main.entity_tags (
+            id, tag_id, entity_type, entity_id, user_id, created_at,
+            sync_status, synced_at, local_updated_at, server_updated_at
+        )
+        SELECT et.id, claimed.id, et.entity_type, et.entity_id, ?1, et.created_at,
+               'pending', NULL, ?2, NULL
+        FROM guest.entity_tags et
+        JOIN guest.tags guest_tag ON guest_tag.id = et.tag_id
+        JOIN main.tags claimed
+          ON claimed.user_id = ?1 AND claimed.name = guest_tag.name
+         AND claimed.sync_status != 'deleted'
+        WHERE et.sync_status != 'deleted' AND guest_tag.sync_status != 'deleted'
+        "#,
+        rusqlite::params![user_id, now],
+    )?;
+    Ok(rows)
+}
+
+/// 引き継いだカード・ページが参照するメディアの記録をコピーする
+///
+/// メディアは内容のハッシュで識別するので、同じ内容が既にあればそちらを使う。
+/// ファイルは `LocalDB::move_media_files_from` で移す。
+fn copy_guest_media(conn: &Connection, result: &mut GuestClaimResult) -> Result<(), DbError> {
+    let media = conn.execute(
+        r#"
+        INSERT OR IGNORE INTO main.media (hash, mime_type, size_bytes, source_url, created_at)
+        SELECT hash, mime_type, size_bytes, source_url, created_at FROM guest.media
+        "#,
+        [],
+    )?;
+    let refs = conn.execute(
+        r#"
+        INSERT OR IGNORE INTO main.media_refs (hash, entity_type, entity_id, field)
+        SELECT hash, entity_type, entity_id, field FROM guest.media_refs
+        WHERE (entity_type = 'card' AND entity_id IN (SELECT id FROM main.cards))
+           OR (entity_type = 'page' AND entity_id IN (SELECT id FROM main.pages))
+        "#,
+        [],
+    )?;
+    let variants = conn.execute(
+        r#"
+        INSERT OR IGNORE INTO main.media_variants (source_hash, variant, hash, created_at)
+        SELECT source_hash, variant, hash, created_at FROM guest.media_variants
+        "#,
+        [],
+    )?;
+
+    result.claimed_rows.insert("media".to_string(), media);
+    result.claimed_rows.insert("media_refs".to_string(), refs);
+    result
+        .claimed_rows
+        .insert("media_variants".to_string(), variants);
+    Ok(())
+}
+
+/// 引き継いだページの本文（Yjsの更新ログ）をコピーし、本文のリンクを記録する
+///
+/// サーバーは本文を一度も受け取っていないため、すべてローカルの更新として扱う。
+fn copy_guest_page_documents(conn: &Connection) -> Result<usize, DbError> {
+    let rows = conn.execute(
+        r#"
+        INSERT INTO main.page_document_updates (page_id, update_data, origin, created_at)
+        SELECT page_id, update_data, 'local', created_at FROM guest.page_document_updates
+        WHERE page_id IN (SELECT id FROM main.pages)
+        ORDER BY id
+        "#,
+        [],
+    )?;
+
+    let page_ids = {
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT DISTINCT page_id FROM guest.page_document_updates
+            WHERE page_id IN (SELECT id FROM main.pages)
+            "#,
+        )?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        ids
+    };
+    for page_id in &page_ids {
+        conn.execute(
+            "INSERT OR IGNORE INTO main.page_documents (page_id) VALUES (?1)",
+            [page_id],
+        )?;
+        page_links::index_page_document(conn, page_id)?;
+    }
+
+    Ok(rows)
+}
+
+impl LocalDB {
+    /// ゲストモードを開始する
+    pub fn enter_guest_mode(&self) -> Result<LocalProfile, DbError> {
+        self.switch_profile(GUEST_USER_ID)
+    }
+
+    /// ゲストデータをサインインしたユーザーに引き継ぐ
+    ///
+    /// ユーザーのプロファイルに切り替えたうえで、ゲストDBの全データを
+    /// 1トランザクションで所有者を書き換えてコピーし、同期待ちにする。
+    /// メディアのファイルもユーザーのプロファイルに移す。
+    /// 完了後、ゲストプロファイルは削除する。
+    pub fn claim_guest_data(&self, new_user_id: &str) -> Result<GuestClaimResult, DbError> {
+        profiles::validate_user_id(new_user_id)?;
+        if new_user_id == GUEST_USER_ID {
+            return Err(DbError::ValidationError(
+                "Cannot claim guest data for the guest profile".to_string(),
+            ));
+        }
+
+        if self.active_profile()?.as_deref() != Some(new_user_id) {
+            self.switch_profile(new_user_id)?;
+        }
+
+        let mut result = GuestClaimResult {
+            user_id: new_user_id.to_string(),
+            ..Default::default()
+        };
+
+        let guest_path = Self::get_db_path(&self.data_dir, Some(GUEST_USER_ID));
+        if !guest_path.exists() {
+            return Ok(result);
+        }
+
+        // 古いバージョンのゲストDBでもカラムが揃うようにマイグレーションしておく
+        drop(Self::open_connection(&guest_path)?);
+
+        {
+            let mut conn = self.connection()?;
+            conn.execute(
+                "ATTACH DATABASE ?1 AS guest",
+                [guest_path.to_string_lossy().as_ref()],
+            )?;
+
+            let copied = Self::copy_guest_rows(&mut conn, new_user_id, &mut result);
+            conn.execute_batch("DETACH DATABASE guest")?;
+            copied?;
+        }
+        // ゲストのプロファイルを削除する前に、メディアのファイルを移しておく
+        self.move_media_files_from(GUEST_USER_ID)?;
+
+        std::fs::remove_dir_all(profiles::profile_dir(&self.data_dir, GUEST_USER_ID))
+            .map_err(|e| DbError::IoError(e.to_string()))?;
+
+        log::info!(
+            "Claimed guest data for {}: {:?}",
+            new_user_id,
+            result.claimed_rows
+        );
+
+        Ok(result)
+    }
+
+    /// ATTACH済みのゲストDBから行をコピーする
+    fn copy_guest_rows(
+        conn: &mut Connection,
+        user_id: &str,
+        result: &mut GuestClaimResult,
+    ) -> Result<(), DbError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let tx = conn.transaction()?;
+
+        // ノートは (owner_id, slug) が一意なので1件ずつスラッグを解決する
+        let guest_notes = {
+            let mut stmt = tx.prepare(
+                "SELECT id, slug FROM guest.notes WHERE sync_status != 'deleted' ORDER BY created_at ASC",
+            )?;
+            let notes = stmt
+                .query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            notes
+        };
+
+        let mut claimed_notes = 0;
+        for (note_id, slug) in guest_notes {
+            let already_claimed = tx
+                .query_row("SELECT 1 FROM main.notes WHERE id = ?1", [&note_id], |_| {
+                    Ok(())
+                })
+                .optional()?
+                .is_some();
+            if already_claimed {
+                continue;
+            }
+
+            let new_slug = next_available_slug(&tx, user_id, &slug, None)?;
+            tx.execute(
+                r#"
+                INSERT INTO main.notes (
+                    id, owner_id, slug, title, description, visibility,
+                    created_at, updated_at, is_trashed, trashed_at,
+                    sync_status, synced_at, local_updated_at, server_updated_at
+                )
+                SELECT id, ?1, ?2, title, description, visibility,
+                       created_at, updated_at, is_trashed, trashed_at,
+                       'pending', NULL, ?3, NULL
+                FROM guest.notes
+                WHERE id = ?4
+                "#,
+                rusqlite::params![user_id, new_slug, now, note_id],
+            )?;
+            claimed_notes += 1;
+
+            if new_slug != slug {
+                result.renamed_slugs.push(RenamedSlug {
+                    note_id,
+                    old_slug: slug,
+                    new_slug,
+                });
+            }
+        }
+        result
+            .claimed_rows
+            .insert("notes".to_string(), claimed_notes);
+
+        for table in CLAIM_TABLES.iter() {
+            let rows = copy_guest_table(&tx, table, user_id, &now)?;
+            result.claimed_rows.insert(table.name.to_string(), rows);
+        }
+        let rows = copy_guest_entity_tags(&tx, user_id, &now)?;
+        result.claimed_rows.insert("entity_tags".to_string(), rows);
+        copy_guest_media(&tx, result)?;
+        let rows = copy_guest_page_documents(&tx)?;
+        result
+            .claimed_rows
+            .insert("page_document_updates".to_string(), rows);
+
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::tiptap::bracket_link_mark;
+    use crate::content::{tiptap_to_yjs_update, TiptapNode};
+    use crate::db::tags::TagEntity;
+    use crate::db::{
+        fixtures, DocumentUpdateOrigin, LocalCard, LocalDeck, LocalDeckConfig, LocalNote,
+        LocalPage, MediaEntity, NoteRole,
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_claim_guest_data_rewrites_owner_and_resolves_slugs() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+
+        // サインイン済みユーザーが既に同じスラッグのノートを持っている
+        db.switch_profile("user-1").unwrap();
+        db.insert_note(&LocalNote {
+            slug: "my-note".to_string(),
+            ..fixtures::note("existing")
+        })
+        .unwrap();
+
+        db.enter_guest_mode().unwrap();
+        db.insert_note(&LocalNote {
+            owner_id: GUEST_USER_ID.to_string(),
+            slug: "my-note".to_string(),
+            ..fixtures::note("guest-note")
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            user_id: GUEST_USER_ID.to_string(),
+            sync_status: "pending".to_string(),
+            ..fixtures::deck("deck-1")
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            user_id: GUEST_USER_ID.to_string(),
+            sync_status: "deleted".to_string(),
+            ..fixtures::deck("deck-2")
+        })
+        .unwrap();
+        db.insert_card(&LocalCard {
+            user_id: GUEST_USER_ID.to_string(),
+            sync_status: "pending".to_string(),
+            ..fixtures::card("card-1", "deck-1")
+        })
+        .unwrap();
+        db.insert_card(&LocalCard {
+            user_id: GUEST_USER_ID.to_string(),
+            sync_status: "pending".to_string(),
+            ..fixtures::card("card-2", "deck-2")
+        })
+        .unwrap();
+
+        let result = db.claim_guest_data("user-1").unwrap();
+        assert_eq!(db.active_profile().unwrap().as_deref(), Some("user-1"));
+        assert_eq!(result.claimed_rows["notes"], 1);
+        assert_eq!(result.claimed_rows["decks"], 1);
+        assert_eq!(result.claimed_rows["cards"], 1);
+        assert_eq!(result.renamed_slugs.len(), 1);
+        assert_eq!(result.renamed_slugs[0].new_slug, "my-note-2");
+
+        let note = db.get_note_by_id("guest-note").unwrap().unwrap();
+        assert_eq!(note.owner_id, "user-1");
+        assert_eq!(note.sync_status, "pending");
+        assert!(note.server_updated_at.is_none());
+
+        let card = db.get_card_by_id("card-1").unwrap().unwrap();
+        assert_eq!(card.user_id, "user-1");
+        assert!(db.get_card_by_id("card-2").unwrap().is_none());
+
+        // ゲストプロファイルは削除される
+        let profiles = db.list_local_profiles().unwrap();
+        assert!(profiles.iter().all(|p| p.user_id != GUEST_USER_ID));
+    }
+
+    #[test]
+    fn test_claim_guest_data_includes_deck_configs_and_sharing() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        db.enter_guest_mode().unwrap();
+        db.insert_deck_config(&LocalDeckConfig {
+            id: "config-1".to_string(),
+            user_id: GUEST_USER_ID.to_string(),
+            name: "Preset".to_string(),
+            new_cards_per_day: 10,
+            max_reviews_per_day: 100,
+            learning_steps: "[1,10]".to_string(),
+            desired_retention: 0.9,
+            maximum_interval: 36500,
+            bury_siblings: true,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            sync_status: "pending".to_string(),
+            synced_at: None,
+            local_updated_at: now.clone(),
+            server_updated_at: None,
+        })
+        .unwrap();
+        db.insert_deck(&LocalDeck {
+            config_id: Some("config-1".to_string()),
+            user_id: GUEST_USER_ID.to_string(),
+            sync_status: "pending".to_string(),
+            ..fixtures::deck("deck-1")
+        })
+        .unwrap();
+        db.insert_note(&LocalNote {
+            owner_id: GUEST_USER_ID.to_string(),
+            slug: "shared".to_string(),
+            ..fixtures::note("guest-note")
+        })
+        .unwrap();
+        db.invite_note_member("guest-note", "user-2", NoteRole::Editor)
+            .unwrap();
+        db.create_note_share_link("guest-note", NoteRole::Viewer, None)
+            .unwrap();
+
+        let result = db.claim_guest_data("user-1").unwrap();
+        assert_eq!(result.claimed_rows["deck_configs"], 1);
+        assert_eq!(result.claimed_rows["note_members"], 1);
+        assert_eq!(result.claimed_rows["note_share_links"], 1);
+
+        let config = db.get_deck_config("config-1").unwrap().unwrap();
+        assert_eq!(config.user_id, "user-1");
+        assert_eq!(
+            db.get_deck_by_id("deck-1")
+                .unwrap()
+                .unwrap()
+                .config_id
+                .as_deref(),
+            Some("config-1")
+        );
+
+        let members = db.get_note_members("guest-note").unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].user_id, "user-2");
+        assert_eq!(members[0].invited_by.as_deref(), Some("user-1"));
+        let links = db.get_note_share_links("guest-note").unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].created_by, "user-1");
+    }
+
+    #[test]
+    fn test_claim_guest_data_moves_tags_to_existing_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.switch_profile("user-1").unwrap();
+        db.insert_note(&LocalNote {
+            slug: "existing".to_string(),
+            ..fixtures::note("existing")
+        })
+        .unwrap();
+        let existing_tag = db
+            .add_tag("user-1", TagEntity::Note, "existing", "rust")
+            .unwrap();
+
+        db.enter_guest_mode().unwrap();
+        db.insert_note(&LocalNote {
+            owner_id: GUEST_USER_ID.to_string(),
+            slug: "guest-note".to_string(),
+            ..fixtures::note("guest-note")
+        })
+        .unwrap();
+        db.add_tag(GUEST_USER_ID, TagEntity::Note, "guest-note", "rust")
+            .unwrap();
+        db.add_tag(GUEST_USER_ID, TagEntity::Note, "guest-note", "go")
+            .unwrap();
+
+        let result = db.claim_guest_data("user-1").unwrap();
+        assert_eq!(result.claimed_rows["tags"], 1);
+        assert_eq!(result.claimed_rows["entity_tags"], 2);
+
+        // 同じ名前のタグは既存のタグに付け替わる
+        let tags = db.get_entity_tags(TagEntity::Note, "guest-note").unwrap();
+        let names = tags.iter().map(|tag| tag.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["go", "rust"]);
+        assert_eq!(tags[1].id, existing_tag.id);
+        assert_eq!(db.get_tags("user-1").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_claim_guest_data_indexes_links_in_yjs_bodies() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+
+        db.enter_guest_mode().unwrap();
+        db.insert_page(&LocalPage {
+            user_id: GUEST_USER_ID.to_string(),
+            sync_status: "pending".to_string(),
+            ..fixtures::page("page-1")
+        })
+        .unwrap();
+        let doc = TiptapNode::new(
+            "doc",
+            vec![TiptapNode::new(
+                "paragraph",
+                vec![TiptapNode::text("Rust", vec![bracket_link_mark("Rust")])],
+            )],
+        );
+        db.apply_page_update(
+            "page-1",
+            &tiptap_to_yjs_update(&doc),
+            DocumentUpdateOrigin::Local,
+        )
+        .unwrap();
+
+        db.claim_guest_data("user-1").unwrap();
+        let links = db.get_outgoing_links("page-1").unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].title, "Rust");
+    }
+
+    #[test]
+    fn test_claim_guest_data_moves_media() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+
+        db.enter_guest_mode().unwrap();
+        db.insert_deck(&LocalDeck {
+            user_id: GUEST_USER_ID.to_string(),
+            sync_status: "pending".to_string(),
+            ..fixtures::deck("deck-1")
+        })
+        .unwrap();
+        db.insert_card(&LocalCard {
+            user_id: GUEST_USER_ID.to_string(),
+            sync_status: "pending".to_string(),
+            ..fixtures::card("card-1", "deck-1")
+        })
+        .unwrap();
+        let media = db
+            .ingest_media(
+                b"audio",
+                Some("audio/mpeg"),
+                Some("https://example.com/a.mp3"),
+            )
+            .unwrap();
+        db.attach_media(&media.hash, MediaEntity::Card, "card-1", "source_audio_url")
+            .unwrap();
+
+        let result = db.claim_guest_data("user-1").unwrap();
+        assert_eq!(result.claimed_rows["media"], 1);
+        assert_eq!(result.claimed_rows["media_refs"], 1);
+
+        let refs = db.get_entity_media(MediaEntity::Card, "card-1").unwrap();
+        assert_eq!(refs.len(), 1);
+        let (_, bytes) = db.read_media(&media.hash).unwrap().unwrap();
+        assert_eq!(bytes, b"audio");
+    }
+
+    #[test]
+    fn test_claim_guest_data_without_guest_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+
+        let result = db.claim_guest_data("user-1").unwrap();
+        assert!(result.claimed_rows.is_empty());
+        assert!(db.claim_guest_data(GUEST_USER_ID).is_err());
+    }
+}
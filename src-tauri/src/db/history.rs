@@ -0,0 +1,730 @@
+//! 変更履歴（リビジョン）
+//!
+//! ノート・ページ・カードを更新・サーバー上書きする前の内容を `revisions` に保存し、
+//! 任意の時点の内容に戻せるようにする。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/history_commands.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/db/journal.rs (復元を元に戻す)
+//!   ├─ src-tauri/src/db/note_pages.rs
+//!   └─ src-tauri/src/db/page_rename.rs (タイトルの復元とリンクの書き換え)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::journal::JournalTx;
+use super::{
+    find_card, find_note, find_page, note_pages, page_rename, write_page_update, DbError,
+    LocalCard, LocalDB, LocalNote, LocalPage, LocalRevision, CARD_COLUMNS,
+};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// エンティティごとに保持するリビジョンの上限
+pub const MAX_REVISIONS_PER_ENTITY: i64 = 50;
+
+/// リビジョンの保持期間（日）
+pub const REVISION_RETENTION_DAYS: i64 = 90;
+
+/// 履歴を持つエンティティの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryEntity {
+    Note,
+    Page,
+    Card,
+}
+
+impl HistoryEntity {
+    /// `revisions.entity_type` に保存する値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryEntity::Note => "note",
+            HistoryEntity::Page => "page",
+            HistoryEntity::Card => "card",
+        }
+    }
+}
+
+/// 変更の発生元
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevisionSource {
+    /// ローカルでの編集
+    LocalEdit,
+    /// 同期によるサーバーデータでの上書き
+    ServerOverwrite,
+    /// リビジョンからの復元
+    Restore,
+}
+
+impl RevisionSource {
+    /// `revisions.source` に保存する値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RevisionSource::LocalEdit => "local_edit",
+            RevisionSource::ServerOverwrite => "server_overwrite",
+            RevisionSource::Restore => "restore",
+        }
+    }
+}
+
+/// 復元後のエンティティ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "entity", content = "data", rename_all = "lowercase")]
+pub enum RestoredEntity {
+    Note(LocalNote),
+    Page(LocalPage),
+    Card(LocalCard),
+}
+
+/// 変更前の内容をリビジョンとして保存する
+///
+/// 呼び出し側のトランザクション内で、本体の更新より先に呼び出すこと。
+pub(crate) fn record_revision<T: Serialize>(
+    conn: &Connection,
+    entity: HistoryEntity,
+    entity_id: &str,
+    snapshot: &T,
+    source: RevisionSource,
+    changed_by: Option<&str>,
+) -> Result<(), DbError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    conn.execute(
+        r#"
+        INSERT INTO revisions (id, entity_type, entity_id, snapshot, source, changed_by, created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        "#,
+        rusqlite::params![
+            uuid::Uuid::new_v4().to_string(),
+            entity.as_str(),
+            entity_id,
+            serde_json::to_string(snapshot)?,
+            source.as_str(),
+            changed_by,
+            now,
+        ],
+    )?;
+
+    prune_revisions(conn, entity, entity_id)
+}
+
+/// 保持件数・保持期間を超えたリビジョンを削除する
+fn prune_revisions(
+    conn: &Connection,
+    entity: HistoryEntity,
+    entity_id: &str,
+) -> Result<(), DbError> {
+    let cutoff =
+        (chrono::Utc::now() - chrono::Duration::days(REVISION_RETENTION_DAYS)).to_rfc3339();
+    conn.execute(
+        r#"
+        DELETE FROM revisions
+        WHERE entity_type = ?1 AND entity_id = ?2
+          AND (
+            created_at < ?3
+            OR id NOT IN (
+                SELECT id FROM revisions
+                WHERE entity_type = ?1 AND entity_id = ?2
+                ORDER BY created_at DESC, rowid DESC
+                LIMIT ?4
+            )
+          )
+        "#,
+        rusqlite::params![entity.as_str(), entity_id, cutoff, MAX_REVISIONS_PER_ENTITY],
+    )?;
+    Ok(())
+}
+
+/// IDでリビジョンを取得
+fn get_revision(conn: &Connection, revision_id: &str) -> Result<Option<LocalRevision>, DbError> {
+    let revision = conn
+        .query_row(
+            r#"
+            SELECT id, entity_type, entity_id, snapshot, source, changed_by, created_at
+            FROM revisions
+            WHERE id = ?1
+            "#,
+            [revision_id],
+            LocalRevision::from_row,
+        )
+        .optional()?;
+    Ok(revision)
+}
+
+/// 物理削除済みのページをスナップショットの内容で作り直す
+fn insert_restored_page(tx: &JournalTx<'_>, page: &LocalPage) -> Result<(), DbError> {
+    tx.execute(
+        r#"
+        INSERT INTO pages (
+            id, user_id, note_id, title, thumbnail_url, is_public,
+            scrapbox_page_id, scrapbox_page_list_synced_at, scrapbox_page_content_synced_at,
+            created_at, updated_at,
+            sync_status, synced_at, local_updated_at, server_updated_at
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+        "#,
+        rusqlite::params![
+            page.id,
+            page.user_id,
+            page.note_id,
+            page.title,
+            page.thumbnail_url,
+            page.is_public,
+            page.scrapbox_page_id,
+            page.scrapbox_page_list_synced_at,
+            page.scrapbox_page_content_synced_at,
+            page.created_at,
+            page.updated_at,
+            page.sync_status,
+            page.synced_at,
+            page.local_updated_at,
+            page.server_updated_at,
+        ],
+    )?;
+    if let Some(note_id) = &page.note_id {
+        let note_page_id = uuid::Uuid::new_v4().to_string();
+        tx.track("note_pages", &note_page_id)?;
+        note_pages::insert_note_page(tx, &note_page_id, note_id, &page.id, None, &page.updated_at)?;
+    }
+
+    Ok(())
+}
+
+impl LocalDB {
+    /// エンティティの変更履歴を取得（新しい順）
+    pub fn get_history(
+        &self,
+        entity: HistoryEntity,
+        entity_id: &str,
+    ) -> Result<Vec<LocalRevision>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT id, entity_type, entity_id, snapshot, source, changed_by, created_at
+            FROM revisions
+            WHERE entity_type = ?1 AND entity_id = ?2
+            ORDER BY created_at DESC, rowid DESC
+            "#,
+        )?;
+
+        let revisions = stmt
+            .query_map([entity.as_str(), entity_id], LocalRevision::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(revisions)
+    }
+
+    /// 現在のユーザーが所有者であることを確かめる
+    ///
+    /// ノートに入らないカードや、権限を確かめる行が残っていない物理削除済みのノートに使う。
+    fn ensure_is_owner(&self, owner_id: &str, entity_id: &str) -> Result<(), DbError> {
+        match self.active_profile()? {
+            Some(user_id) if user_id != owner_id => Err(DbError::PermissionDenied(format!(
+                "User {} cannot restore {}",
+                user_id, entity_id
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// リビジョンの内容に戻す
+    ///
+    /// 現在の内容も `restore` のリビジョンとして保存するので、復元自体も元に戻せる。
+    /// 復元は元に戻す・やり直すの対象になる（ページのタイトルを戻す場合を除く）。
+    /// 内容に関わる項目だけを戻し、学習スケジュールやスラッグは現在の値を維持する。
+    /// 物理削除済みの場合はリビジョンの内容で再作成する。
+    /// 現在の内容を編集できない（物理削除済み・カードは所有者でない）場合は
+    /// `DbError::PermissionDenied` を返す。
+    pub fn restore_revision(&self, revision_id: &str) -> Result<RestoredEntity, DbError> {
+        let revision = {
+            let conn = self.connection()?;
+            get_revision(&conn, revision_id)?
+        }
+        .ok_or_else(|| DbError::NotFound(format!("Revision not found: {}", revision_id)))?;
+
+        match revision.entity_type.as_str() {
+            "note" => {
+                let snapshot: LocalNote = serde_json::from_str(&revision.snapshot)?;
+                self.restore_note(snapshot).map(RestoredEntity::Note)
+            }
+            "page" => {
+                let snapshot: LocalPage = serde_json::from_str(&revision.snapshot)?;
+                self.restore_page(snapshot).map(RestoredEntity::Page)
+            }
+            "card" => {
+                let snapshot: LocalCard = serde_json::from_str(&revision.snapshot)?;
+                self.restore_card(snapshot).map(RestoredEntity::Card)
+            }
+            other => Err(DbError::ValidationError(format!(
+                "Unknown revision entity type: {}",
+                other
+            ))),
+        }
+    }
+
+    /// ノートをスナップショットの内容に戻す
+    fn restore_note(&self, snapshot: LocalNote) -> Result<LocalNote, DbError> {
+        let id = snapshot.id.clone();
+        self.with_journal("restore_revision", &[("notes", &id)], |tx| {
+            let now = chrono::Utc::now().to_rfc3339();
+            let current = find_note(tx, &id)?;
+            match &current {
+                Some(_) => self.ensure_can_edit_note(tx, &id)?,
+                None => self.ensure_is_owner(&snapshot.owner_id, &id)?,
+            }
+
+            let restored = match &current {
+                Some(current) => LocalNote {
+                    title: snapshot.title,
+                    description: snapshot.description,
+                    visibility: snapshot.visibility,
+                    is_trashed: snapshot.is_trashed,
+                    trashed_at: snapshot.trashed_at,
+                    ..current.clone()
+                },
+                None => snapshot,
+            };
+            let restored = LocalNote {
+                updated_at: now.clone(),
+                local_updated_at: now,
+                sync_status: "pending".to_string(),
+                ..restored
+            };
+
+            if let Some(current) = &current {
+                record_revision(
+                    tx,
+                    HistoryEntity::Note,
+                    &current.id,
+                    current,
+                    RevisionSource::Restore,
+                    Some(&current.owner_id),
+                )?;
+            }
+            tx.execute(
+                r#"
+                INSERT INTO notes (
+                    id, owner_id, slug, title, description, visibility,
+                    created_at, updated_at, is_trashed, trashed_at,
+                    sync_status, synced_at, local_updated_at, server_updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+                ON CONFLICT(id) DO UPDATE SET
+                    title = excluded.title, description = excluded.description,
+                    visibility = excluded.visibility,
+                    is_trashed = excluded.is_trashed, trashed_at = excluded.trashed_at,
+                    updated_at = excluded.updated_at, local_updated_at = excluded.local_updated_at,
+                    sync_status = excluded.sync_status
+                "#,
+                rusqlite::params![
+                    restored.id,
+                    restored.owner_id,
+                    restored.slug,
+                    restored.title,
+                    restored.description,
+                    restored.visibility,
+                    restored.created_at,
+                    restored.updated_at,
+                    restored.is_trashed,
+                    restored.trashed_at,
+                    restored.sync_status,
+                    restored.synced_at,
+                    restored.local_updated_at,
+                    restored.server_updated_at,
+                ],
+            )?;
+
+            Ok(restored)
+        })
+    }
+
+    /// ページをスナップショットの内容に戻す
+    ///
+    /// タイトルはページ名の変更と同じく、このページへのリンクも書き換える（元に戻せない操作になる）。
+    /// ホームのノートはページの更新と同じく、ノートとページの対応も移す。
+    fn restore_page(&self, snapshot: LocalPage) -> Result<LocalPage, DbError> {
+        let id = snapshot.id.clone();
+        let (restored, rewritten_page_ids) =
+            self.with_journal("restore_revision", &[("pages", &id)], |tx| {
+                let now = chrono::Utc::now().to_rfc3339();
+                let Some(current) = find_page(tx, &id)? else {
+                    self.ensure_can_edit_page(tx, &snapshot)?;
+                    if let Some(note_id) = &snapshot.note_id {
+                        self.ensure_can_edit_note(tx, note_id)?;
+                    }
+                    let restored = LocalPage {
+                        updated_at: now.clone(),
+                        local_updated_at: now,
+                        sync_status: "pending".to_string(),
+                        ..snapshot
+                    };
+                    insert_restored_page(tx, &restored)?;
+                    return Ok((restored, Vec::new()));
+                };
+                self.ensure_can_edit_page(tx, &current)?;
+                if snapshot.note_id != current.note_id {
+                    if let Some(note_id) = &snapshot.note_id {
+                        self.ensure_can_edit_note(tx, note_id)?;
+                    }
+                }
+
+                let (previous, rewritten_page_ids) = if snapshot.title != current.title {
+                    let renamed = page_rename::rename_in_transaction(
+                        tx,
+                        &current,
+                        &snapshot.title,
+                        true,
+                        RevisionSource::Restore,
+                    )?;
+                    // 書き換えたページ本文の更新はジャーナルで戻せない
+                    tx.mark_irreversible();
+                    (renamed.page, renamed.rewritten_page_ids)
+                } else {
+                    record_revision(
+                        tx,
+                        HistoryEntity::Page,
+                        &current.id,
+                        &current,
+                        RevisionSource::Restore,
+                        Some(&current.user_id),
+                    )?;
+                    (current, Vec::new())
+                };
+
+                let restored = LocalPage {
+                    note_id: snapshot.note_id,
+                    thumbnail_url: snapshot.thumbnail_url,
+                    is_public: snapshot.is_public,
+                    updated_at: now.clone(),
+                    local_updated_at: now,
+                    sync_status: "pending".to_string(),
+                    ..previous.clone()
+                };
+                write_page_update(tx, &previous, &restored)?;
+
+                Ok((restored, rewritten_page_ids))
+            })?;
+
+        for page_id in &rewritten_page_ids {
+            self.compact_page_document_if_needed(page_id)?;
+        }
+
+        Ok(restored)
+    }
+
+    /// カードをスナップショットの内容に戻す
+    fn restore_card(&self, snapshot: LocalCard) -> Result<LocalCard, DbError> {
+        let id = snapshot.id.clone();
+        self.with_journal("restore_revision", &[("cards", &id)], |tx| {
+            let now = chrono::Utc::now().to_rfc3339();
+            let current = find_card(tx, &id)?;
+            let user_id = current.as_ref().map_or(&snapshot.user_id, |card| &card.user_id);
+            self.ensure_is_owner(user_id, &id)?;
+
+            let restored = match &current {
+                Some(current) => LocalCard {
+                    front_content: snapshot.front_content,
+                    back_content: snapshot.back_content,
+                    source_audio_url: snapshot.source_audio_url,
+                    source_ocr_image_url: snapshot.source_ocr_image_url,
+                    ..current.clone()
+                },
+                None => snapshot,
+            };
+            let restored = LocalCard {
+                updated_at: now.clone(),
+                local_updated_at: now,
+                sync_status: "pending".to_string(),
+                ..restored
+            };
+
+            if let Some(current) = &current {
+                record_revision(
+                    tx,
+                    HistoryEntity::Card,
+                    &current.id,
+                    current,
+                    RevisionSource::Restore,
+                    Some(&current.user_id),
+                )?;
+            }
+            tx.execute(
+                &format!(
+                    r#"
+                    INSERT INTO cards ({})
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26)
+                    ON CONFLICT(id) DO UPDATE SET
+                        front_content = excluded.front_content, back_content = excluded.back_content,
+                        source_audio_url = excluded.source_audio_url,
+                        source_ocr_image_url = excluded.source_ocr_image_url,
+                        updated_at = excluded.updated_at, local_updated_at = excluded.local_updated_at,
+                        sync_status = excluded.sync_status
+                    "#,
+                    CARD_COLUMNS
+                ),
+                rusqlite::params![
+                    restored.id,
+                    restored.deck_id,
+                    restored.user_id,
+                    restored.front_content,
+                    restored.back_content,
+                    restored.source_audio_url,
+                    restored.source_ocr_image_url,
+                    restored.created_at,
+                    restored.updated_at,
+                    restored.ease_factor,
+                    restored.repetition_count,
+                    restored.review_interval,
+                    restored.next_review_at,
+                    restored.stability,
+                    restored.difficulty,
+                    restored.last_reviewed_at,
+                    restored.sync_status,
+                    restored.synced_at,
+                    restored.local_updated_at,
+                    restored.server_updated_at,
+                    restored.card_kind,
+                    restored.card_note_id,
+                    restored.ordinal,
+                    restored.is_suspended,
+                    restored.buried_until,
+                    restored.flag,
+                ],
+            )?;
+
+            Ok(restored)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::tiptap::bracket_link_mark;
+    use crate::content::{extract_links, normalize_title_to_key, TiptapNode};
+    use crate::db::{fixtures, CardUpdate, NoteUpdate, PageUpdate};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_update_and_restore_note() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_note(&LocalNote {
+            title: "Original".to_string(),
+            ..fixtures::note("note-1")
+        })
+        .unwrap();
+
+        let updates = NoteUpdate {
+            title: Some("Edited".to_string()),
+            ..Default::default()
+        };
+        db.update_note("note-1", updates).unwrap();
+
+        let history = db.get_history(HistoryEntity::Note, "note-1").unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].source, "local_edit");
+        assert_eq!(history[0].changed_by.as_deref(), Some("user-1"));
+
+        let restored = db.restore_revision(&history[0].id).unwrap();
+        let RestoredEntity::Note(note) = restored else {
+            panic!("expected a note");
+        };
+        assert_eq!(note.title, "Original");
+        assert_eq!(note.sync_status, "pending");
+        assert_eq!(
+            db.get_note_by_id("note-1").unwrap().unwrap().title,
+            "Original"
+        );
+
+        // 復元前の内容も履歴に残る
+        let history = db.get_history(HistoryEntity::Note, "note-1").unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].source, "restore");
+    }
+
+    #[test]
+    fn test_server_overwrite_is_recorded() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_note(&LocalNote {
+            title: "Original".to_string(),
+            ..fixtures::note("note-1")
+        })
+        .unwrap();
+
+        let server_note = LocalNote {
+            title: "From server".to_string(),
+            updated_at: "2099-01-01T00:00:00+00:00".to_string(),
+            ..fixtures::note("note-1")
+        };
+        db.overwrite_note_with_server(&server_note).unwrap();
+
+        let history = db.get_history(HistoryEntity::Note, "note-1").unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].source, "server_overwrite");
+        assert!(history[0].changed_by.is_none());
+        assert!(history[0].snapshot.contains("Original"));
+    }
+
+    #[test]
+    fn test_history_is_capped_per_entity() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_page(&LocalPage {
+            title: "Original".to_string(),
+            ..fixtures::page("page-1")
+        })
+        .unwrap();
+
+        for i in 0..(MAX_REVISIONS_PER_ENTITY + 5) {
+            let updates = PageUpdate {
+                title: Some(format!("Title {}", i)),
+                ..Default::default()
+            };
+            db.update_page("page-1", updates).unwrap();
+        }
+
+        let history = db.get_history(HistoryEntity::Page, "page-1").unwrap();
+        assert_eq!(history.len() as i64, MAX_REVISIONS_PER_ENTITY);
+        assert!(history[0]
+            .snapshot
+            .contains(&format!("Title {}", MAX_REVISIONS_PER_ENTITY + 3)));
+    }
+
+    #[test]
+    fn test_restore_can_be_undone() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_note(&LocalNote {
+            title: "Original".to_string(),
+            ..fixtures::note("note-1")
+        })
+        .unwrap();
+        let updates = NoteUpdate {
+            title: Some("Edited".to_string()),
+            ..Default::default()
+        };
+        db.update_note("note-1", updates).unwrap();
+
+        let history = db.get_history(HistoryEntity::Note, "note-1").unwrap();
+        db.restore_revision(&history[0].id).unwrap();
+        db.undo().unwrap();
+
+        assert_eq!(
+            db.get_note_by_id("note-1").unwrap().unwrap().title,
+            "Edited"
+        );
+    }
+
+    #[test]
+    fn test_restore_page_moves_it_back_to_its_note() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_note(&fixtures::note("note-1")).unwrap();
+        db.insert_note(&fixtures::note("note-2")).unwrap();
+        db.insert_page(&LocalPage {
+            title: "Original".to_string(),
+            ..fixtures::page("page-1")
+        })
+        .unwrap();
+        for note_id in ["note-1", "note-2"] {
+            let updates = PageUpdate {
+                note_id: Some(Some(note_id.to_string())),
+                ..Default::default()
+            };
+            db.update_page("page-1", updates).unwrap();
+        }
+
+        let history = db.get_history(HistoryEntity::Page, "page-1").unwrap();
+        db.restore_revision(&history[0].id).unwrap();
+
+        let page = db.get_page_by_id("page-1").unwrap().unwrap();
+        assert_eq!(page.note_id.as_deref(), Some("note-1"));
+        assert_eq!(db.get_note_pages("note-1").unwrap().len(), 1);
+        assert!(db.get_note_pages("note-2").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_page_title_rewrites_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_page(&LocalPage {
+            title: "Original".to_string(),
+            ..fixtures::page("page-1")
+        })
+        .unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        let front = TiptapNode::new(
+            "doc",
+            vec![TiptapNode::new(
+                "paragraph",
+                vec![TiptapNode::text(
+                    "Original",
+                    vec![bracket_link_mark("Original")],
+                )],
+            )],
+        );
+        db.insert_card(&LocalCard {
+            front_content: serde_json::to_string(&front).unwrap(),
+            ..fixtures::card("card-1", "deck-1")
+        })
+        .unwrap();
+        db.rename_page("page-1", "Edited", true).unwrap();
+
+        let history = db.get_history(HistoryEntity::Page, "page-1").unwrap();
+        db.restore_revision(&history[0].id).unwrap();
+
+        let card = db.get_card_by_id("card-1").unwrap().unwrap();
+        let doc: TiptapNode = serde_json::from_str(&card.front_content).unwrap();
+        assert_eq!(
+            extract_links(&doc)[0].key,
+            normalize_title_to_key("Original")
+        );
+        // ページ本文の書き換えは元に戻せない
+        assert!(!db.get_undo_state().unwrap().can_undo);
+    }
+
+    #[test]
+    fn test_restore_requires_permission() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        // メンバーでないユーザーは他人のノートやカードを戻せない
+        db.switch_profile("mallory").unwrap();
+        db.insert_note(&LocalNote {
+            title: "Original".to_string(),
+            ..fixtures::note("note-1")
+        })
+        .unwrap();
+        db.overwrite_note_with_server(&LocalNote {
+            title: "From server".to_string(),
+            ..fixtures::note("note-1")
+        })
+        .unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        db.insert_card(&fixtures::card("card-1", "deck-1")).unwrap();
+        let updates = CardUpdate {
+            front_content: Some("{\"type\":\"doc\"}".to_string()),
+            ..Default::default()
+        };
+        db.update_card("card-1", updates).unwrap();
+
+        for (entity, id) in [
+            (HistoryEntity::Note, "note-1"),
+            (HistoryEntity::Card, "card-1"),
+        ] {
+            let history = db.get_history(entity, id).unwrap();
+            assert!(matches!(
+                db.restore_revision(&history[0].id),
+                Err(DbError::PermissionDenied(_))
+            ));
+        }
+        assert_eq!(
+            db.get_note_by_id("note-1").unwrap().unwrap().title,
+            "From server"
+        );
+    }
+}
@@ -0,0 +1,505 @@
+//! 操作ジャーナル（元に戻す / やり直す）
+//!
+//! 削除・更新などの操作の前後で対象行の内容を `_operation_journal` に記録し、
+//! `undo` では変更前の内容、`redo` では変更後の内容を書き戻す。
+//! 変更前の取得・操作・ジャーナルの記録は接続のロックを保ったまま1つのトランザクションで行い、
+//! 操作が失敗した場合はジャーナルも含めてすべて取り消す。
+//! ジャーナルはアプリの起動（セッション）ごとに管理し、件数には上限を設ける。
+//! ページ名の変更のように元に戻せない操作は、セッションのジャーナルを削除する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/journal_commands.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{DbError, LocalDB};
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{Connection, OptionalExtension, Transaction};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+
+/// セッションごとに保持する操作の上限
+pub const MAX_JOURNAL_ENTRIES: i64 = 100;
+
+/// 行の内容（カラム名 → 値）
+type RowImage = Map<String, Value>;
+
+/// 1行分の変更
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JournalChange {
+    table: String,
+    id: String,
+    /// 変更前の内容（None なら行は存在しなかった）
+    before: Option<RowImage>,
+    /// 変更後の内容（None なら行は削除された）
+    after: Option<RowImage>,
+}
+
+/// 元に戻す・やり直した操作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntrySummary {
+    pub id: String,
+    /// 操作名（例: "delete_deck"）
+    pub operation: String,
+    /// 書き戻した行数
+    pub changed_rows: usize,
+    pub created_at: String,
+}
+
+/// 元に戻す・やり直すの可否
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoState {
+    pub can_undo: bool,
+    pub can_redo: bool,
+    /// 次に元に戻す操作名
+    pub undo_operation: Option<String>,
+    /// 次にやり直す操作名
+    pub redo_operation: Option<String>,
+}
+
+/// 記録の対象の行と変更前の内容
+struct TrackedRow {
+    table: String,
+    id: String,
+    before: Option<RowImage>,
+}
+
+/// ジャーナルに記録しながら操作を行うトランザクション
+///
+/// 操作は `track` で変更する行を登録してから書き込む。
+/// `Connection` として使え、コミットは `with_journal` が行う。
+pub(crate) struct JournalTx<'conn> {
+    tx: Transaction<'conn>,
+    tracked: RefCell<Vec<TrackedRow>>,
+    irreversible: Cell<bool>,
+}
+
+impl Deref for JournalTx<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        &self.tx
+    }
+}
+
+impl JournalTx<'_> {
+    /// 変更する行を記録の対象にする（登録済みの行は何もしない）
+    pub(crate) fn track(&self, table: &str, id: &str) -> Result<(), DbError> {
+        if self
+            .tracked
+            .borrow()
+            .iter()
+            .any(|row| row.table == table && row.id == id)
+        {
+            return Ok(());
+        }
+        let before = capture_row(&self.tx, table, id)?;
+        self.tracked.borrow_mut().push(TrackedRow {
+            table: table.to_string(),
+            id: id.to_string(),
+            before,
+        });
+        Ok(())
+    }
+
+    /// 元に戻せない操作にする（記録せず、セッションのジャーナルを削除する）
+    ///
+    /// ジャーナルで戻せない変更を含む操作に使う。それより前の操作を元に戻すと
+    /// 状態が食い違うため、元に戻す・やり直す履歴もすべて破棄する。
+    pub(crate) fn mark_irreversible(&self) {
+        self.irreversible.set(true);
+    }
+}
+
+/// 行の現在の内容を取得する
+fn capture_row(conn: &Connection, table: &str, id: &str) -> Result<Option<RowImage>, DbError> {
+    let mut stmt = conn.prepare(&format!("SELECT * FROM {} WHERE id = ?1", table))?;
+    let names = stmt
+        .column_names()
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+
+    let image = stmt
+        .query_row([id], |row| {
+            let mut image = RowImage::new();
+            for (index, name) in names.iter().enumerate() {
+                let value = match row.get_ref(index)? {
+                    ValueRef::Null => Value::Null,
+                    ValueRef::Integer(v) => Value::from(v),
+                    ValueRef::Real(v) => Value::from(v),
+                    ValueRef::Text(v) => Value::from(String::from_utf8_lossy(v).to_string()),
+                    ValueRef::Blob(v) => Value::from(v.to_vec()),
+                };
+                image.insert(name.clone(), value);
+            }
+            Ok(image)
+        })
+        .optional()?;
+
+    Ok(image)
+}
+
+/// JSONの値をSQLiteの値に変換する
+fn to_sql_value(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(v) => SqlValue::Integer(*v as i64),
+        Value::Number(n) => match n.as_i64() {
+            Some(v) => SqlValue::Integer(v),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(v) => SqlValue::Text(v.clone()),
+        Value::Array(items) => SqlValue::Blob(
+            items
+                .iter()
+                .filter_map(|item| item.as_u64().map(|b| b as u8))
+                .collect(),
+        ),
+        Value::Object(_) => SqlValue::Text(value.to_string()),
+    }
+}
+
+/// 行を指定した内容に書き戻す
+///
+/// 同期対象のテーブルでは、削除状態に戻す場合を除き同期待ちにする。
+fn write_row(
+    conn: &Connection,
+    table: &str,
+    id: &str,
+    image: Option<&RowImage>,
+    now: &str,
+) -> Result<(), DbError> {
+    let Some(image) = image else {
+        conn.execute(&format!("DELETE FROM {} WHERE id = ?1", table), [id])?;
+        return Ok(());
+    };
+
+    let mut image = image.clone();
+    let is_deleted = image.get("sync_status").and_then(Value::as_str) == Some("deleted");
+    if image.contains_key("sync_status") && !is_deleted {
+        image.insert("sync_status".to_string(), Value::from("pending"));
+    }
+    if image.contains_key("local_updated_at") {
+        image.insert("local_updated_at".to_string(), Value::from(now));
+    }
+
+    let columns = image.keys().cloned().collect::<Vec<_>>();
+    let placeholders = (1..=columns.len())
+        .map(|i| format!("?{}", i))
+        .collect::<Vec<_>>();
+    let assignments = columns
+        .iter()
+        .filter(|column| column.as_str() != "id")
+        .map(|column| format!("{0} = excluded.{0}", column))
+        .collect::<Vec<_>>();
+
+    let sql = format!(
+        "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT(id) DO UPDATE SET {}",
+        table,
+        columns.join(", "),
+        placeholders.join(", "),
+        assignments.join(", "),
+    );
+    conn.execute(
+        &sql,
+        rusqlite::params_from_iter(image.values().map(to_sql_value)),
+    )?;
+
+    Ok(())
+}
+
+/// 別セッションのジャーナルを削除する
+pub(crate) fn clear_other_sessions(conn: &Connection, session_id: &str) -> Result<(), DbError> {
+    conn.execute(
+        "DELETE FROM _operation_journal WHERE session_id != ?1",
+        [session_id],
+    )?;
+    Ok(())
+}
+
+/// セッションのジャーナルを削除する（元に戻せない操作の後に使う）
+fn clear_session(conn: &Connection, session_id: &str) -> Result<(), DbError> {
+    conn.execute(
+        "DELETE FROM _operation_journal WHERE session_id = ?1",
+        [session_id],
+    )?;
+    Ok(())
+}
+
+impl LocalDB {
+    /// 操作を実行し、対象行の変更前後をジャーナルに記録する
+    ///
+    /// `targets` は操作によって変わる可能性のある (テーブル名, ID) の組。
+    /// 操作の中で決まる行は `JournalTx::track` で追加する。
+    pub(crate) fn with_journal<T, F>(
+        &self,
+        operation: &str,
+        targets: &[(&str, &str)],
+        f: F,
+    ) -> Result<T, DbError>
+    where
+        F: FnOnce(&JournalTx<'_>) -> Result<T, DbError>,
+    {
+        let mut conn = self.connection()?;
+        let journal = JournalTx {
+            tx: conn.transaction()?,
+            tracked: RefCell::new(Vec::new()),
+            irreversible: Cell::new(false),
+        };
+        for (table, id) in targets {
+            journal.track(table, id)?;
+        }
+
+        let result = f(&journal)?;
+
+        let JournalTx {
+            tx,
+            tracked,
+            irreversible,
+        } = journal;
+        if irreversible.get() {
+            clear_session(&tx, &self.session_id)?;
+            tx.commit()?;
+            return Ok(result);
+        }
+
+        let mut changes = Vec::new();
+        for row in tracked.into_inner() {
+            let after = capture_row(&tx, &row.table, &row.id)?;
+            if row.before != after {
+                changes.push(JournalChange {
+                    table: row.table,
+                    id: row.id,
+                    before: row.before,
+                    after,
+                });
+            }
+        }
+
+        if !changes.is_empty() {
+            // 新しい操作を記録したら、やり直し用の履歴は破棄する
+            tx.execute(
+                "DELETE FROM _operation_journal WHERE session_id = ?1 AND state = 'undone'",
+                [&self.session_id],
+            )?;
+            tx.execute(
+                r#"
+                INSERT INTO _operation_journal (id, session_id, operation, changes, state, created_at)
+                VALUES (?1, ?2, ?3, ?4, 'done', ?5)
+                "#,
+                rusqlite::params![
+                    uuid::Uuid::new_v4().to_string(),
+                    self.session_id,
+                    operation,
+                    serde_json::to_string(&changes)?,
+                    chrono::Utc::now().to_rfc3339(),
+                ],
+            )?;
+            tx.execute(
+                r#"
+                DELETE FROM _operation_journal
+                WHERE session_id = ?1
+                  AND rowid NOT IN (
+                    SELECT rowid FROM _operation_journal
+                    WHERE session_id = ?1
+                    ORDER BY rowid DESC
+                    LIMIT ?2
+                  )
+                "#,
+                rusqlite::params![self.session_id, MAX_JOURNAL_ENTRIES],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(result)
+    }
+
+    /// 直前の操作を元に戻す（戻す操作が無ければ None）
+    pub fn undo(&self) -> Result<Option<JournalEntrySummary>, DbError> {
+        self.replay_journal(true)
+    }
+
+    /// 元に戻した操作をやり直す（やり直す操作が無ければ None）
+    pub fn redo(&self) -> Result<Option<JournalEntrySummary>, DbError> {
+        self.replay_journal(false)
+    }
+
+    /// 元に戻す・やり直すの可否を取得
+    pub fn get_undo_state(&self) -> Result<UndoState, DbError> {
+        let conn = self.connection()?;
+        let undo_operation: Option<String> = conn
+            .query_row(
+                "SELECT operation FROM _operation_journal WHERE session_id = ?1 AND state = 'done' ORDER BY rowid DESC LIMIT 1",
+                [&self.session_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let redo_operation: Option<String> = conn
+            .query_row(
+                "SELECT operation FROM _operation_journal WHERE session_id = ?1 AND state = 'undone' ORDER BY rowid ASC LIMIT 1",
+                [&self.session_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(UndoState {
+            can_undo: undo_operation.is_some(),
+            can_redo: redo_operation.is_some(),
+            undo_operation,
+            redo_operation,
+        })
+    }
+
+    /// ジャーナルの変更を書き戻す
+    ///
+    /// `undo` が true なら最新の実行済み操作の変更前、false なら
+    /// 最も最近元に戻した操作の変更後の内容を書き戻す。
+    fn replay_journal(&self, undo: bool) -> Result<Option<JournalEntrySummary>, DbError> {
+        let mut conn = self.connection()?;
+        let tx = conn.transaction()?;
+
+        let sql = if undo {
+            "SELECT id, operation, changes, created_at FROM _operation_journal WHERE session_id = ?1 AND state = 'done' ORDER BY rowid DESC LIMIT 1"
+        } else {
+            "SELECT id, operation, changes, created_at FROM _operation_journal WHERE session_id = ?1 AND state = 'undone' ORDER BY rowid ASC LIMIT 1"
+        };
+        let entry = tx
+            .query_row(sql, [&self.session_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })
+            .optional()?;
+        let Some((entry_id, operation, changes, created_at)) = entry else {
+            return Ok(None);
+        };
+
+        let changes: Vec<JournalChange> = serde_json::from_str(&changes)?;
+        let now = chrono::Utc::now().to_rfc3339();
+
+        if undo {
+            for change in changes.iter().rev() {
+                write_row(&tx, &change.table, &change.id, change.before.as_ref(), &now)?;
+            }
+        } else {
+            for change in changes.iter() {
+                write_row(&tx, &change.table, &change.id, change.after.as_ref(), &now)?;
+            }
+        }
+
+        tx.execute(
+            "UPDATE _operation_journal SET state = ?1 WHERE id = ?2",
+            rusqlite::params![if undo { "undone" } else { "done" }, entry_id],
+        )?;
+        tx.commit()?;
+
+        Ok(Some(JournalEntrySummary {
+            id: entry_id,
+            operation,
+            changed_rows: changes.len(),
+            created_at,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{fixtures, PageUpdate};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_undo_and_redo_delete_deck() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+
+        db.delete_deck("deck-1").unwrap();
+        assert!(db.get_decks_by_user("user-1").unwrap().is_empty());
+
+        let undone = db.undo().unwrap().unwrap();
+        assert_eq!(undone.operation, "delete_deck");
+        let deck = db.get_deck_by_id("deck-1").unwrap().unwrap();
+        assert_eq!(deck.sync_status, "pending");
+
+        let state = db.get_undo_state().unwrap();
+        assert!(!state.can_undo);
+        assert_eq!(state.redo_operation.as_deref(), Some("delete_deck"));
+
+        db.redo().unwrap().unwrap();
+        let deck = db.get_deck_by_id("deck-1").unwrap().unwrap();
+        assert_eq!(deck.sync_status, "deleted");
+        assert!(db.redo().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_new_operation_clears_redo_stack() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        db.insert_deck(&fixtures::deck("deck-2")).unwrap();
+
+        db.delete_deck("deck-1").unwrap();
+        db.undo().unwrap();
+        db.delete_deck("deck-2").unwrap();
+
+        let state = db.get_undo_state().unwrap();
+        assert!(!state.can_redo);
+        assert_eq!(state.undo_operation.as_deref(), Some("delete_deck"));
+        assert!(db
+            .update_page("missing", PageUpdate::default())
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            db.get_undo_state().unwrap().undo_operation.as_deref(),
+            Some("delete_deck")
+        );
+    }
+
+    #[test]
+    fn test_failed_operation_rolls_back_with_its_journal() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+
+        let result: Result<(), DbError> = db.with_journal("failing", &[], |tx| {
+            tx.track("decks", "deck-1")?;
+            tx.execute("UPDATE decks SET title = 'Changed' WHERE id = 'deck-1'", [])?;
+            Err(DbError::ValidationError("failed".to_string()))
+        });
+        assert!(result.is_err());
+
+        assert_eq!(
+            db.get_deck_by_id("deck-1").unwrap().unwrap().title,
+            "deck-1"
+        );
+        assert!(!db.get_undo_state().unwrap().can_undo);
+    }
+
+    #[test]
+    fn test_journal_is_bounded() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+
+        for i in 0..(MAX_JOURNAL_ENTRIES + 10) {
+            let id = format!("deck-{}", i);
+            db.insert_deck(&fixtures::deck(&id)).unwrap();
+            db.delete_deck(&id).unwrap();
+        }
+
+        let mut undone = 0;
+        while db.undo().unwrap().is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, MAX_JOURNAL_ENTRIES);
+    }
+}
@@ -0,0 +1,614 @@
+//! ローカルメディアストア（音声・OCR画像・サムネイル）
+//!
+//! カードの音声やOCR画像、ページのサムネイルはリモートURLのため、オフラインでは
+//! 表示できない。取得したファイルは内容のSHA-256をファイル名として
+//! `media/<先頭2文字>/<ハッシュ>` に保存し（同じ内容は1つにまとまる）、
+//! `media` テーブルで管理する。カード・ページからの参照は `media_refs` に記録し、
+//! 参照されなくなったファイルは `collect_media_garbage` で削除する。
+//!
+//! 同期対象の `source_audio_url` などはリモートURLのまま残し、
+//! 表示時に `resolve_media_url` でローカルURI（`media://localhost/<ハッシュ>`）に置き換える。
+//! ローカルで生成したサムネイルだけは `thumbnail_url` に直接ローカルURIを設定する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   ├─ src-tauri/src/commands/media_commands.rs
+//!   └─ src-tauri/src/media_protocol.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{profiles, DbError, LocalDB, LocalPage, PageUpdate};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// メディアを配信するURIスキーム
+pub const MEDIA_SCHEME: &str = "media";
+
+/// メディアを保存するディレクトリ名（プロファイルのディレクトリ直下）
+const MEDIA_DIR: &str = "media";
+
+/// 参照されなくなってから削除するまでの猶予（時間）
+///
+/// 取り込み直後でまだ参照を登録していないファイルを消さないための猶予
+pub const MEDIA_GC_GRACE_HOURS: i64 = 24;
+
+/// MIMEタイプが不明な場合の値
+const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+/// メディアを参照するエンティティ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaEntity {
+    Card,
+    Page,
+}
+
+impl MediaEntity {
+    /// `media_refs.entity_type` に保存する値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaEntity::Card => "card",
+            MediaEntity::Page => "page",
+        }
+    }
+
+    /// メディアを参照できるフィールド
+    pub fn fields(&self) -> &'static [&'static str] {
+        match self {
+            MediaEntity::Card => &["source_audio_url", "source_ocr_image_url"],
+            MediaEntity::Page => &["thumbnail_url"],
+        }
+    }
+}
+
+/// ローカルに保存されたメディア
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalMedia {
+    /// 内容のSHA-256（16進小文字）
+    pub hash: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    /// 取得元のURL（ファイルから取り込んだ場合は None）
+    pub source_url: Option<String>,
+    pub created_at: String,
+    /// カード・ページからの参照数
+    pub ref_count: i64,
+    /// WebViewで読み込むためのURI
+    pub uri: String,
+}
+
+impl LocalMedia {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let hash: String = row.get(0)?;
+        Ok(LocalMedia {
+            uri: media_uri(&hash),
+            hash,
+            mime_type: row.get(1)?,
+            size_bytes: row.get(2)?,
+            source_url: row.get(3)?,
+            created_at: row.get(4)?,
+            ref_count: row.get(5)?,
+        })
+    }
+}
+
+/// ガベージコレクションの結果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MediaGcResult {
+    /// 削除された参照（参照元のカード・ページが存在しない）
+    pub removed_refs: usize,
+    /// 削除されたメディア
+    pub removed_media: usize,
+    /// 削除されたファイルの合計サイズ
+    pub removed_bytes: u64,
+}
+
+const SELECT_MEDIA: &str = "SELECT m.hash, m.mime_type, m.size_bytes, m.source_url, m.created_at,
+        (SELECT COUNT(*) FROM media_refs r WHERE r.hash = m.hash)
+     FROM media m";
+
+/// メディアのURIを作成
+pub fn media_uri(hash: &str) -> String {
+    format!("{}://localhost/{}", MEDIA_SCHEME, hash)
+}
+
+/// メディアのURIからハッシュを取り出す
+///
+/// Windows・Androidで使われる `http://media.localhost/<ハッシュ>` 形式も受け付ける
+pub fn parse_media_uri(uri: &str) -> Option<&str> {
+    let hash = [
+        format!("{}://localhost/", MEDIA_SCHEME),
+        format!("http://{}.localhost/", MEDIA_SCHEME),
+        format!("https://{}.localhost/", MEDIA_SCHEME),
+    ]
+    .iter()
+    .find_map(|prefix| uri.strip_prefix(prefix.as_str()))?;
+    is_valid_media_hash(hash).then_some(hash)
+}
+
+/// ハッシュとして正しい形式か（SHA-256の16進小文字）
+pub fn is_valid_media_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// 内容のハッシュを計算
+pub fn hash_media_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// ハッシュに対応するファイルのパス
+fn blob_path(media_dir: &Path, hash: &str) -> PathBuf {
+    media_dir.join(&hash[..2]).join(hash)
+}
+
+fn io_error(err: std::io::Error) -> DbError {
+    DbError::IoError(err.to_string())
+}
+
+fn validate_field(entity: MediaEntity, field: &str) -> Result<(), DbError> {
+    if entity.fields().contains(&field) {
+        Ok(())
+    } else {
+        Err(DbError::ValidationError(format!(
+            "{} has no media field: {}",
+            entity.as_str(),
+            field
+        )))
+    }
+}
+
+fn query_media(conn: &Connection, hash: &str) -> Result<Option<LocalMedia>, DbError> {
+    let media = conn
+        .query_row(
+            &format!("{} WHERE m.hash = ?1", SELECT_MEDIA),
+            [hash],
+            LocalMedia::from_row,
+        )
+        .optional()?;
+    Ok(media)
+}
+
+/// 保存ディレクトリ内のファイルを列挙する（ハッシュ, パス, サイズ）
+fn list_blobs(media_dir: &Path) -> Result<Vec<(String, PathBuf, u64)>, DbError> {
+    let mut blobs = Vec::new();
+    let Ok(shards) = std::fs::read_dir(media_dir) else {
+        return Ok(blobs);
+    };
+    for shard in shards {
+        let shard = shard.map_err(io_error)?;
+        if !shard.file_type().map_err(io_error)?.is_dir() {
+            continue;
+        }
+        for entry in std::fs::read_dir(shard.path()).map_err(io_error)? {
+            let entry = entry.map_err(io_error)?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            // 書き込み途中の一時ファイルなどは対象外
+            if !is_valid_media_hash(&name) {
+                continue;
+            }
+            let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+            blobs.push((name, entry.path(), size));
+        }
+    }
+    Ok(blobs)
+}
+
+impl LocalDB {
+    /// 現在のプロファイルのメディア保存ディレクトリ
+    fn media_dir(&self) -> Result<PathBuf, DbError> {
+        let dir = match self.active_profile()? {
+            Some(user_id) => profiles::profile_dir(&self.data_dir, &user_id),
+            None => self.data_dir.clone(),
+        };
+        Ok(dir.join(MEDIA_DIR))
+    }
+
+    /// 別のプロファイルのメディアファイルを現在のプロファイルに移す
+    ///
+    /// ゲストデータの引き継ぎで使う。同じ内容のファイルが既にあれば移さない。
+    pub(crate) fn move_media_files_from(&self, user_id: &str) -> Result<usize, DbError> {
+        let source_dir = profiles::profile_dir(&self.data_dir, user_id).join(MEDIA_DIR);
+        let media_dir = self.media_dir()?;
+
+        // ガベージコレクションと競合しないよう、接続をロックしてから移す
+        let _conn = self.connection()?;
+        let mut moved = 0;
+        for (hash, path, _) in list_blobs(&source_dir)? {
+            let dest = blob_path(&media_dir, &hash);
+            if dest.exists() {
+                continue;
+            }
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent).map_err(io_error)?;
+            }
+            std::fs::rename(&path, &dest).map_err(io_error)?;
+            moved += 1;
+        }
+        Ok(moved)
+    }
+
+    /// ファイルの内容をメディアストアに取り込む
+    ///
+    /// 同じ内容が既にあればそれを返す（取得元URLが未設定なら補う）。
+    pub fn ingest_media(
+        &self,
+        bytes: &[u8],
+        mime_type: Option<&str>,
+        source_url: Option<&str>,
+    ) -> Result<LocalMedia, DbError> {
+        let hash = hash_media_bytes(bytes);
+        let path = blob_path(&self.media_dir()?, &hash);
+        let mime_type = mime_type
+            .map(str::trim)
+            .filter(|mime| !mime.is_empty())
+            .unwrap_or(DEFAULT_MIME_TYPE);
+
+        // ガベージコレクションと競合しないよう、接続をロックしてから書き込む
+        let conn = self.connection()?;
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(io_error)?;
+            }
+            // 途中で終了しても壊れたファイルが残らないよう、一時ファイルから移動する
+            let temp_path = path.with_extension("tmp");
+            std::fs::write(&temp_path, bytes).map_err(io_error)?;
+            std::fs::rename(&temp_path, &path).map_err(io_error)?;
+        }
+
+        conn.execute(
+            "INSERT INTO media (hash, mime_type, size_bytes, source_url, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(hash) DO UPDATE SET
+                source_url = COALESCE(media.source_url, excluded.source_url)",
+            params![
+                hash,
+                mime_type,
+                bytes.len() as i64,
+                source_url,
+                chrono::Utc::now().to_rfc3339(),
+            ],
+        )?;
+
+        query_media(&conn, &hash)?.ok_or(DbError::NotFound(hash))
+    }
+
+    /// カード・ページのフィールドからメディアを参照する
+    ///
+    /// 同じフィールドに別のメディアが登録されていれば置き換える。
+    pub fn attach_media(
+        &self,
+        hash: &str,
+        entity: MediaEntity,
+        entity_id: &str,
+        field: &str,
+    ) -> Result<(), DbError> {
+        validate_field(entity, field)?;
+        let conn = self.connection()?;
+        if query_media(&conn, hash)?.is_none() {
+            return Err(DbError::NotFound(format!("media {}", hash)));
+        }
+
+        conn.execute(
+            "INSERT INTO media_refs (hash, entity_type, entity_id, field)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(entity_type, entity_id, field) DO UPDATE SET hash = excluded.hash",
+            params![hash, entity.as_str(), entity_id, field],
+        )?;
+        Ok(())
+    }
+
+    /// カード・ページからの参照を外す（`field` が None なら全フィールド）
+    pub fn detach_media(
+        &self,
+        entity: MediaEntity,
+        entity_id: &str,
+        field: Option<&str>,
+    ) -> Result<usize, DbError> {
+        if let Some(field) = field {
+            validate_field(entity, field)?;
+        }
+        let conn = self.connection()?;
+        let removed = conn.execute(
+            "DELETE FROM media_refs
+             WHERE entity_type = ?1 AND entity_id = ?2 AND (?3 IS NULL OR field = ?3)",
+            params![entity.as_str(), entity_id, field],
+        )?;
+        Ok(removed)
+    }
+
+    /// メディアを取得
+    pub fn get_media(&self, hash: &str) -> Result<Option<LocalMedia>, DbError> {
+        let conn = self.connection()?;
+        query_media(&conn, hash)
+    }
+
+    /// カード・ページが参照しているメディアを取得
+    pub fn get_entity_media(
+        &self,
+        entity: MediaEntity,
+        entity_id: &str,
+    ) -> Result<Vec<(String, LocalMedia)>, DbError> {
+        let conn = self.connection()?;
+        let refs = {
+            let mut stmt = conn.prepare(
+                "SELECT field, hash FROM media_refs
+                 WHERE entity_type = ?1 AND entity_id = ?2
+                 ORDER BY field",
+            )?;
+            let refs = stmt
+                .query_map(params![entity.as_str(), entity_id], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            refs
+        };
+
+        let mut media = Vec::new();
+        for (field, hash) in refs {
+            if let Some(item) = query_media(&conn, &hash)? {
+                media.push((field, item));
+            }
+        }
+        Ok(media)
+    }
+
+    /// メディアの内容を読み込む（URIスキームからの配信用）
+    pub fn read_media(&self, hash: &str) -> Result<Option<(LocalMedia, Vec<u8>)>, DbError> {
+        if !is_valid_media_hash(hash) {
+            return Ok(None);
+        }
+        let Some(media) = self.get_media(hash)? else {
+            return Ok(None);
+        };
+        match std::fs::read(blob_path(&self.media_dir()?, hash)) {
+            Ok(bytes) => Ok(Some((media, bytes))),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                log::warn!("Media file is missing: {}", hash);
+                Ok(None)
+            }
+            Err(err) => Err(io_error(err)),
+        }
+    }
+
+    /// 取得元URLからメディアを探す（同じURLが複数あれば最新のもの）
+    pub fn find_media_by_source_url(&self, url: &str) -> Result<Option<LocalMedia>, DbError> {
+        let conn = self.connection()?;
+        let media = conn
+            .query_row(
+                &format!(
+                    "{} WHERE m.source_url = ?1 ORDER BY m.created_at DESC LIMIT 1",
+                    SELECT_MEDIA
+                ),
+                [url],
+                LocalMedia::from_row,
+            )
+            .optional()?;
+        Ok(media)
+    }
+
+    /// 元のメディアから生成した派生メディア（サムネイルなど）を取得
+    pub fn get_media_variant(
+        &self,
+        source_hash: &str,
+        variant: &str,
+    ) -> Result<Option<LocalMedia>, DbError> {
+        let conn = self.connection()?;
+        let hash: Option<String> = conn
+            .query_row(
+                "SELECT hash FROM media_variants WHERE source_hash = ?1 AND variant = ?2",
+                params![source_hash, variant],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match hash {
+            Some(hash) => query_media(&conn, &hash),
+            None => Ok(None),
+        }
+    }
+
+    /// 派生メディアを記録する
+    pub fn put_media_variant(
+        &self,
+        source_hash: &str,
+        variant: &str,
+        hash: &str,
+    ) -> Result<(), DbError> {
+        let conn = self.connection()?;
+        conn.execute(
+            "INSERT INTO media_variants (source_hash, variant, hash, created_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(source_hash, variant) DO UPDATE SET
+                hash = excluded.hash, created_at = excluded.created_at",
+            params![source_hash, variant, hash, chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// ページのサムネイルをメディアに設定する
+    ///
+    /// `thumbnail_url` をメディアのURIに更新し、参照を登録する。
+    pub fn set_page_thumbnail(
+        &self,
+        page_id: &str,
+        media: &LocalMedia,
+    ) -> Result<Option<LocalPage>, DbError> {
+        let updated = self.update_page(
+            page_id,
+            PageUpdate {
+                thumbnail_url: Some(Some(media.uri.clone())),
+                ..Default::default()
+            },
+        )?;
+        if updated.is_some() {
+            self.attach_media(&media.hash, MediaEntity::Page, page_id, "thumbnail_url")?;
+        }
+        Ok(updated)
+    }
+
+    /// リモートURLを取り込み済みメディアのURIに解決する
+    ///
+    /// 取り込んでいなければ None（呼び出し側は元のURLをそのまま使う）
+    pub fn resolve_media_url(&self, url: &str) -> Result<Option<String>, DbError> {
+        Ok(self.find_media_by_source_url(url)?.map(|media| media.uri))
+    }
+
+    /// 参照されなくなったメディアを削除する
+    ///
+    /// 1. 参照元のカード・ページが存在しない参照を削除
+    /// 2. 猶予期間を過ぎても参照されていないメディアとファイルを削除
+    /// 3. テーブルに登録されていないファイルを削除
+    pub fn collect_media_garbage(&self) -> Result<MediaGcResult, DbError> {
+        let media_dir = self.media_dir()?;
+        let cutoff =
+            (chrono::Utc::now() - chrono::Duration::hours(MEDIA_GC_GRACE_HOURS)).to_rfc3339();
+        let mut result = MediaGcResult::default();
+
+        let mut conn = self.connection()?;
+        let tx = conn.transaction()?;
+        result.removed_refs = tx.execute(
+            "DELETE FROM media_refs
+             WHERE (entity_type = 'card' AND entity_id NOT IN (SELECT id FROM cards))
+                OR (entity_type = 'page' AND entity_id NOT IN (SELECT id FROM pages))",
+            [],
+        )?;
+
+        let unreferenced = {
+            let mut stmt = tx.prepare(
+                "SELECT hash FROM media
+                 WHERE created_at < ?1
+                   AND hash NOT IN (SELECT hash FROM media_refs)",
+            )?;
+            let hashes = stmt
+                .query_map([&cutoff], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            hashes
+        };
+        for hash in &unreferenced {
+            tx.execute("DELETE FROM media WHERE hash = ?1", [hash])?;
+        }
+        result.removed_media = unreferenced.len();
+
+        let known = {
+            let mut stmt = tx.prepare("SELECT hash FROM media")?;
+            let hashes = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<HashSet<_>, _>>()?;
+            hashes
+        };
+        tx.commit()?;
+
+        // 行を削除した後でファイルを消す（逆順だと失敗時に行だけが残る）
+        for (hash, path, size) in list_blobs(&media_dir)? {
+            if known.contains(&hash) {
+                continue;
+            }
+            match std::fs::remove_file(&path) {
+                Ok(()) => result.removed_bytes += size,
+                Err(err) => log::warn!("Failed to remove media file {}: {}", hash, err),
+            }
+        }
+
+        if result.removed_media > 0 || result.removed_bytes > 0 {
+            log::info!(
+                "Media GC removed {} media ({} bytes), {} refs",
+                result.removed_media,
+                result.removed_bytes,
+                result.removed_refs
+            );
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures;
+    use tempfile::TempDir;
+
+    /// 猶予期間が過ぎたことにする
+    fn age_media(db: &LocalDB) {
+        let old =
+            (chrono::Utc::now() - chrono::Duration::hours(MEDIA_GC_GRACE_HOURS + 1)).to_rfc3339();
+        db.connection()
+            .unwrap()
+            .execute("UPDATE media SET created_at = ?1", [old])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_ingest_deduplicates_by_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+
+        let first = db.ingest_media(b"image", Some("image/png"), None).unwrap();
+        let second = db
+            .ingest_media(
+                b"image",
+                Some("image/png"),
+                Some("https://example.com/a.png"),
+            )
+            .unwrap();
+        assert_eq!(first.hash, second.hash);
+        assert_eq!(
+            second.source_url.as_deref(),
+            Some("https://example.com/a.png")
+        );
+        assert_eq!(second.uri, format!("media://localhost/{}", first.hash));
+
+        let (media, bytes) = db.read_media(&first.hash).unwrap().unwrap();
+        assert_eq!(bytes, b"image");
+        assert_eq!(media.size_bytes, 5);
+        assert_eq!(
+            db.resolve_media_url("https://example.com/a.png").unwrap(),
+            Some(first.uri)
+        );
+        assert!(db
+            .resolve_media_url("https://example.com/b.png")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_garbage_collection_removes_unreferenced_media() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_page(&LocalPage {
+            thumbnail_url: Some("https://example.com/thumb.png".to_string()),
+            ..fixtures::page("page-1")
+        })
+        .unwrap();
+
+        let kept = db.ingest_media(b"kept", None, None).unwrap();
+        let dropped = db.ingest_media(b"dropped", None, None).unwrap();
+        db.attach_media(&kept.hash, MediaEntity::Page, "page-1", "thumbnail_url")
+            .unwrap();
+        db.attach_media(&dropped.hash, MediaEntity::Page, "page-2", "thumbnail_url")
+            .unwrap();
+        assert!(db
+            .attach_media(&kept.hash, MediaEntity::Page, "page-1", "source_audio_url")
+            .is_err());
+
+        // 猶予期間中は削除しない
+        let result = db.collect_media_garbage().unwrap();
+        assert_eq!(result.removed_refs, 1);
+        assert_eq!(result.removed_media, 0);
+
+        age_media(&db);
+        let result = db.collect_media_garbage().unwrap();
+        assert_eq!(result.removed_media, 1);
+        assert_eq!(result.removed_bytes, 7);
+        assert!(db.read_media(&dropped.hash).unwrap().is_none());
+        assert_eq!(db.get_media(&kept.hash).unwrap().unwrap().ref_count, 1);
+
+        db.detach_media(MediaEntity::Page, "page-1", None).unwrap();
+        let result = db.collect_media_garbage().unwrap();
+        assert_eq!(result.removed_media, 1);
+        assert!(db.get_media(&kept.hash).unwrap().is_none());
+    }
+}
@@ -10,75 +10,178 @@
 //! Children (Modules in this module):
 //!   ├─ schema.rs - スキーマ定義
 //!   ├─ models.rs - データモデル
+//!   ├─ profiles.rs - ユーザー別DB（プロファイル）管理
+//!   ├─ guest.rs - ゲストモードとデータ引き継ぎ
+//!   ├─ sign_out.rs - サインアウト時のデータ保持・消去・封印
+//!   ├─ change_events.rs - 変更イベントとクエリ購読
+//!   ├─ history.rs - 変更履歴と復元
+//!   ├─ journal.rs - 操作ジャーナル（元に戻す / やり直す）
+//!   ├─ page_documents.rs - ページ本文（Yjsドキュメント）の保存
+//!   ├─ page_document_compaction.rs - ページ本文の更新ログの圧縮
+//!   ├─ page_links.rs - ページリンクのインデックス
+//!   ├─ page_rename.rs - ページ名の変更とリンクの書き換え
+//!   ├─ note_pages.rs - ノートとページの多対多の対応
+//!   ├─ note_slugs.rs - ノートのスラッグとリダイレクト
+//!   ├─ note_members.rs - ノートの共有とメンバーの権限
+//!   ├─ media.rs - ローカルメディアストア
+//!   ├─ deck_tree.rs - デッキの階層
+//!   ├─ card_notes.rs - カードノート（穴埋め・表裏）と兄弟カードの生成
+//!   ├─ card_status.rs - カードの一時停止・延期・フラグ
+//!   ├─ deck_configs.rs - デッキの学習オプション（プリセット）
+//!   ├─ review.rs - 復習のスケジュールと1日の上限
+//!   ├─ questions.rs - 問題（クイズ）と問題ごとの成績
+//!   ├─ quiz.rs - 多肢選択クイズの生成（誤答の選択）とタイピングの採点
+//!   ├─ tags.rs - タグ
+//!   ├─ fixtures.rs - テスト用のデータ（テストのみ）
 //!   └─ error.rs - エラー型
 //!
 //! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
 //! Issue: https://github.com/otomatty/for-all-learners/issues/191
 
+pub mod card_notes;
+pub mod card_status;
+pub mod change_events;
+pub mod deck_configs;
+pub mod deck_tree;
 pub mod error;
+#[cfg(test)]
+mod fixtures;
+pub mod guest;
+pub mod history;
+pub mod journal;
+pub mod media;
 pub mod models;
+pub mod note_members;
+pub mod note_pages;
+pub mod note_slugs;
+pub mod page_document_compaction;
+pub mod page_documents;
+pub mod page_links;
+pub mod page_rename;
+pub mod profiles;
+pub mod questions;
+pub mod quiz;
+pub mod review;
 pub mod schema;
+pub mod sign_out;
+pub mod tags;
 
-use rusqlite::Connection;
-use std::path::PathBuf;
+use journal::JournalTx;
+use rusqlite::{Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Manager};
 
+pub use card_notes::{CardNoteKind, CardNoteWithCards};
+pub use card_status::CARD_FLAGS;
+pub use change_events::{DbChangedEvent, QuerySubscription, DB_CHANGED_EVENT};
+pub use deck_configs::DeckStudyOptions;
+pub use deck_tree::DeckTreeNode;
 pub use error::DbError;
+pub use guest::{GuestClaimResult, GUEST_USER_ID};
+pub use history::{HistoryEntity, RestoredEntity, RevisionSource};
+pub use journal::{JournalEntrySummary, UndoState};
+pub use media::{LocalMedia, MediaEntity, MediaGcResult, MEDIA_SCHEME};
 pub use models::*;
+pub use note_members::{NoteRole, SharedNote};
+pub use page_document_compaction::{PageDocumentCompaction, PageDocumentStats};
+pub use page_documents::{DocumentUpdateOrigin, PageDocumentDiff, PageDocumentState};
+pub use page_links::{PageLink, TwoHopLink, UnresolvedLink};
+pub use page_rename::PageRenameResult;
+pub use profiles::LocalProfile;
+pub use questions::{QuestionStats, QuestionType};
+pub use quiz::{QuizChoice, QuizQuestion, DEFAULT_CHOICE_COUNT};
+pub use review::{CardSchedule, ReviewResult};
 pub use schema::*;
+pub use sign_out::{SignOutMode, SignOutResult};
+pub use tags::{TagEntity, TagMergeResult, TagSummary, TaggedEntity};
 
 /// データベースファイル名
 const DB_FILE_NAME: &str = "local.db";
 
 /// ローカルデータベース
+///
+/// ユーザーごとに `profiles/<user_id>/local.db` を使い分ける。
+/// プロファイル未選択（未ログイン）の間はアプリデータ直下の `local.db` を使用する。
 pub struct LocalDB {
     conn: Arc<Mutex<Connection>>,
+    /// アプリデータディレクトリ
+    data_dir: PathBuf,
+    /// 現在開いているプロファイルのユーザーID
+    active_profile: Mutex<Option<String>>,
+    /// 変更イベントの収集
+    changes: Arc<change_events::ChangeTracker>,
+    /// 操作ジャーナルのセッションID（起動ごとに生成）
+    session_id: String,
 }
 
 impl LocalDB {
     /// 新しいデータベース接続を作成
     pub fn new(app_handle: &AppHandle) -> Result<Self, DbError> {
-        let db_path = Self::get_db_path(app_handle)?;
+        let data_dir = app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| DbError::PathError(e.to_string()))?;
+
+        Self::open(data_dir)
+    }
+
+    /// データディレクトリを指定してデータベースを開く
+    ///
+    /// 前回アクティブだったプロファイルがあれば、そのDBを開く。
+    pub fn open(data_dir: PathBuf) -> Result<Self, DbError> {
+        let active_profile = profiles::read_active_profile(&data_dir)?;
+        let db_path = Self::get_db_path(&data_dir, active_profile.as_deref());
+        let conn = Self::open_connection(&db_path)?;
+
+        let changes = Arc::new(change_events::ChangeTracker::default());
+        changes.install_hooks(&conn);
+
+        // 前回起動時の操作ジャーナルは元に戻せないので破棄する
+        let session_id = uuid::Uuid::new_v4().to_string();
+        journal::clear_other_sessions(&conn, &session_id)?;
 
+        Ok(LocalDB {
+            conn: Arc::new(Mutex::new(conn)),
+            data_dir,
+            active_profile: Mutex::new(active_profile),
+            changes,
+            session_id,
+        })
+    }
+
+    /// データベースファイルのパスを取得
+    fn get_db_path(data_dir: &Path, profile: Option<&str>) -> PathBuf {
+        match profile {
+            Some(user_id) => profiles::profile_dir(data_dir, user_id).join(DB_FILE_NAME),
+            None => data_dir.join(DB_FILE_NAME),
+        }
+    }
+
+    /// 接続を開き、PRAGMA設定とマイグレーションを行う
+    fn open_connection(db_path: &Path) -> Result<Connection, DbError> {
         // ディレクトリを作成
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| DbError::IoError(e.to_string()))?;
         }
 
-        let conn = Connection::open(&db_path).map_err(DbError::from)?;
+        let conn = Connection::open(db_path).map_err(DbError::from)?;
 
         // WALモードを有効化（パフォーマンス向上）
         conn.execute_batch("PRAGMA journal_mode=WAL;")?;
         // 外部キー制約を有効化
         conn.execute_batch("PRAGMA foreign_keys=ON;")?;
 
-        let db = LocalDB {
-            conn: Arc::new(Mutex::new(conn)),
-        };
-
         // マイグレーション実行
-        db.run_migrations()?;
-
-        Ok(db)
-    }
-
-    /// データベースファイルのパスを取得
-    fn get_db_path(app_handle: &AppHandle) -> Result<PathBuf, DbError> {
-        let app_data_dir = app_handle
-            .path()
-            .app_data_dir()
-            .map_err(|e| DbError::PathError(e.to_string()))?;
+        Self::run_migrations(&conn)?;
 
-        Ok(app_data_dir.join(DB_FILE_NAME))
+        Ok(conn)
     }
 
     /// マイグレーションを実行
-    fn run_migrations(&self) -> Result<(), DbError> {
-        let conn = self.conn.lock().map_err(|e| DbError::LockError(e.to_string()))?;
-
+    fn run_migrations(conn: &Connection) -> Result<(), DbError> {
         // 現在のバージョンを取得
-        let current_version = self.get_db_version(&conn)?;
+        let current_version = Self::get_db_version(conn)?;
 
         if current_version < schema::DB_VERSION {
             log::info!(
@@ -87,13 +190,77 @@ impl LocalDB {
                 schema::DB_VERSION
             );
 
+            // v12 でデッキに親デッキを追加（インデックスを作る前にカラムを足す）
+            if current_version > 0 && current_version < 12 {
+                Self::add_column_if_missing(conn, "decks", "parent_deck_id", "TEXT")?;
+            }
+
+            // v13 でデッキに学習オプションのプリセットを追加
+            if current_version > 0 && current_version < 13 {
+                Self::add_column_if_missing(conn, "decks", "config_id", "TEXT")?;
+            }
+
+            // v14 でカードに種類と生成元のカードノートを追加
+            if current_version > 0 && current_version < 14 {
+                Self::add_column_if_missing(
+                    conn,
+                    "cards",
+                    "card_kind",
+                    "TEXT NOT NULL DEFAULT 'basic'",
+                )?;
+                Self::add_column_if_missing(conn, "cards", "card_note_id", "TEXT")?;
+                Self::add_column_if_missing(conn, "cards", "ordinal", "INTEGER")?;
+            }
+
+            // v15 でカードノートに裏面の入力欄を追加（v14 より前は表ごと新しく作る）
+            if current_version == 14 {
+                Self::add_column_if_missing(conn, "card_notes", "back_text", "TEXT")?;
+            }
+
+            // v17 でカードに一時停止・延期・フラグを追加
+            if current_version > 0 && current_version < 17 {
+                Self::add_column_if_missing(
+                    conn,
+                    "cards",
+                    "is_suspended",
+                    "INTEGER NOT NULL DEFAULT 0",
+                )?;
+                Self::add_column_if_missing(conn, "cards", "buried_until", "TEXT")?;
+                Self::add_column_if_missing(
+                    conn,
+                    "cards",
+                    "flag",
+                    "TEXT CHECK (flag IN ('red', 'orange', 'green', 'blue', 'pink', 'turquoise', 'purple'))",
+                )?;
+            }
+
+            // v18 で学習ログに未受信の問題IDを追加（外部キーは全スキーマの適用後に付ける）
+            if current_version > 0 && current_version < 18 {
+                Self::add_column_if_missing(conn, "learning_logs", "pending_question_id", "TEXT")?;
+            }
+
             // 全スキーマを適用
             for schema_sql in schema::get_all_schemas() {
                 conn.execute_batch(schema_sql)?;
             }
 
+            // v7 でページリンクのインデックスを追加したため、既存の本文から作り直す
+            if current_version > 0 && current_version < 7 {
+                page_links::rebuild_page_links(conn)?;
+            }
+
+            // v9 でノートとページを多対多にしたため、既存の `pages.note_id` を移す
+            if current_version > 0 && current_version < 9 {
+                note_pages::migrate_page_note_ids(conn)?;
+            }
+
+            // v18 で学習ログの問題IDに外部キーを付けたため、学習ログの表を作り直す
+            if current_version > 0 && current_version < 18 {
+                questions::rebuild_learning_logs(conn)?;
+            }
+
             // バージョンを更新
-            self.set_db_version(&conn, schema::DB_VERSION)?;
+            Self::set_db_version(conn, schema::DB_VERSION)?;
 
             log::info!("Migrations completed successfully");
         }
@@ -101,8 +268,32 @@ impl LocalDB {
         Ok(())
     }
 
+    /// 既存のテーブルにカラムが無ければ追加する
+    fn add_column_if_missing(
+        conn: &Connection,
+        table: &str,
+        column: &str,
+        definition: &str,
+    ) -> Result<(), DbError> {
+        let exists: bool = conn.query_row(
+            &format!(
+                "SELECT EXISTS(SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1)",
+                table
+            ),
+            [column],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            conn.execute_batch(&format!(
+                "ALTER TABLE {} ADD COLUMN {} {}",
+                table, column, definition
+            ))?;
+        }
+        Ok(())
+    }
+
     /// データベースバージョンを取得
-    fn get_db_version(&self, conn: &Connection) -> Result<i32, DbError> {
+    fn get_db_version(conn: &Connection) -> Result<i32, DbError> {
         // メタデータテーブルが存在するか確認
         let table_exists: bool = conn
             .query_row(
@@ -131,7 +322,7 @@ impl LocalDB {
     }
 
     /// データベースバージョンを設定
-    fn set_db_version(&self, conn: &Connection, version: i32) -> Result<(), DbError> {
+    fn set_db_version(conn: &Connection, version: i32) -> Result<(), DbError> {
         conn.execute(
             "INSERT OR REPLACE INTO _metadata (key, value) VALUES ('db_version', ?1)",
             [version.to_string()],
@@ -151,6 +342,24 @@ impl LocalDB {
 // Notes CRUD
 // ============================================================================
 
+/// IDでノートを取得（トランザクション内から使う）
+pub(crate) fn find_note(conn: &Connection, id: &str) -> Result<Option<LocalNote>, DbError> {
+    let note = conn
+        .query_row(
+            r#"
+            SELECT id, owner_id, slug, title, description, visibility,
+                   created_at, updated_at, is_trashed, trashed_at,
+                   sync_status, synced_at, local_updated_at, server_updated_at
+            FROM notes
+            WHERE id = ?1
+            "#,
+            [id],
+            LocalNote::from_row,
+        )
+        .optional()?;
+    Ok(note)
+}
+
 impl LocalDB {
     /// 全ノートを取得
     pub fn get_notes_by_owner(&self, owner_id: &str) -> Result<Vec<LocalNote>, DbError> {
@@ -176,27 +385,26 @@ impl LocalDB {
     /// IDでノートを取得
     pub fn get_note_by_id(&self, id: &str) -> Result<Option<LocalNote>, DbError> {
         let conn = self.connection()?;
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT id, owner_id, slug, title, description, visibility,
-                   created_at, updated_at, is_trashed, trashed_at,
-                   sync_status, synced_at, local_updated_at, server_updated_at
-            FROM notes
-            WHERE id = ?1
-            "#,
-        )?;
-
-        let note = stmt
-            .query_row([id], |row| LocalNote::from_row(row))
-            .ok();
-
-        Ok(note)
+        find_note(&conn, id)
     }
 
     /// ノートを作成
-    pub fn insert_note(&self, note: &LocalNote) -> Result<(), DbError> {
-        let conn = self.connection()?;
-        conn.execute(
+    ///
+    /// スラッグが空ならタイトルから作る。ローカルで作成したノート（`pending`）は
+    /// 所有者内でスラッグが重複する場合に番号を付ける。保存したノートを返す。
+    pub fn insert_note(&self, note: &LocalNote) -> Result<LocalNote, DbError> {
+        let mut note = note.clone();
+        if note.slug.trim().is_empty() {
+            note.slug = note_slugs::slugify_title(&note.title);
+        }
+
+        let mut conn = self.connection()?;
+        let tx = conn.transaction()?;
+        if note.sync_status == "pending" {
+            note.slug = note_slugs::next_available_slug(&tx, &note.owner_id, &note.slug, None)?;
+        }
+        note_slugs::clear_slug_redirect(&tx, &note.owner_id, &note.slug)?;
+        tx.execute(
             r#"
             INSERT INTO notes (
                 id, owner_id, slug, title, description, visibility,
@@ -221,81 +429,111 @@ impl LocalDB {
                 note.server_updated_at,
             ],
         )?;
-        Ok(())
+        tx.commit()?;
+        Ok(note)
     }
 
     /// ノートを更新
+    ///
+    /// 現在のユーザーが閲覧者の場合は `DbError::PermissionDenied` を返す。
     pub fn update_note(&self, id: &str, updates: NoteUpdate) -> Result<Option<LocalNote>, DbError> {
-        let conn = self.connection()?;
-        let now = chrono::Utc::now().to_rfc3339();
+        self.with_journal("update_note", &[("notes", id)], |tx| {
+            let now = chrono::Utc::now().to_rfc3339();
 
-        // 現在のノートを取得
-        let current = self.get_note_by_id(id)?;
-        if current.is_none() {
-            return Ok(None);
-        }
-        let current = current.unwrap();
-
-        // 更新を適用
-        let updated = LocalNote {
-            title: updates.title.unwrap_or(current.title),
-            description: updates.description.or(current.description),
-            visibility: updates.visibility.unwrap_or(current.visibility),
-            is_trashed: updates.is_trashed.unwrap_or(current.is_trashed),
-            trashed_at: updates.trashed_at.or(current.trashed_at),
-            updated_at: now.clone(),
-            local_updated_at: now.clone(),
-            sync_status: "pending".to_string(),
-            ..current
-        };
-
-        conn.execute(
-            r#"
-            UPDATE notes SET
-                title = ?1, description = ?2, visibility = ?3,
-                is_trashed = ?4, trashed_at = ?5,
-                updated_at = ?6, local_updated_at = ?7, sync_status = ?8
-            WHERE id = ?9
-            "#,
-            rusqlite::params![
-                updated.title,
-                updated.description,
-                updated.visibility,
-                updated.is_trashed,
-                updated.trashed_at,
-                updated.updated_at,
-                updated.local_updated_at,
-                updated.sync_status,
+            // 現在のノートを取得
+            let current = find_note(tx, id)?;
+            if current.is_none() {
+                return Ok(None);
+            }
+            let current = current.unwrap();
+            self.ensure_can_edit_note(tx, id)?;
+            let previous = current.clone();
+
+            // 更新を適用
+            let updated = LocalNote {
+                title: updates.title.unwrap_or(current.title),
+                description: updates.description.or(current.description),
+                visibility: updates.visibility.unwrap_or(current.visibility),
+                is_trashed: updates.is_trashed.unwrap_or(current.is_trashed),
+                trashed_at: updates.trashed_at.or(current.trashed_at),
+                updated_at: now.clone(),
+                local_updated_at: now.clone(),
+                sync_status: "pending".to_string(),
+                ..current
+            };
+
+            // 変更前の内容を履歴に残す
+            history::record_revision(
+                tx,
+                HistoryEntity::Note,
                 id,
-            ],
-        )?;
-
-        Ok(Some(updated))
+                &previous,
+                RevisionSource::LocalEdit,
+                Some(&previous.owner_id),
+            )?;
+
+            tx.execute(
+                r#"
+                UPDATE notes SET
+                    title = ?1, description = ?2, visibility = ?3,
+                    is_trashed = ?4, trashed_at = ?5,
+                    updated_at = ?6, local_updated_at = ?7, sync_status = ?8
+                WHERE id = ?9
+                "#,
+                rusqlite::params![
+                    updated.title,
+                    updated.description,
+                    updated.visibility,
+                    updated.is_trashed,
+                    updated.trashed_at,
+                    updated.updated_at,
+                    updated.local_updated_at,
+                    updated.sync_status,
+                    id,
+                ],
+            )?;
+
+            Ok(Some(updated))
+        })
     }
 
     /// ノートを削除（論理削除）
+    ///
+    /// 共有したメンバーからも見えなくなるため、所有者以外は `DbError::PermissionDenied` を返す。
     pub fn delete_note(&self, id: &str) -> Result<bool, DbError> {
-        let conn = self.connection()?;
-        let now = chrono::Utc::now().to_rfc3339();
-
-        let rows_affected = conn.execute(
-            r#"
-            UPDATE notes SET
-                sync_status = 'deleted',
-                local_updated_at = ?1
-            WHERE id = ?2
-            "#,
-            rusqlite::params![now, id],
-        )?;
-
-        Ok(rows_affected > 0)
+        self.with_journal("delete_note", &[("notes", id)], |conn| {
+            if find_note(conn, id)?.is_none() {
+                return Ok(false);
+            }
+            self.ensure_can_manage_note(conn, id)?;
+            let now = chrono::Utc::now().to_rfc3339();
+
+            let rows_affected = conn.execute(
+                r#"
+                UPDATE notes SET
+                    sync_status = 'deleted',
+                    local_updated_at = ?1
+                WHERE id = ?2
+                "#,
+                rusqlite::params![now, id],
+            )?;
+
+            Ok(rows_affected > 0)
+        })
     }
 
     /// ノートを物理削除
+    ///
+    /// 所有者以外は `DbError::PermissionDenied` を返す。
     pub fn hard_delete_note(&self, id: &str) -> Result<(), DbError> {
-        let conn = self.connection()?;
-        conn.execute("DELETE FROM notes WHERE id = ?1", [id])?;
-        Ok(())
+        self.with_journal("hard_delete_note", &[("notes", id)], |conn| {
+            if find_note(conn, id)?.is_none() {
+                return Ok(());
+            }
+            self.ensure_can_manage_note(conn, id)?;
+            conn.execute("DELETE FROM notes WHERE id = ?1", [id])?;
+            Ok(())
+        })
     }
 
     /// 同期待ちのノートを取得
@@ -358,17 +596,44 @@ impl LocalDB {
     }
 
     /// サーバーデータでノートを上書き
+    ///
+    /// 行を置き換えるとノートのページ・メンバー・共有リンク・スラッグのリダイレクトが
+    /// 外部キーで一緒に消えるため、既存の行は更新する。
     pub fn overwrite_note_with_server(&self, note: &LocalNote) -> Result<(), DbError> {
-        let conn = self.connection()?;
         let now = chrono::Utc::now().to_rfc3339();
 
-        conn.execute(
+        let mut conn = self.connection()?;
+        let tx = conn.transaction()?;
+        let previous = find_note(&tx, &note.id)?;
+
+        // 上書きで失われるローカルの内容を履歴に残す
+        if let Some(previous) = previous.filter(|p| p.updated_at != note.updated_at) {
+            history::record_revision(
+                &tx,
+                HistoryEntity::Note,
+                &note.id,
+                &previous,
+                RevisionSource::ServerOverwrite,
+                None,
+            )?;
+        }
+
+        tx.execute(
             r#"
-            INSERT OR REPLACE INTO notes (
+            INSERT INTO notes (
                 id, owner_id, slug, title, description, visibility,
                 created_at, updated_at, is_trashed, trashed_at,
                 sync_status, synced_at, local_updated_at, server_updated_at
             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 'synced', ?11, ?12, ?13)
+            ON CONFLICT(id) DO UPDATE SET
+                owner_id = excluded.owner_id, slug = excluded.slug,
+                title = excluded.title, description = excluded.description,
+                visibility = excluded.visibility,
+                created_at = excluded.created_at, updated_at = excluded.updated_at,
+                is_trashed = excluded.is_trashed, trashed_at = excluded.trashed_at,
+                sync_status = excluded.sync_status, synced_at = excluded.synced_at,
+                local_updated_at = excluded.local_updated_at,
+                server_updated_at = excluded.server_updated_at
             "#,
             rusqlite::params![
                 note.id,
@@ -386,6 +651,7 @@ impl LocalDB {
                 note.updated_at,
             ],
         )?;
+        tx.commit()?;
 
         Ok(())
     }
@@ -395,6 +661,25 @@ impl LocalDB {
 // Decks CRUD
 // ============================================================================
 
+/// IDでデッキを取得（トランザクション内から使う）
+pub(crate) fn find_deck(conn: &Connection, id: &str) -> Result<Option<LocalDeck>, DbError> {
+    let deck = conn
+        .query_row(
+            r#"
+            SELECT id, user_id, title, description, is_public,
+                   created_at, updated_at,
+                   sync_status, synced_at, local_updated_at, server_updated_at,
+                   parent_deck_id, config_id
+            FROM decks
+            WHERE id = ?1
+            "#,
+            [id],
+            LocalDeck::from_row,
+        )
+        .optional()?;
+    Ok(deck)
+}
+
 impl LocalDB {
     /// 全デッキを取得
     pub fn get_decks_by_user(&self, user_id: &str) -> Result<Vec<LocalDeck>, DbError> {
@@ -403,7 +688,8 @@ impl LocalDB {
             r#"
             SELECT id, user_id, title, description, is_public,
                    created_at, updated_at,
-                   sync_status, synced_at, local_updated_at, server_updated_at
+                   sync_status, synced_at, local_updated_at, server_updated_at,
+                   parent_deck_id, config_id
             FROM decks
             WHERE user_id = ?1 AND sync_status != 'deleted'
             ORDER BY updated_at DESC
@@ -420,33 +706,26 @@ impl LocalDB {
     /// IDでデッキを取得
     pub fn get_deck_by_id(&self, id: &str) -> Result<Option<LocalDeck>, DbError> {
         let conn = self.connection()?;
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT id, user_id, title, description, is_public,
-                   created_at, updated_at,
-                   sync_status, synced_at, local_updated_at, server_updated_at
-            FROM decks
-            WHERE id = ?1
-            "#,
-        )?;
-
-        let deck = stmt
-            .query_row([id], |row| LocalDeck::from_row(row))
-            .ok();
-
-        Ok(deck)
+        find_deck(&conn, id)
     }
 
     /// デッキを作成
     pub fn insert_deck(&self, deck: &LocalDeck) -> Result<(), DbError> {
+        if deck.parent_deck_id.as_deref() == Some(deck.id.as_str()) {
+            return Err(DbError::ValidationError(format!(
+                "Deck {} cannot be its own parent",
+                deck.id
+            )));
+        }
         let conn = self.connection()?;
         conn.execute(
             r#"
             INSERT INTO decks (
                 id, user_id, title, description, is_public,
                 created_at, updated_at,
-                sync_status, synced_at, local_updated_at, server_updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                sync_status, synced_at, local_updated_at, server_updated_at,
+                parent_deck_id, config_id
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
             "#,
             rusqlite::params![
                 deck.id,
@@ -460,6 +739,8 @@ impl LocalDB {
                 deck.synced_at,
                 deck.local_updated_at,
                 deck.server_updated_at,
+                deck.parent_deck_id,
+                deck.config_id,
             ],
         )?;
         Ok(())
@@ -467,20 +748,21 @@ impl LocalDB {
 
     /// デッキを削除（論理削除）
     pub fn delete_deck(&self, id: &str) -> Result<bool, DbError> {
-        let conn = self.connection()?;
-        let now = chrono::Utc::now().to_rfc3339();
-
-        let rows_affected = conn.execute(
-            r#"
-            UPDATE decks SET
-                sync_status = 'deleted',
-                local_updated_at = ?1
-            WHERE id = ?2
-            "#,
-            rusqlite::params![now, id],
-        )?;
-
-        Ok(rows_affected > 0)
+        self.with_journal("delete_deck", &[("decks", id)], |conn| {
+            let now = chrono::Utc::now().to_rfc3339();
+
+            let rows_affected = conn.execute(
+                r#"
+                UPDATE decks SET
+                    sync_status = 'deleted',
+                    local_updated_at = ?1
+                WHERE id = ?2
+                "#,
+                rusqlite::params![now, id],
+            )?;
+
+            Ok(rows_affected > 0)
+        })
     }
 
     /// 同期待ちのデッキを取得
@@ -490,7 +772,8 @@ impl LocalDB {
             r#"
             SELECT id, user_id, title, description, is_public,
                    created_at, updated_at,
-                   sync_status, synced_at, local_updated_at, server_updated_at
+                   sync_status, synced_at, local_updated_at, server_updated_at,
+                   parent_deck_id, config_id
             FROM decks
             WHERE sync_status = 'pending'
             "#,
@@ -508,23 +791,39 @@ impl LocalDB {
 // Cards CRUD
 // ============================================================================
 
+/// カードのカラム（`LocalCard::from_row` の順）
+pub(crate) const CARD_COLUMNS: &str = "id, deck_id, user_id, front_content, back_content,
+    source_audio_url, source_ocr_image_url, created_at, updated_at,
+    ease_factor, repetition_count, review_interval, next_review_at,
+    stability, difficulty, last_reviewed_at,
+    sync_status, synced_at, local_updated_at, server_updated_at,
+    card_kind, card_note_id, ordinal, is_suspended, buried_until, flag";
+
+/// IDでカードを取得（トランザクション内から使う）
+pub(crate) fn find_card(conn: &Connection, id: &str) -> Result<Option<LocalCard>, DbError> {
+    let card = conn
+        .query_row(
+            &format!("SELECT {} FROM cards WHERE id = ?1", CARD_COLUMNS),
+            [id],
+            LocalCard::from_row,
+        )
+        .optional()?;
+    Ok(card)
+}
+
 impl LocalDB {
     /// デッキの全カードを取得
     pub fn get_cards_by_deck(&self, deck_id: &str) -> Result<Vec<LocalCard>, DbError> {
         let conn = self.connection()?;
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare(&format!(
             r#"
-            SELECT id, deck_id, user_id, front_content, back_content,
-                   source_audio_url, source_ocr_image_url,
-                   created_at, updated_at,
-                   ease_factor, repetition_count, review_interval,
-                   next_review_at, stability, difficulty, last_reviewed_at,
-                   sync_status, synced_at, local_updated_at, server_updated_at
+            SELECT {}
             FROM cards
             WHERE deck_id = ?1 AND sync_status != 'deleted'
             ORDER BY created_at DESC
             "#,
-        )?;
+            CARD_COLUMNS
+        ))?;
 
         let cards = stmt
             .query_map([deck_id], |row| LocalCard::from_row(row))?
@@ -536,40 +835,20 @@ impl LocalDB {
     /// IDでカードを取得
     pub fn get_card_by_id(&self, id: &str) -> Result<Option<LocalCard>, DbError> {
         let conn = self.connection()?;
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT id, deck_id, user_id, front_content, back_content,
-                   source_audio_url, source_ocr_image_url,
-                   created_at, updated_at,
-                   ease_factor, repetition_count, review_interval,
-                   next_review_at, stability, difficulty, last_reviewed_at,
-                   sync_status, synced_at, local_updated_at, server_updated_at
-            FROM cards
-            WHERE id = ?1
-            "#,
-        )?;
-
-        let card = stmt
-            .query_row([id], |row| LocalCard::from_row(row))
-            .ok();
-
-        Ok(card)
+        find_card(&conn, id)
     }
 
     /// カードを作成
     pub fn insert_card(&self, card: &LocalCard) -> Result<(), DbError> {
         let conn = self.connection()?;
         conn.execute(
-            r#"
-            INSERT INTO cards (
-                id, deck_id, user_id, front_content, back_content,
-                source_audio_url, source_ocr_image_url,
-                created_at, updated_at,
-                ease_factor, repetition_count, review_interval,
-                next_review_at, stability, difficulty, last_reviewed_at,
-                sync_status, synced_at, local_updated_at, server_updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20)
-            "#,
+            &format!(
+                r#"
+                INSERT INTO cards ({})
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26)
+                "#,
+                CARD_COLUMNS
+            ),
             rusqlite::params![
                 card.id,
                 card.deck_id,
@@ -591,72 +870,162 @@ impl LocalDB {
                 card.synced_at,
                 card.local_updated_at,
                 card.server_updated_at,
+                card.card_kind,
+                card.card_note_id,
+                card.ordinal,
+                card.is_suspended,
+                card.buried_until,
+                card.flag,
             ],
         )?;
         Ok(())
     }
 
-    /// カードを削除（論理削除）
-    pub fn delete_card(&self, id: &str) -> Result<bool, DbError> {
-        let conn = self.connection()?;
-        let now = chrono::Utc::now().to_rfc3339();
+    /// カードを更新
+    ///
+    /// 表裏の内容・素材URLが変わる場合のみ変更前の内容を履歴に残す
+    /// （復習のたびに更新されるスケジュール項目は履歴の対象外）。
+    pub fn update_card(&self, id: &str, updates: CardUpdate) -> Result<Option<LocalCard>, DbError> {
+        self.with_journal("update_card", &[("cards", id)], |tx| {
+            let now = chrono::Utc::now().to_rfc3339();
+
+            let current = find_card(tx, id)?;
+            if current.is_none() {
+                return Ok(None);
+            }
+            let current = current.unwrap();
+            let previous = current.clone();
+
+            let updated = LocalCard {
+                front_content: updates.front_content.unwrap_or(current.front_content),
+                back_content: updates.back_content.unwrap_or(current.back_content),
+                source_audio_url: updates.source_audio_url.unwrap_or(current.source_audio_url),
+                source_ocr_image_url: updates
+                    .source_ocr_image_url
+                    .unwrap_or(current.source_ocr_image_url),
+                ease_factor: updates.ease_factor.unwrap_or(current.ease_factor),
+                repetition_count: updates.repetition_count.unwrap_or(current.repetition_count),
+                review_interval: updates.review_interval.unwrap_or(current.review_interval),
+                next_review_at: updates.next_review_at.unwrap_or(current.next_review_at),
+                stability: updates.stability.unwrap_or(current.stability),
+                difficulty: updates.difficulty.unwrap_or(current.difficulty),
+                last_reviewed_at: updates.last_reviewed_at.unwrap_or(current.last_reviewed_at),
+                updated_at: now.clone(),
+                local_updated_at: now.clone(),
+                sync_status: "pending".to_string(),
+                ..current
+            };
+
+            let content_changed = updated.front_content != previous.front_content
+                || updated.back_content != previous.back_content
+                || updated.source_audio_url != previous.source_audio_url
+                || updated.source_ocr_image_url != previous.source_ocr_image_url;
+
+            if content_changed {
+                history::record_revision(
+                    tx,
+                    HistoryEntity::Card,
+                    id,
+                    &previous,
+                    RevisionSource::LocalEdit,
+                    Some(&previous.user_id),
+                )?;
+            }
 
-        let rows_affected = conn.execute(
-            r#"
-            UPDATE cards SET
-                sync_status = 'deleted',
-                local_updated_at = ?1
-            WHERE id = ?2
-            "#,
-            rusqlite::params![now, id],
-        )?;
+            tx.execute(
+                r#"
+                UPDATE cards SET
+                    front_content = ?1, back_content = ?2,
+                    source_audio_url = ?3, source_ocr_image_url = ?4,
+                    ease_factor = ?5, repetition_count = ?6, review_interval = ?7,
+                    next_review_at = ?8, stability = ?9, difficulty = ?10, last_reviewed_at = ?11,
+                    updated_at = ?12, local_updated_at = ?13, sync_status = ?14
+                WHERE id = ?15
+                "#,
+                rusqlite::params![
+                    updated.front_content,
+                    updated.back_content,
+                    updated.source_audio_url,
+                    updated.source_ocr_image_url,
+                    updated.ease_factor,
+                    updated.repetition_count,
+                    updated.review_interval,
+                    updated.next_review_at,
+                    updated.stability,
+                    updated.difficulty,
+                    updated.last_reviewed_at,
+                    updated.updated_at,
+                    updated.local_updated_at,
+                    updated.sync_status,
+                    id,
+                ],
+            )?;
+
+            Ok(Some(updated))
+        })
+    }
 
-        Ok(rows_affected > 0)
+    /// カードを削除（論理削除）
+    pub fn delete_card(&self, id: &str) -> Result<bool, DbError> {
+        self.with_journal("delete_card", &[("cards", id)], |conn| {
+            let now = chrono::Utc::now().to_rfc3339();
+
+            let rows_affected = conn.execute(
+                r#"
+                UPDATE cards SET
+                    sync_status = 'deleted',
+                    local_updated_at = ?1
+                WHERE id = ?2
+                "#,
+                rusqlite::params![now, id],
+            )?;
+
+            Ok(rows_affected > 0)
+        })
     }
 
     /// 期限切れカードを取得
+    ///
+    /// 一時停止中・延期中のカード、デッキの学習オプションの1日の最大復習数を超える分と、
+    /// 延期した兄弟カードは含めない。
     pub fn get_due_cards(&self, user_id: &str) -> Result<Vec<LocalCard>, DbError> {
         let conn = self.connection()?;
         let now = chrono::Utc::now().to_rfc3339();
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare(&format!(
             r#"
-            SELECT id, deck_id, user_id, front_content, back_content,
-                   source_audio_url, source_ocr_image_url,
-                   created_at, updated_at,
-                   ease_factor, repetition_count, review_interval,
-                   next_review_at, stability, difficulty, last_reviewed_at,
-                   sync_status, synced_at, local_updated_at, server_updated_at
+            SELECT {}
             FROM cards
             WHERE user_id = ?1 
               AND sync_status != 'deleted'
               AND next_review_at IS NOT NULL
               AND next_review_at <= ?2
+              AND is_suspended = 0
+              AND (buried_until IS NULL OR buried_until <= ?2)
             ORDER BY next_review_at ASC
             "#,
-        )?;
+            CARD_COLUMNS
+        ))?;
 
         let cards = stmt
             .query_map([user_id, &now], |row| LocalCard::from_row(row))?
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(cards)
+        // 兄弟カードを延期してから、デッキごとの1日の最大復習数を適用
+        let cards = review::bury_siblings(&conn, cards)?;
+        review::apply_daily_limits(&conn, cards, None)
     }
 
     /// 同期待ちのカードを取得
     pub fn get_pending_sync_cards(&self) -> Result<Vec<LocalCard>, DbError> {
         let conn = self.connection()?;
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare(&format!(
             r#"
-            SELECT id, deck_id, user_id, front_content, back_content,
-                   source_audio_url, source_ocr_image_url,
-                   created_at, updated_at,
-                   ease_factor, repetition_count, review_interval,
-                   next_review_at, stability, difficulty, last_reviewed_at,
-                   sync_status, synced_at, local_updated_at, server_updated_at
+            SELECT {}
             FROM cards
             WHERE sync_status = 'pending'
             "#,
-        )?;
+            CARD_COLUMNS
+        ))?;
 
         let cards = stmt
             .query_map([], |row| LocalCard::from_row(row))?
@@ -670,6 +1039,76 @@ impl LocalDB {
 // Pages CRUD
 // ============================================================================
 
+/// IDでページを取得（トランザクション内から使う）
+pub(crate) fn find_page(conn: &Connection, id: &str) -> Result<Option<LocalPage>, DbError> {
+    let page = conn
+        .query_row(
+            r#"
+            SELECT id, user_id, note_id, title, thumbnail_url, is_public,
+                   scrapbox_page_id, scrapbox_page_list_synced_at, scrapbox_page_content_synced_at,
+                   created_at, updated_at,
+                   sync_status, synced_at, local_updated_at, server_updated_at
+            FROM pages
+            WHERE id = ?1
+            "#,
+            [id],
+            LocalPage::from_row,
+        )
+        .optional()?;
+    Ok(page)
+}
+
+/// ページの更新を書き込む（トランザクション内から使う）
+///
+/// ホームのノート（`note_id`）が変わる場合は、元のノートから外して新しいノートの末尾に入れる。
+pub(crate) fn write_page_update(
+    tx: &JournalTx<'_>,
+    previous: &LocalPage,
+    updated: &LocalPage,
+) -> Result<(), DbError> {
+    tx.execute(
+        r#"
+        UPDATE pages SET
+            title = ?1, note_id = ?2, thumbnail_url = ?3, is_public = ?4,
+            updated_at = ?5, local_updated_at = ?6, sync_status = ?7
+        WHERE id = ?8
+        "#,
+        rusqlite::params![
+            updated.title,
+            updated.note_id,
+            updated.thumbnail_url,
+            updated.is_public,
+            updated.updated_at,
+            updated.local_updated_at,
+            updated.sync_status,
+            updated.id,
+        ],
+    )?;
+
+    // ホームのノートを変える場合は、影響を受ける対応もジャーナルの対象にする
+    if updated.note_id != previous.note_id {
+        if let Some(old_note_id) = &previous.note_id {
+            note_pages::track_note_pages(tx, old_note_id)?;
+            note_pages::remove_note_page(tx, old_note_id, &updated.id, &updated.updated_at)?;
+        }
+        if let Some(new_note_id) = &updated.note_id {
+            let new_note_page_id = uuid::Uuid::new_v4().to_string();
+            note_pages::track_note_pages(tx, new_note_id)?;
+            tx.track("note_pages", &new_note_page_id)?;
+            note_pages::insert_note_page(
+                tx,
+                &new_note_page_id,
+                new_note_id,
+                &updated.id,
+                None,
+                &updated.updated_at,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
 impl LocalDB {
     /// ユーザーの全ページを取得
     pub fn get_pages_by_user(&self, user_id: &str) -> Result<Vec<LocalPage>, DbError> {
@@ -693,18 +1132,19 @@ impl LocalDB {
         Ok(pages)
     }
 
-    /// ノートに紐づくページを取得
+    /// ノートに入っているページを並び順で取得
     pub fn get_pages_by_note(&self, note_id: &str) -> Result<Vec<LocalPage>, DbError> {
         let conn = self.connection()?;
         let mut stmt = conn.prepare(
             r#"
-            SELECT id, user_id, note_id, title, thumbnail_url, is_public,
-                   scrapbox_page_id, scrapbox_page_list_synced_at, scrapbox_page_content_synced_at,
-                   created_at, updated_at,
-                   sync_status, synced_at, local_updated_at, server_updated_at
-            FROM pages
-            WHERE note_id = ?1 AND sync_status != 'deleted'
-            ORDER BY updated_at DESC
+            SELECT p.id, p.user_id, p.note_id, p.title, p.thumbnail_url, p.is_public,
+                   p.scrapbox_page_id, p.scrapbox_page_list_synced_at, p.scrapbox_page_content_synced_at,
+                   p.created_at, p.updated_at,
+                   p.sync_status, p.synced_at, p.local_updated_at, p.server_updated_at
+            FROM note_pages np
+            JOIN pages p ON p.id = np.page_id
+            WHERE np.note_id = ?1 AND np.sync_status != 'deleted' AND p.sync_status != 'deleted'
+            ORDER BY np.position, np.created_at
             "#,
         )?;
 
@@ -718,26 +1158,18 @@ impl LocalDB {
     /// IDでページを取得
     pub fn get_page_by_id(&self, id: &str) -> Result<Option<LocalPage>, DbError> {
         let conn = self.connection()?;
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT id, user_id, note_id, title, thumbnail_url, is_public,
-                   scrapbox_page_id, scrapbox_page_list_synced_at, scrapbox_page_content_synced_at,
-                   created_at, updated_at,
-                   sync_status, synced_at, local_updated_at, server_updated_at
-            FROM pages
-            WHERE id = ?1
-            "#,
-        )?;
-
-        let page = stmt.query_row([id], |row| LocalPage::from_row(row)).ok();
-
-        Ok(page)
+        find_page(&conn, id)
     }
 
     /// ページを作成
+    ///
+    /// ローカルで作成したページ（`sync_status = 'pending'`）に `note_id` があれば、
+    /// そのノートの末尾にページを入れる。サーバーから取得したページの対応は
+    /// `note_pages` の同期で届くため、ここでは作らない。
     pub fn insert_page(&self, page: &LocalPage) -> Result<(), DbError> {
-        let conn = self.connection()?;
-        conn.execute(
+        let mut conn = self.connection()?;
+        let tx = conn.transaction()?;
+        tx.execute(
             r#"
             INSERT INTO pages (
                 id, user_id, note_id, title, thumbnail_url, is_public,
@@ -764,69 +1196,87 @@ impl LocalDB {
                 page.server_updated_at,
             ],
         )?;
+        if let (Some(note_id), "pending") = (&page.note_id, page.sync_status.as_str()) {
+            let id = uuid::Uuid::new_v4().to_string();
+            note_pages::insert_note_page(&tx, &id, note_id, &page.id, None, &page.created_at)?;
+        }
+        tx.commit()?;
         Ok(())
     }
 
     /// ページを更新
+    ///
+    /// ホームのノート（`note_id`）を変えた場合は、元のノートから外して新しいノートの末尾に入れる。
+    /// 現在のユーザーが閲覧者の場合や、移動先のノートを編集できない場合は
+    /// `DbError::PermissionDenied` を返す。
     pub fn update_page(&self, id: &str, updates: PageUpdate) -> Result<Option<LocalPage>, DbError> {
-        let conn = self.connection()?;
-        let now = chrono::Utc::now().to_rfc3339();
+        self.with_journal("update_page", &[("pages", id)], |tx| {
+            let now = chrono::Utc::now().to_rfc3339();
 
-        let current = self.get_page_by_id(id)?;
-        if current.is_none() {
-            return Ok(None);
-        }
-        let current = current.unwrap();
-
-        let updated = LocalPage {
-            title: updates.title.unwrap_or(current.title),
-            note_id: updates.note_id.unwrap_or(current.note_id),
-            thumbnail_url: updates.thumbnail_url.unwrap_or(current.thumbnail_url),
-            is_public: updates.is_public.unwrap_or(current.is_public),
-            updated_at: now.clone(),
-            local_updated_at: now.clone(),
-            sync_status: "pending".to_string(),
-            ..current
-        };
+            let current = find_page(tx, id)?;
+            if current.is_none() {
+                return Ok(None);
+            }
+            let current = current.unwrap();
+            self.ensure_can_edit_page(tx, &current)?;
+            let previous = current.clone();
+
+            let updated = LocalPage {
+                title: updates.title.unwrap_or(current.title),
+                note_id: updates.note_id.unwrap_or(current.note_id),
+                thumbnail_url: updates.thumbnail_url.unwrap_or(current.thumbnail_url),
+                is_public: updates.is_public.unwrap_or(current.is_public),
+                updated_at: now.clone(),
+                local_updated_at: now.clone(),
+                sync_status: "pending".to_string(),
+                ..current
+            };
+
+            if updated.note_id != previous.note_id {
+                if let Some(new_note_id) = &updated.note_id {
+                    self.ensure_can_edit_note(tx, new_note_id)?;
+                }
+            }
 
-        conn.execute(
-            r#"
-            UPDATE pages SET
-                title = ?1, note_id = ?2, thumbnail_url = ?3, is_public = ?4,
-                updated_at = ?5, local_updated_at = ?6, sync_status = ?7
-            WHERE id = ?8
-            "#,
-            rusqlite::params![
-                updated.title,
-                updated.note_id,
-                updated.thumbnail_url,
-                updated.is_public,
-                updated.updated_at,
-                updated.local_updated_at,
-                updated.sync_status,
+            // 変更前の内容を履歴に残す
+            history::record_revision(
+                tx,
+                HistoryEntity::Page,
                 id,
-            ],
-        )?;
+                &previous,
+                RevisionSource::LocalEdit,
+                Some(&previous.user_id),
+            )?;
 
-        Ok(Some(updated))
+            write_page_update(tx, &previous, &updated)?;
+
+            Ok(Some(updated))
+        })
     }
 
     /// ページを削除（論理削除）
+    ///
+    /// 現在のユーザーが閲覧者の場合は `DbError::PermissionDenied` を返す。
     pub fn delete_page(&self, id: &str) -> Result<bool, DbError> {
-        let conn = self.connection()?;
-        let now = chrono::Utc::now().to_rfc3339();
-
-        let rows_affected = conn.execute(
-            r#"
-            UPDATE pages SET
-                sync_status = 'deleted',
-                local_updated_at = ?1
-            WHERE id = ?2
-            "#,
-            rusqlite::params![now, id],
-        )?;
-
-        Ok(rows_affected > 0)
+        self.with_journal("delete_page", &[("pages", id)], |conn| {
+            let Some(page) = find_page(conn, id)? else {
+                return Ok(false);
+            };
+            self.ensure_can_edit_page(conn, &page)?;
+            let now = chrono::Utc::now().to_rfc3339();
+
+            let rows_affected = conn.execute(
+                r#"
+                UPDATE pages SET
+                    sync_status = 'deleted',
+                    local_updated_at = ?1
+                WHERE id = ?2
+                "#,
+                rusqlite::params![now, id],
+            )?;
+
+            Ok(rows_affected > 0)
+        })
     }
 
     /// 同期待ちのページを取得
@@ -949,20 +1399,21 @@ impl LocalDB {
 
     /// 学習目標を削除（論理削除）
     pub fn delete_study_goal(&self, id: &str) -> Result<bool, DbError> {
-        let conn = self.connection()?;
-        let now = chrono::Utc::now().to_rfc3339();
-
-        let rows_affected = conn.execute(
-            r#"
-            UPDATE study_goals SET
-                sync_status = 'deleted',
-                local_updated_at = ?1
-            WHERE id = ?2
-            "#,
-            rusqlite::params![now, id],
-        )?;
-
-        Ok(rows_affected > 0)
+        self.with_journal("delete_study_goal", &[("study_goals", id)], |conn| {
+            let now = chrono::Utc::now().to_rfc3339();
+
+            let rows_affected = conn.execute(
+                r#"
+                UPDATE study_goals SET
+                    sync_status = 'deleted',
+                    local_updated_at = ?1
+                WHERE id = ?2
+                "#,
+                rusqlite::params![now, id],
+            )?;
+
+            Ok(rows_affected > 0)
+        })
     }
 
     /// 同期待ちの学習目標を取得
@@ -996,8 +1447,8 @@ impl LocalDB {
         let conn = self.connection()?;
         let mut stmt = conn.prepare(
             r#"
-            SELECT id, user_id, card_id, question_id, answered_at, is_correct,
-                   user_answer, practice_mode, review_interval, next_review_at,
+            SELECT id, user_id, card_id, COALESCE(question_id, pending_question_id), answered_at,
+                   is_correct, user_answer, practice_mode, review_interval, next_review_at,
                    quality, response_time, effort_time, attempt_count,
                    sync_status, synced_at, local_updated_at, server_updated_at
             FROM learning_logs
@@ -1018,8 +1469,8 @@ impl LocalDB {
         let conn = self.connection()?;
         let mut stmt = conn.prepare(
             r#"
-            SELECT id, user_id, card_id, question_id, answered_at, is_correct,
-                   user_answer, practice_mode, review_interval, next_review_at,
+            SELECT id, user_id, card_id, COALESCE(question_id, pending_question_id), answered_at,
+                   is_correct, user_answer, practice_mode, review_interval, next_review_at,
                    quality, response_time, effort_time, attempt_count,
                    sync_status, synced_at, local_updated_at, server_updated_at
             FROM learning_logs
@@ -1036,16 +1487,24 @@ impl LocalDB {
     }
 
     /// 学習ログを作成
+    ///
+    /// 問題がまだ届いていない場合は、問題IDを `pending_question_id` に置いておき、
+    /// 問題を作成した時点で `question_id` に移す。
     pub fn insert_learning_log(&self, log: &LocalLearningLog) -> Result<(), DbError> {
         let conn = self.connection()?;
         conn.execute(
             r#"
             INSERT INTO learning_logs (
-                id, user_id, card_id, question_id, answered_at, is_correct,
+                id, user_id, card_id, question_id, pending_question_id, answered_at, is_correct,
                 user_answer, practice_mode, review_interval, next_review_at,
                 quality, response_time, effort_time, attempt_count,
                 sync_status, synced_at, local_updated_at, server_updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)
+            ) VALUES (
+                ?1, ?2, ?3,
+                (SELECT id FROM questions WHERE id = ?4),
+                CASE WHEN EXISTS (SELECT 1 FROM questions WHERE id = ?4) THEN NULL ELSE ?4 END,
+                ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18
+            )
             "#,
             rusqlite::params![
                 log.id,
@@ -1076,8 +1535,8 @@ impl LocalDB {
         let conn = self.connection()?;
         let mut stmt = conn.prepare(
             r#"
-            SELECT id, user_id, card_id, question_id, answered_at, is_correct,
-                   user_answer, practice_mode, review_interval, next_review_at,
+            SELECT id, user_id, card_id, COALESCE(question_id, pending_question_id), answered_at,
+                   is_correct, user_answer, practice_mode, review_interval, next_review_at,
                    quality, response_time, effort_time, attempt_count,
                    sync_status, synced_at, local_updated_at, server_updated_at
             FROM learning_logs
@@ -1170,20 +1629,21 @@ impl LocalDB {
 
     /// マイルストーンを削除（論理削除）
     pub fn delete_milestone(&self, id: &str) -> Result<bool, DbError> {
-        let conn = self.connection()?;
-        let now = chrono::Utc::now().to_rfc3339();
-
-        let rows_affected = conn.execute(
-            r#"
-            UPDATE milestones SET
-                sync_status = 'deleted',
-                local_updated_at = ?1
-            WHERE id = ?2
-            "#,
-            rusqlite::params![now, id],
-        )?;
-
-        Ok(rows_affected > 0)
+        self.with_journal("delete_milestone", &[("milestones", id)], |conn| {
+            let now = chrono::Utc::now().to_rfc3339();
+
+            let rows_affected = conn.execute(
+                r#"
+                UPDATE milestones SET
+                    sync_status = 'deleted',
+                    local_updated_at = ?1
+                WHERE id = ?2
+                "#,
+                rusqlite::params![now, id],
+            )?;
+
+            Ok(rows_affected > 0)
+        })
     }
 
     /// 同期待ちのマイルストーンを取得
@@ -1302,20 +1762,7 @@ mod tests {
 
     fn setup_test_db() -> (LocalDB, TempDir) {
         let temp_dir = TempDir::new().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-
-        let conn = Connection::open(&db_path).unwrap();
-        conn.execute_batch("PRAGMA journal_mode=WAL;").unwrap();
-        conn.execute_batch("PRAGMA foreign_keys=ON;").unwrap();
-
-        // スキーマを適用
-        for schema_sql in schema::get_all_schemas() {
-            conn.execute_batch(schema_sql).unwrap();
-        }
-
-        let db = LocalDB {
-            conn: Arc::new(Mutex::new(conn)),
-        };
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
 
         (db, temp_dir)
     }
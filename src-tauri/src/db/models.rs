@@ -130,6 +130,12 @@ pub struct PageUpdate {
 pub struct LocalDeck {
     pub id: String,
     pub user_id: String,
+    /// 親デッキ（ルートのデッキは None）
+    #[serde(default)]
+    pub parent_deck_id: Option<String>,
+    /// 学習オプションのプリセット（None は既定値）
+    #[serde(default)]
+    pub config_id: Option<String>,
     pub title: String,
     pub description: Option<String>,
     pub is_public: bool,
@@ -156,6 +162,8 @@ impl LocalDeck {
             synced_at: row.get(8)?,
             local_updated_at: row.get(9)?,
             server_updated_at: row.get(10)?,
+            parent_deck_id: row.get(11)?,
+            config_id: row.get(12)?,
         })
     }
 }
@@ -168,6 +176,67 @@ pub struct DeckUpdate {
     pub is_public: Option<bool>,
 }
 
+// ============================================================================
+// Deck Configs
+// ============================================================================
+
+/// デッキの学習オプションのプリセット
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalDeckConfig {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub new_cards_per_day: i32,
+    pub max_reviews_per_day: i32,
+    /// 学習ステップ（分）のJSON配列
+    pub learning_steps: String,
+    pub desired_retention: f64,
+    /// 最大間隔（日）
+    pub maximum_interval: i32,
+    pub bury_siblings: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    pub sync_status: String,
+    pub synced_at: Option<String>,
+    pub local_updated_at: String,
+    pub server_updated_at: Option<String>,
+}
+
+impl LocalDeckConfig {
+    /// SQLite行からLocalDeckConfigを生成
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            user_id: row.get(1)?,
+            name: row.get(2)?,
+            new_cards_per_day: row.get(3)?,
+            max_reviews_per_day: row.get(4)?,
+            learning_steps: row.get(5)?,
+            desired_retention: row.get(6)?,
+            maximum_interval: row.get(7)?,
+            bury_siblings: row.get::<_, i32>(8)? != 0,
+            created_at: row.get(9)?,
+            updated_at: row.get(10)?,
+            sync_status: row.get(11)?,
+            synced_at: row.get(12)?,
+            local_updated_at: row.get(13)?,
+            server_updated_at: row.get(14)?,
+        })
+    }
+}
+
+/// プリセット更新用構造体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeckConfigUpdate {
+    pub name: Option<String>,
+    pub new_cards_per_day: Option<i32>,
+    pub max_reviews_per_day: Option<i32>,
+    pub learning_steps: Option<String>,
+    pub desired_retention: Option<f64>,
+    pub maximum_interval: Option<i32>,
+    pub bury_siblings: Option<bool>,
+}
+
 // ============================================================================
 // Cards
 // ============================================================================
@@ -195,6 +264,28 @@ pub struct LocalCard {
     pub synced_at: Option<String>,
     pub local_updated_at: String,
     pub server_updated_at: Option<String>,
+    /// カードの種類（`basic` / `cloze` / `forward` / `reverse`）
+    #[serde(default = "default_card_kind")]
+    pub card_kind: String,
+    /// 生成元のカードノート（手動で作成したカードは NULL）
+    #[serde(default)]
+    pub card_note_id: Option<String>,
+    /// カードノート内の番号（穴埋めの `c1` なら 1）
+    #[serde(default)]
+    pub ordinal: Option<i32>,
+    /// 一時停止中（復習・学習に出題しない）
+    #[serde(default)]
+    pub is_suspended: bool,
+    /// この日時まで出題しない（延期。次の学習日の始まり）
+    #[serde(default)]
+    pub buried_until: Option<String>,
+    /// 色のフラグ（`red` / `orange` / `green` / `blue` / `pink` / `turquoise` / `purple`）
+    #[serde(default)]
+    pub flag: Option<String>,
+}
+
+fn default_card_kind() -> String {
+    "basic".to_string()
 }
 
 impl LocalCard {
@@ -221,6 +312,12 @@ impl LocalCard {
             synced_at: row.get(17)?,
             local_updated_at: row.get(18)?,
             server_updated_at: row.get(19)?,
+            card_kind: row.get(20)?,
+            card_note_id: row.get(21)?,
+            ordinal: row.get(22)?,
+            is_suspended: row.get(23)?,
+            buried_until: row.get(24)?,
+            flag: row.get(25)?,
         })
     }
 }
@@ -241,6 +338,123 @@ pub struct CardUpdate {
     pub last_reviewed_at: Option<Option<String>>,
 }
 
+// ============================================================================
+// Card Notes
+// ============================================================================
+
+/// カードノート（兄弟カードを生成する元のテキスト）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalCardNote {
+    pub id: String,
+    pub deck_id: String,
+    pub user_id: String,
+    /// 生成するカードの種類（`cloze` / `forward` / `reverse` / `bidirectional`）
+    pub kind: String,
+    /// 穴埋めのテキスト、または表裏のカードの表の入力欄
+    pub source_text: String,
+    /// 表裏のカードの裏の入力欄
+    #[serde(default)]
+    pub back_text: Option<String>,
+    /// 裏面に追加する補足
+    pub extra: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub sync_status: String,
+    pub synced_at: Option<String>,
+    pub local_updated_at: String,
+    pub server_updated_at: Option<String>,
+}
+
+impl LocalCardNote {
+    /// SQLite行からLocalCardNoteを生成
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            deck_id: row.get(1)?,
+            user_id: row.get(2)?,
+            kind: row.get(3)?,
+            source_text: row.get(4)?,
+            extra: row.get(5)?,
+            created_at: row.get(6)?,
+            updated_at: row.get(7)?,
+            sync_status: row.get(8)?,
+            synced_at: row.get(9)?,
+            local_updated_at: row.get(10)?,
+            server_updated_at: row.get(11)?,
+            back_text: row.get(12)?,
+        })
+    }
+}
+
+/// カードノート更新用構造体
+///
+/// `kind` は表裏のカードの向き（順方向・逆方向・双方向）の間でのみ変更できる。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CardNoteUpdate {
+    pub kind: Option<String>,
+    pub source_text: Option<String>,
+    pub back_text: Option<Option<String>>,
+    pub extra: Option<Option<String>>,
+}
+
+// ============================================================================
+// Questions
+// ============================================================================
+
+/// ローカルの問題
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalQuestion {
+    pub id: String,
+    pub card_id: String,
+    pub user_id: String,
+    /// `multiple_choice` / `true_false` / `fill_in`
+    pub question_type: String,
+    pub prompt: String,
+    /// 選択肢のJSON配列
+    pub options: String,
+    /// 正解のJSON配列
+    pub correct_answers: String,
+    pub explanation: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub sync_status: String,
+    pub synced_at: Option<String>,
+    pub local_updated_at: String,
+    pub server_updated_at: Option<String>,
+}
+
+impl LocalQuestion {
+    /// SQLite行からLocalQuestionを生成
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            card_id: row.get(1)?,
+            user_id: row.get(2)?,
+            question_type: row.get(3)?,
+            prompt: row.get(4)?,
+            options: row.get(5)?,
+            correct_answers: row.get(6)?,
+            explanation: row.get(7)?,
+            created_at: row.get(8)?,
+            updated_at: row.get(9)?,
+            sync_status: row.get(10)?,
+            synced_at: row.get(11)?,
+            local_updated_at: row.get(12)?,
+            server_updated_at: row.get(13)?,
+        })
+    }
+}
+
+/// 問題更新用構造体
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuestionUpdate {
+    pub question_type: Option<String>,
+    pub prompt: Option<String>,
+    pub options: Option<String>,
+    pub correct_answers: Option<String>,
+    pub explanation: Option<Option<String>>,
+}
+
 // ============================================================================
 // Study Goals
 // ============================================================================
@@ -435,3 +649,224 @@ impl LocalUserSettings {
     }
 }
 
+
+// ============================================================================
+// Revisions
+// ============================================================================
+
+/// ノート・ページ・カードの変更履歴
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalRevision {
+    pub id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    /// 変更前の行（JSON）
+    pub snapshot: String,
+    pub source: String,
+    pub changed_by: Option<String>,
+    pub created_at: String,
+}
+
+impl LocalRevision {
+    /// SQLite行からLocalRevisionを生成
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            entity_type: row.get(1)?,
+            entity_id: row.get(2)?,
+            snapshot: row.get(3)?,
+            source: row.get(4)?,
+            changed_by: row.get(5)?,
+            created_at: row.get(6)?,
+        })
+    }
+}
+
+// ============================================================================
+// Note Pages
+// ============================================================================
+
+/// ノートとページの対応
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalNotePage {
+    pub id: String,
+    pub note_id: String,
+    pub page_id: String,
+    /// ノート内の並び順（0始まり）
+    pub position: i64,
+    pub created_at: String,
+    pub updated_at: String,
+    pub sync_status: String,
+    pub synced_at: Option<String>,
+    pub local_updated_at: String,
+    pub server_updated_at: Option<String>,
+}
+
+impl LocalNotePage {
+    /// SQLite行からLocalNotePageを生成
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            note_id: row.get(1)?,
+            page_id: row.get(2)?,
+            position: row.get(3)?,
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+            sync_status: row.get(6)?,
+            synced_at: row.get(7)?,
+            local_updated_at: row.get(8)?,
+            server_updated_at: row.get(9)?,
+        })
+    }
+}
+
+// ============================================================================
+// Note Members
+// ============================================================================
+
+/// ノートのメンバー
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalNoteMember {
+    pub id: String,
+    pub note_id: String,
+    pub user_id: String,
+    /// 権限（`owner`・`editor`・`viewer`）
+    pub role: String,
+    pub invited_by: Option<String>,
+    pub invited_at: String,
+    /// 招待を受けた日時（招待中は None）
+    pub accepted_at: Option<String>,
+    pub updated_at: String,
+    pub sync_status: String,
+    pub synced_at: Option<String>,
+    pub local_updated_at: String,
+    pub server_updated_at: Option<String>,
+}
+
+impl LocalNoteMember {
+    /// SQLite行からLocalNoteMemberを生成
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            note_id: row.get(1)?,
+            user_id: row.get(2)?,
+            role: row.get(3)?,
+            invited_by: row.get(4)?,
+            invited_at: row.get(5)?,
+            accepted_at: row.get(6)?,
+            updated_at: row.get(7)?,
+            sync_status: row.get(8)?,
+            synced_at: row.get(9)?,
+            local_updated_at: row.get(10)?,
+            server_updated_at: row.get(11)?,
+        })
+    }
+}
+
+/// ノートの共有リンク
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalNoteShareLink {
+    pub id: String,
+    pub note_id: String,
+    pub token: String,
+    /// 参加したユーザーに与える権限（`editor`・`viewer`）
+    pub role: String,
+    pub created_by: String,
+    pub expires_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub sync_status: String,
+    pub synced_at: Option<String>,
+    pub local_updated_at: String,
+    pub server_updated_at: Option<String>,
+}
+
+impl LocalNoteShareLink {
+    /// SQLite行からLocalNoteShareLinkを生成
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            note_id: row.get(1)?,
+            token: row.get(2)?,
+            role: row.get(3)?,
+            created_by: row.get(4)?,
+            expires_at: row.get(5)?,
+            created_at: row.get(6)?,
+            updated_at: row.get(7)?,
+            sync_status: row.get(8)?,
+            synced_at: row.get(9)?,
+            local_updated_at: row.get(10)?,
+            server_updated_at: row.get(11)?,
+        })
+    }
+}
+
+// ============================================================================
+// Tags
+// ============================================================================
+
+/// ローカルタグ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalTag {
+    pub id: String,
+    pub user_id: String,
+    /// タグ名（`親::子` の形で階層を表す）
+    pub name: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub sync_status: String,
+    pub synced_at: Option<String>,
+    pub local_updated_at: String,
+    pub server_updated_at: Option<String>,
+}
+
+impl LocalTag {
+    /// SQLite行からLocalTagを生成
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            user_id: row.get(1)?,
+            name: row.get(2)?,
+            created_at: row.get(3)?,
+            updated_at: row.get(4)?,
+            sync_status: row.get(5)?,
+            synced_at: row.get(6)?,
+            local_updated_at: row.get(7)?,
+            server_updated_at: row.get(8)?,
+        })
+    }
+}
+
+/// ノート・ページ・カードへのタグ付け
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalEntityTag {
+    pub id: String,
+    pub tag_id: String,
+    /// `note` / `page` / `card`
+    pub entity_type: String,
+    pub entity_id: String,
+    pub user_id: String,
+    pub created_at: String,
+    pub sync_status: String,
+    pub synced_at: Option<String>,
+    pub local_updated_at: String,
+    pub server_updated_at: Option<String>,
+}
+
+impl LocalEntityTag {
+    /// SQLite行からLocalEntityTagを生成
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            tag_id: row.get(1)?,
+            entity_type: row.get(2)?,
+            entity_id: row.get(3)?,
+            user_id: row.get(4)?,
+            created_at: row.get(5)?,
+            sync_status: row.get(6)?,
+            synced_at: row.get(7)?,
+            local_updated_at: row.get(8)?,
+            server_updated_at: row.get(9)?,
+        })
+    }
+}
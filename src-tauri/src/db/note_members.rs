@@ -0,0 +1,947 @@
+//! ノートの共有とメンバー
+//!
+//! `visibility = 'invite'` のノートに招待したユーザーと権限、共有リンクを保存し、
+//! オフラインでも「共有されたノート」の表示と編集権限の確認ができるようにする。
+//! `note_members`・`note_share_links` は他のテーブルと同じ同期メタデータを持ち、
+//! 削除は論理削除（`sync_status = 'deleted'`）としてサーバーに伝える。
+//!
+//! 権限の確認は現在のプロファイルのユーザーに対して行う。
+//! プロファイルを使っていない場合（従来の単一DB）は確認しない。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   ├─ src-tauri/src/db/page_rename.rs
+//!   ├─ src-tauri/src/db/page_documents.rs
+//!   ├─ src-tauri/src/db/note_pages.rs
+//!   ├─ src-tauri/src/db/note_slugs.rs
+//!   └─ src-tauri/src/commands/note_members_commands.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{
+    find_note, DbError, LocalDB, LocalNote, LocalNoteMember, LocalNoteShareLink, LocalPage,
+};
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+const NOTE_MEMBER_COLUMNS: &str = "id, note_id, user_id, role, invited_by, invited_at,
+    accepted_at, updated_at, sync_status, synced_at, local_updated_at, server_updated_at";
+
+const SHARE_LINK_COLUMNS: &str = "id, note_id, token, role, created_by, expires_at,
+    created_at, updated_at, sync_status, synced_at, local_updated_at, server_updated_at";
+
+/// ノートに対する権限
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteRole {
+    Viewer,
+    Editor,
+    Owner,
+}
+
+impl NoteRole {
+    /// `note_members.role` に保存する値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NoteRole::Owner => "owner",
+            NoteRole::Editor => "editor",
+            NoteRole::Viewer => "viewer",
+        }
+    }
+
+    /// 保存された値から復元
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "owner" => Some(NoteRole::Owner),
+            "editor" => Some(NoteRole::Editor),
+            "viewer" => Some(NoteRole::Viewer),
+            _ => None,
+        }
+    }
+
+    /// ノートとページを編集できるか
+    pub fn can_edit(&self) -> bool {
+        *self >= NoteRole::Editor
+    }
+}
+
+/// 共有されたノートと自分のメンバー情報
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedNote {
+    pub note: LocalNote,
+    pub membership: LocalNoteMember,
+}
+
+/// ノートのメンバー（削除済みを除く）を取得
+fn find_member(
+    conn: &Connection,
+    note_id: &str,
+    user_id: &str,
+) -> Result<Option<LocalNoteMember>, DbError> {
+    let member = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM note_members
+                 WHERE note_id = ?1 AND user_id = ?2 AND sync_status != 'deleted'",
+                NOTE_MEMBER_COLUMNS
+            ),
+            [note_id, user_id],
+            LocalNoteMember::from_row,
+        )
+        .optional()?;
+    Ok(member)
+}
+
+/// ユーザーのノートに対する権限
+///
+/// 所有者は `Owner`。それ以外は招待を受けたメンバーの権限（招待中は権限なし）。
+pub(crate) fn note_role(
+    conn: &Connection,
+    note_id: &str,
+    user_id: &str,
+) -> Result<Option<NoteRole>, DbError> {
+    let owner_id: Option<String> = conn
+        .query_row(
+            "SELECT owner_id FROM notes WHERE id = ?1",
+            [note_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if owner_id.as_deref() == Some(user_id) {
+        return Ok(Some(NoteRole::Owner));
+    }
+
+    Ok(find_member(conn, note_id, user_id)?
+        .filter(|member| member.accepted_at.is_some())
+        .and_then(|member| NoteRole::parse(&member.role)))
+}
+
+/// ページを編集できるか
+///
+/// 自分のページか、ページが入っているノートのいずれかで編集者以上であれば編集できる。
+pub(crate) fn can_edit_page(
+    conn: &Connection,
+    page: &LocalPage,
+    user_id: &str,
+) -> Result<bool, DbError> {
+    if page.user_id == user_id {
+        return Ok(true);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT note_id FROM note_pages WHERE page_id = ?1 AND sync_status != 'deleted'",
+    )?;
+    let mut note_ids = stmt
+        .query_map([page.id.as_str()], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    note_ids.extend(page.note_id.clone());
+
+    for note_id in note_ids {
+        if note_role(conn, &note_id, user_id)?.is_some_and(|role| role.can_edit()) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// 共有リンクの有効期限をUTCのRFC3339に揃える
+fn normalize_expires_at(expires_at: &str) -> Result<String, DbError> {
+    DateTime::parse_from_rfc3339(expires_at)
+        .map(|expires_at| expires_at.with_timezone(&Utc).to_rfc3339())
+        .map_err(|e| {
+            DbError::ValidationError(format!("Invalid expires_at \"{}\": {}", expires_at, e))
+        })
+}
+
+/// 共有リンクが有効か（取り消し・期限切れでない）
+///
+/// 期限は時刻として比較する（読めない期限は切れたものとして扱う）。
+fn is_link_active(link: &LocalNoteShareLink, now: DateTime<Utc>) -> bool {
+    link.sync_status != "deleted"
+        && link.expires_at.as_deref().map_or(true, |expires_at| {
+            DateTime::parse_from_rfc3339(expires_at).is_ok_and(|expires_at| expires_at > now)
+        })
+}
+
+impl LocalDB {
+    /// 現在のユーザーがノートを編集できることを確かめる
+    pub(crate) fn ensure_can_edit_note(
+        &self,
+        conn: &Connection,
+        note_id: &str,
+    ) -> Result<(), DbError> {
+        let Some(user_id) = self.active_profile()? else {
+            return Ok(());
+        };
+        if note_role(conn, note_id, &user_id)?.is_some_and(|role| role.can_edit()) {
+            return Ok(());
+        }
+        Err(DbError::PermissionDenied(format!(
+            "User {} cannot edit note {}",
+            user_id, note_id
+        )))
+    }
+
+    /// 現在のユーザーがページを編集できることを確かめる
+    pub(crate) fn ensure_can_edit_page(
+        &self,
+        conn: &Connection,
+        page: &LocalPage,
+    ) -> Result<(), DbError> {
+        let Some(user_id) = self.active_profile()? else {
+            return Ok(());
+        };
+        if can_edit_page(conn, page, &user_id)? {
+            return Ok(());
+        }
+        Err(DbError::PermissionDenied(format!(
+            "User {} cannot edit page {}",
+            user_id, page.id
+        )))
+    }
+
+    /// 現在のユーザーがノートのメンバーを管理できる（所有者である）ことを確かめる
+    pub(crate) fn ensure_can_manage_note(
+        &self,
+        conn: &Connection,
+        note_id: &str,
+    ) -> Result<Option<String>, DbError> {
+        let Some(user_id) = self.active_profile()? else {
+            return Ok(None);
+        };
+        if note_role(conn, note_id, &user_id)? == Some(NoteRole::Owner) {
+            return Ok(Some(user_id));
+        }
+        Err(DbError::PermissionDenied(format!(
+            "User {} cannot manage members of note {}",
+            user_id, note_id
+        )))
+    }
+
+    /// ノートのメンバーを取得（招待中を含む）
+    pub fn get_note_members(&self, note_id: &str) -> Result<Vec<LocalNoteMember>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM note_members
+             WHERE note_id = ?1 AND sync_status != 'deleted'
+             ORDER BY invited_at",
+            NOTE_MEMBER_COLUMNS
+        ))?;
+        let members = stmt
+            .query_map([note_id], LocalNoteMember::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(members)
+    }
+
+    /// 自分に共有されたノートを取得（招待中を含む、ゴミ箱のノートを除く）
+    pub fn get_shared_notes(&self, user_id: &str) -> Result<Vec<SharedNote>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT n.id, n.owner_id, n.slug, n.title, n.description, n.visibility,
+                   n.created_at, n.updated_at, n.is_trashed, n.trashed_at,
+                   n.sync_status, n.synced_at, n.local_updated_at, n.server_updated_at,
+                   m.id, m.note_id, m.user_id, m.role, m.invited_by, m.invited_at,
+                   m.accepted_at, m.updated_at, m.sync_status, m.synced_at,
+                   m.local_updated_at, m.server_updated_at
+            FROM note_members m
+            JOIN notes n ON n.id = m.note_id
+            WHERE m.user_id = ?1 AND m.sync_status != 'deleted'
+              AND n.owner_id != ?1 AND n.sync_status != 'deleted' AND n.is_trashed = 0
+            ORDER BY COALESCE(m.accepted_at, m.invited_at) DESC
+            "#,
+        )?;
+        let notes = stmt
+            .query_map([user_id], |row| {
+                Ok(SharedNote {
+                    note: LocalNote::from_row(row)?,
+                    membership: LocalNoteMember {
+                        id: row.get(14)?,
+                        note_id: row.get(15)?,
+                        user_id: row.get(16)?,
+                        role: row.get(17)?,
+                        invited_by: row.get(18)?,
+                        invited_at: row.get(19)?,
+                        accepted_at: row.get(20)?,
+                        updated_at: row.get(21)?,
+                        sync_status: row.get(22)?,
+                        synced_at: row.get(23)?,
+                        local_updated_at: row.get(24)?,
+                        server_updated_at: row.get(25)?,
+                    },
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(notes)
+    }
+
+    /// ユーザーのノートに対する権限を取得
+    pub fn get_note_role(&self, note_id: &str, user_id: &str) -> Result<Option<NoteRole>, DbError> {
+        let conn = self.connection()?;
+        note_role(&conn, note_id, user_id)
+    }
+
+    /// ユーザーをノートに招待する（既にメンバーの場合は権限を変更する）
+    pub fn invite_note_member(
+        &self,
+        note_id: &str,
+        user_id: &str,
+        role: NoteRole,
+    ) -> Result<LocalNoteMember, DbError> {
+        self.with_journal("invite_note_member", &[], |tx| {
+            let invited_by = self.ensure_can_manage_note(tx, note_id)?;
+            let owner_id: Option<String> = tx
+                .query_row(
+                    "SELECT owner_id FROM notes WHERE id = ?1 AND sync_status != 'deleted'",
+                    [note_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            match owner_id {
+                None => return Err(DbError::NotFound(format!("Note {}", note_id))),
+                Some(owner_id) if owner_id == user_id => {
+                    return Err(DbError::ValidationError(format!(
+                        "User {} already owns note {}",
+                        user_id, note_id
+                    )))
+                }
+                Some(_) => {}
+            }
+
+            let now = chrono::Utc::now().to_rfc3339();
+            let member = match find_member(tx, note_id, user_id)? {
+                Some(member) if member.role == role.as_str() => return Ok(member),
+                Some(member) => LocalNoteMember {
+                    role: role.as_str().to_string(),
+                    updated_at: now.clone(),
+                    sync_status: "pending".to_string(),
+                    local_updated_at: now,
+                    ..member
+                },
+                None => LocalNoteMember {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    note_id: note_id.to_string(),
+                    user_id: user_id.to_string(),
+                    role: role.as_str().to_string(),
+                    invited_by,
+                    invited_at: now.clone(),
+                    accepted_at: None,
+                    updated_at: now.clone(),
+                    sync_status: "pending".to_string(),
+                    synced_at: None,
+                    local_updated_at: now,
+                    server_updated_at: None,
+                },
+            };
+            tx.track("note_members", &member.id)?;
+            upsert_member(tx, &member)?;
+            Ok(member)
+        })
+    }
+
+    /// 招待を受ける
+    pub fn accept_note_invitation(
+        &self,
+        note_id: &str,
+        user_id: &str,
+    ) -> Result<LocalNoteMember, DbError> {
+        if let Some(active) = self.active_profile()? {
+            if active != user_id {
+                return Err(DbError::PermissionDenied(format!(
+                    "User {} cannot accept an invitation for {}",
+                    active, user_id
+                )));
+            }
+        }
+        self.with_journal("accept_note_invitation", &[], |tx| {
+            let member = find_member(tx, note_id, user_id)?
+                .ok_or_else(|| DbError::NotFound(format!("Invitation to note {}", note_id)))?;
+            if member.accepted_at.is_some() {
+                return Ok(member);
+            }
+
+            let now = chrono::Utc::now().to_rfc3339();
+            let member = LocalNoteMember {
+                accepted_at: Some(now.clone()),
+                updated_at: now.clone(),
+                sync_status: "pending".to_string(),
+                local_updated_at: now,
+                ..member
+            };
+            tx.track("note_members", &member.id)?;
+            upsert_member(tx, &member)?;
+            Ok(member)
+        })
+    }
+
+    /// メンバーを外す（所有者による削除、または自分での退出）
+    pub fn remove_note_member(&self, note_id: &str, user_id: &str) -> Result<bool, DbError> {
+        self.with_journal("remove_note_member", &[], |tx| {
+            if self.active_profile()?.as_deref() != Some(user_id) {
+                self.ensure_can_manage_note(tx, note_id)?;
+            }
+            let Some(member) = find_member(tx, note_id, user_id)? else {
+                return Ok(false);
+            };
+
+            tx.track("note_members", &member.id)?;
+            let now = chrono::Utc::now().to_rfc3339();
+            tx.execute(
+                "UPDATE note_members SET sync_status = 'deleted', local_updated_at = ?1
+                 WHERE id = ?2",
+                rusqlite::params![now, member.id],
+            )?;
+            Ok(true)
+        })
+    }
+
+    /// 共有リンクを作成する
+    pub fn create_note_share_link(
+        &self,
+        note_id: &str,
+        role: NoteRole,
+        expires_at: Option<&str>,
+    ) -> Result<LocalNoteShareLink, DbError> {
+        if role == NoteRole::Owner {
+            return Err(DbError::ValidationError(
+                "Share links cannot grant the owner role".to_string(),
+            ));
+        }
+        let expires_at = expires_at.map(normalize_expires_at).transpose()?;
+        self.with_journal("create_note_share_link", &[], |tx| {
+            let active = self.ensure_can_manage_note(tx, note_id)?;
+            let note = find_note(tx, note_id)?
+                .filter(|note| note.sync_status != "deleted")
+                .ok_or_else(|| DbError::NotFound(format!("Note {}", note_id)))?;
+
+            let now = chrono::Utc::now().to_rfc3339();
+            let link = LocalNoteShareLink {
+                id: uuid::Uuid::new_v4().to_string(),
+                note_id: note_id.to_string(),
+                token: uuid::Uuid::new_v4().simple().to_string(),
+                role: role.as_str().to_string(),
+                created_by: active.unwrap_or(note.owner_id),
+                expires_at,
+                created_at: now.clone(),
+                updated_at: now.clone(),
+                sync_status: "pending".to_string(),
+                synced_at: None,
+                local_updated_at: now,
+                server_updated_at: None,
+            };
+
+            tx.track("note_share_links", &link.id)?;
+            tx.execute(
+                r#"
+                INSERT INTO note_share_links (
+                    id, note_id, token, role, created_by, expires_at,
+                    created_at, updated_at, sync_status, local_updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7, 'pending', ?7)
+                "#,
+                rusqlite::params![
+                    link.id,
+                    link.note_id,
+                    link.token,
+                    link.role,
+                    link.created_by,
+                    link.expires_at,
+                    link.created_at,
+                ],
+            )?;
+            Ok(link)
+        })
+    }
+
+    /// ノートの共有リンクを取得（取り消したリンクを除く）
+    pub fn get_note_share_links(&self, note_id: &str) -> Result<Vec<LocalNoteShareLink>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM note_share_links
+             WHERE note_id = ?1 AND sync_status != 'deleted'
+             ORDER BY created_at",
+            SHARE_LINK_COLUMNS
+        ))?;
+        let links = stmt
+            .query_map([note_id], LocalNoteShareLink::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(links)
+    }
+
+    /// 共有リンクを取り消す
+    pub fn revoke_note_share_link(&self, id: &str) -> Result<bool, DbError> {
+        self.with_journal("revoke_note_share_link", &[("note_share_links", id)], |tx| {
+            let note_id: Option<String> = tx
+                .query_row(
+                    "SELECT note_id FROM note_share_links WHERE id = ?1 AND sync_status != 'deleted'",
+                    [id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let Some(note_id) = note_id else {
+                return Ok(false);
+            };
+            self.ensure_can_manage_note(tx, &note_id)?;
+
+            let now = chrono::Utc::now().to_rfc3339();
+            tx.execute(
+                "UPDATE note_share_links SET sync_status = 'deleted', local_updated_at = ?1
+                 WHERE id = ?2",
+                rusqlite::params![now, id],
+            )?;
+            Ok(true)
+        })
+    }
+
+    /// 共有リンクでノートに参加する
+    ///
+    /// 既にメンバーの場合は、リンクの権限の方が高いときだけ権限を上げる。
+    pub fn join_note_by_share_link(
+        &self,
+        token: &str,
+        user_id: &str,
+    ) -> Result<LocalNoteMember, DbError> {
+        if let Some(active) = self.active_profile()? {
+            if active != user_id {
+                return Err(DbError::PermissionDenied(format!(
+                    "User {} cannot join a note for {}",
+                    active, user_id
+                )));
+            }
+        }
+        self.with_journal("join_note_by_share_link", &[], |tx| {
+            let checked_at = Utc::now();
+            let now = checked_at.to_rfc3339();
+            let link = tx
+                .query_row(
+                    &format!(
+                        "SELECT {} FROM note_share_links WHERE token = ?1",
+                        SHARE_LINK_COLUMNS
+                    ),
+                    [token],
+                    LocalNoteShareLink::from_row,
+                )
+                .optional()?
+                .filter(|link| is_link_active(link, checked_at))
+                .ok_or_else(|| DbError::NotFound("Share link".to_string()))?;
+            let owner_id: String = tx.query_row(
+                "SELECT owner_id FROM notes WHERE id = ?1",
+                [link.note_id.as_str()],
+                |row| row.get(0),
+            )?;
+            if owner_id == user_id {
+                return Err(DbError::ValidationError(format!(
+                    "User {} already owns note {}",
+                    user_id, link.note_id
+                )));
+            }
+
+            let link_role = NoteRole::parse(&link.role).unwrap_or(NoteRole::Viewer);
+            let member = match find_member(tx, &link.note_id, user_id)? {
+                Some(member) => {
+                    let role =
+                        NoteRole::parse(&member.role).map_or(link_role, |role| role.max(link_role));
+                    if member.accepted_at.is_some() && role.as_str() == member.role {
+                        return Ok(member);
+                    }
+                    LocalNoteMember {
+                        role: role.as_str().to_string(),
+                        accepted_at: member.accepted_at.or_else(|| Some(now.clone())),
+                        updated_at: now.clone(),
+                        sync_status: "pending".to_string(),
+                        local_updated_at: now.clone(),
+                        ..member
+                    }
+                }
+                None => LocalNoteMember {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    note_id: link.note_id.clone(),
+                    user_id: user_id.to_string(),
+                    role: link.role.clone(),
+                    invited_by: Some(link.created_by.clone()),
+                    invited_at: now.clone(),
+                    accepted_at: Some(now.clone()),
+                    updated_at: now.clone(),
+                    sync_status: "pending".to_string(),
+                    synced_at: None,
+                    local_updated_at: now.clone(),
+                    server_updated_at: None,
+                },
+            };
+            tx.track("note_members", &member.id)?;
+            upsert_member(tx, &member)?;
+            Ok(member)
+        })
+    }
+
+    /// 同期待ちのメンバーを取得（外したメンバーも含む）
+    pub fn get_pending_sync_note_members(&self) -> Result<Vec<LocalNoteMember>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM note_members WHERE sync_status IN ('pending', 'deleted')",
+            NOTE_MEMBER_COLUMNS
+        ))?;
+        let members = stmt
+            .query_map([], LocalNoteMember::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(members)
+    }
+
+    /// 同期待ちの共有リンクを取得（取り消したリンクも含む）
+    pub fn get_pending_sync_note_share_links(&self) -> Result<Vec<LocalNoteShareLink>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM note_share_links WHERE sync_status IN ('pending', 'deleted')",
+            SHARE_LINK_COLUMNS
+        ))?;
+        let links = stmt
+            .query_map([], LocalNoteShareLink::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(links)
+    }
+
+    /// メンバーを同期済みにマーク（外したメンバーは行を消す）
+    pub fn mark_note_member_synced(
+        &self,
+        id: &str,
+        server_updated_at: &str,
+    ) -> Result<(), DbError> {
+        let conn = self.connection()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "DELETE FROM note_members WHERE id = ?1 AND sync_status = 'deleted'",
+            [id],
+        )?;
+        conn.execute(
+            "UPDATE note_members SET sync_status = 'synced', synced_at = ?1, server_updated_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![now, server_updated_at, id],
+        )?;
+        Ok(())
+    }
+
+    /// 共有リンクを同期済みにマーク（取り消したリンクは行を消す）
+    pub fn mark_note_share_link_synced(
+        &self,
+        id: &str,
+        server_updated_at: &str,
+    ) -> Result<(), DbError> {
+        let conn = self.connection()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "DELETE FROM note_share_links WHERE id = ?1 AND sync_status = 'deleted'",
+            [id],
+        )?;
+        conn.execute(
+            "UPDATE note_share_links SET sync_status = 'synced', synced_at = ?1,
+                server_updated_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![now, server_updated_at, id],
+        )?;
+        Ok(())
+    }
+}
+
+/// メンバーを保存（同じIDの行は置き換える）
+fn upsert_member(conn: &Connection, member: &LocalNoteMember) -> Result<(), DbError> {
+    conn.execute(
+        r#"
+        INSERT OR REPLACE INTO note_members (
+            id, note_id, user_id, role, invited_by, invited_at, accepted_at, updated_at,
+            sync_status, synced_at, local_updated_at, server_updated_at
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+        "#,
+        rusqlite::params![
+            member.id,
+            member.note_id,
+            member.user_id,
+            member.role,
+            member.invited_by,
+            member.invited_at,
+            member.accepted_at,
+            member.updated_at,
+            member.sync_status,
+            member.synced_at,
+            member.local_updated_at,
+            member.server_updated_at,
+        ],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{fixtures, NoteUpdate, PageUpdate};
+    use tempfile::TempDir;
+
+    fn title_update(title: &str) -> NoteUpdate {
+        NoteUpdate {
+            title: Some(title.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_invite_share_link_and_shared_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_note(&LocalNote {
+            owner_id: "owner".to_string(),
+            visibility: "invite".to_string(),
+            ..fixtures::note("note-1")
+        })
+        .unwrap();
+
+        let invited = db
+            .invite_note_member("note-1", "alice", NoteRole::Editor)
+            .unwrap();
+        assert!(invited.accepted_at.is_none());
+        assert_eq!(db.get_note_role("note-1", "alice").unwrap(), None);
+        assert!(db
+            .invite_note_member("note-1", "owner", NoteRole::Viewer)
+            .is_err());
+
+        db.accept_note_invitation("note-1", "alice").unwrap();
+        assert_eq!(
+            db.get_note_role("note-1", "alice").unwrap(),
+            Some(NoteRole::Editor)
+        );
+
+        // 共有リンクは既存の権限を下げない
+        let link = db
+            .create_note_share_link("note-1", NoteRole::Viewer, None)
+            .unwrap();
+        let alice = db.join_note_by_share_link(&link.token, "alice").unwrap();
+        assert_eq!(alice.role, "editor");
+        let bob = db.join_note_by_share_link(&link.token, "bob").unwrap();
+        assert_eq!(bob.role, "viewer");
+        assert!(bob.accepted_at.is_some());
+
+        let shared = db.get_shared_notes("bob").unwrap();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].note.id, "note-1");
+        assert!(db.get_shared_notes("owner").unwrap().is_empty());
+
+        assert!(db.revoke_note_share_link(&link.id).unwrap());
+        assert!(db.join_note_by_share_link(&link.token, "carol").is_err());
+        assert_eq!(db.get_note_members("note-1").unwrap().len(), 2);
+        assert_eq!(db.get_pending_sync_note_members().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_server_overwrite_keeps_pages_members_and_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_note(&LocalNote {
+            owner_id: "owner".to_string(),
+            visibility: "invite".to_string(),
+            ..fixtures::note("note-1")
+        })
+        .unwrap();
+        db.insert_page(&LocalPage {
+            user_id: "owner".to_string(),
+            note_id: Some("note-1".to_string()),
+            ..fixtures::page("page-1")
+        })
+        .unwrap();
+        db.link_page_to_note("note-1", "page-1", None).unwrap();
+        db.invite_note_member("note-1", "alice", NoteRole::Editor)
+            .unwrap();
+        db.create_note_share_link("note-1", NoteRole::Viewer, None)
+            .unwrap();
+
+        db.overwrite_note_with_server(&LocalNote {
+            title: "From server".to_string(),
+            updated_at: "2099-01-01T00:00:00+00:00".to_string(),
+            owner_id: "owner".to_string(),
+            visibility: "invite".to_string(),
+            ..fixtures::note("note-1")
+        })
+        .unwrap();
+
+        let note = db.get_note_by_id("note-1").unwrap().unwrap();
+        assert_eq!(
+            (note.title.as_str(), note.sync_status.as_str()),
+            ("From server", "synced")
+        );
+        assert_eq!(db.get_note_pages("note-1").unwrap().len(), 1);
+        assert_eq!(db.get_note_members("note-1").unwrap().len(), 1);
+        assert_eq!(db.get_note_share_links("note-1").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_viewers_cannot_edit() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.switch_profile("viewer").unwrap();
+
+        db.insert_note(&LocalNote {
+            owner_id: "owner".to_string(),
+            visibility: "invite".to_string(),
+            ..fixtures::note("note-1")
+        })
+        .unwrap();
+        db.insert_page(&LocalPage {
+            user_id: "owner".to_string(),
+            note_id: Some("note-1".to_string()),
+            ..fixtures::page("page-1")
+        })
+        .unwrap();
+        {
+            // サーバーから受け取ったメンバー
+            let conn = db.connection().unwrap();
+            let now = chrono::Utc::now().to_rfc3339();
+            upsert_member(
+                &conn,
+                &LocalNoteMember {
+                    id: "member-1".to_string(),
+                    note_id: "note-1".to_string(),
+                    user_id: "viewer".to_string(),
+                    role: "viewer".to_string(),
+                    invited_by: Some("owner".to_string()),
+                    invited_at: now.clone(),
+                    accepted_at: Some(now.clone()),
+                    updated_at: now.clone(),
+                    sync_status: "synced".to_string(),
+                    synced_at: None,
+                    local_updated_at: now,
+                    server_updated_at: None,
+                },
+            )
+            .unwrap();
+        }
+
+        assert!(matches!(
+            db.update_note("note-1", title_update("changed")),
+            Err(DbError::PermissionDenied(_))
+        ));
+        let page_update = PageUpdate {
+            title: Some("changed".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            db.update_page("page-1", page_update.clone()),
+            Err(DbError::PermissionDenied(_))
+        ));
+        assert!(db
+            .invite_note_member("note-1", "mallory", NoteRole::Editor)
+            .is_err());
+        assert!(matches!(
+            db.delete_page("page-1"),
+            Err(DbError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            db.delete_note("note-1"),
+            Err(DbError::PermissionDenied(_))
+        ));
+        db.insert_page(&LocalPage {
+            note_id: None,
+            user_id: "viewer".to_string(),
+            ..fixtures::page("page-2")
+        })
+        .unwrap();
+        assert!(matches!(
+            db.link_page_to_note("note-1", "page-2", None),
+            Err(DbError::PermissionDenied(_))
+        ));
+        // 自分のページでも、編集できないノートには移せない
+        let move_update = PageUpdate {
+            note_id: Some(Some("note-1".to_string())),
+            ..Default::default()
+        };
+        assert!(matches!(
+            db.update_page("page-2", move_update),
+            Err(DbError::PermissionDenied(_))
+        ));
+
+        // 編集者になれば編集できる
+        {
+            let conn = db.connection().unwrap();
+            conn.execute(
+                "UPDATE note_members SET role = 'editor' WHERE id = 'member-1'",
+                [],
+            )
+            .unwrap();
+        }
+        db.update_note("note-1", title_update("changed")).unwrap();
+        db.update_page("page-1", page_update).unwrap();
+        db.link_page_to_note("note-1", "page-2", None).unwrap();
+        assert!(db.delete_page("page-1").unwrap());
+        // ノートの削除は所有者だけ
+        assert!(matches!(
+            db.delete_note("note-1"),
+            Err(DbError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            db.hard_delete_note("note-1"),
+            Err(DbError::PermissionDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_share_link_expiry_and_joining_user() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_note(&LocalNote {
+            owner_id: "owner".to_string(),
+            visibility: "invite".to_string(),
+            ..fixtures::note("note-1")
+        })
+        .unwrap();
+
+        // 期限はUTCに揃えて保存する
+        let link = db
+            .create_note_share_link(
+                "note-1",
+                NoteRole::Viewer,
+                Some("2099-01-01T09:00:00+09:00"),
+            )
+            .unwrap();
+        assert_eq!(
+            link.expires_at.as_deref(),
+            Some("2099-01-01T00:00:00+00:00")
+        );
+        assert!(matches!(
+            db.create_note_share_link("note-1", NoteRole::Viewer, Some("tomorrow")),
+            Err(DbError::ValidationError(_))
+        ));
+
+        let expired = db
+            .create_note_share_link("note-1", NoteRole::Viewer, Some("2000-01-01T00:00:00Z"))
+            .unwrap();
+        assert!(db.join_note_by_share_link(&expired.token, "bob").is_err());
+
+        // 他のユーザーとしては参加できない
+        db.switch_profile("alice").unwrap();
+        db.insert_note(&LocalNote {
+            owner_id: "owner".to_string(),
+            visibility: "invite".to_string(),
+            ..fixtures::note("note-1")
+        })
+        .unwrap();
+        let link = db
+            .connection()
+            .unwrap()
+            .execute(
+                &format!(
+                    "INSERT INTO note_share_links ({}) VALUES
+                     ('link-1', 'note-1', 'token-1', 'viewer', 'owner', NULL,
+                      '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z', 'synced', NULL,
+                      '2025-01-01T00:00:00Z', NULL)",
+                    SHARE_LINK_COLUMNS
+                ),
+                [],
+            )
+            .map(|_| "token-1")
+            .unwrap();
+        assert!(matches!(
+            db.join_note_by_share_link(link, "bob"),
+            Err(DbError::PermissionDenied(_))
+        ));
+        let alice = db.join_note_by_share_link(link, "alice").unwrap();
+        assert_eq!(alice.role, "viewer");
+    }
+}
@@ -0,0 +1,423 @@
+//! ノートとページの多対多の対応
+//!
+//! Webアプリと同じく、1つのページを複数のノートに入れられるようにする。
+//! `pages.note_id` はページを作成したノート（ホーム）として残し、
+//! ホームの変更に合わせて `note_pages` も更新する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/note_pages_commands.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/note_members.rs (権限の確認)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::journal::JournalTx;
+use super::{find_note, DbError, LocalDB, LocalNote, LocalNotePage};
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::HashSet;
+
+const NOTE_PAGE_COLUMNS: &str = "id, note_id, page_id, position, created_at, updated_at,
+    sync_status, synced_at, local_updated_at, server_updated_at";
+
+/// 既存の `pages.note_id` を `note_pages` に移す（v9 のマイグレーション）
+///
+/// 並び順は従来の `get_pages_by_note` と同じく更新日時の新しい順にする。
+pub(crate) fn migrate_page_note_ids(conn: &Connection) -> Result<usize, DbError> {
+    let rows = conn.execute(
+        r#"
+        INSERT OR IGNORE INTO note_pages (
+            id, note_id, page_id, position, created_at, updated_at, sync_status, local_updated_at
+        )
+        SELECT lower(hex(randomblob(16))), p.note_id, p.id,
+               ROW_NUMBER() OVER (PARTITION BY p.note_id ORDER BY p.updated_at DESC) - 1,
+               p.created_at, p.updated_at, 'pending', p.local_updated_at
+        FROM pages p
+        WHERE p.note_id IS NOT NULL AND p.sync_status != 'deleted'
+          AND EXISTS (SELECT 1 FROM notes n WHERE n.id = p.note_id)
+        "#,
+        [],
+    )?;
+    log::info!("Migrated {} page note_id values to note_pages", rows);
+    Ok(rows)
+}
+
+/// ノートとページの対応を取得
+fn find_note_page(
+    conn: &Connection,
+    note_id: &str,
+    page_id: &str,
+) -> Result<Option<LocalNotePage>, DbError> {
+    let note_page = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM note_pages
+                 WHERE note_id = ?1 AND page_id = ?2 AND sync_status != 'deleted'",
+                NOTE_PAGE_COLUMNS
+            ),
+            [note_id, page_id],
+            LocalNotePage::from_row,
+        )
+        .optional()?;
+    Ok(note_page)
+}
+
+/// ノート内の対応を並び順で取得
+fn note_pages_in_order(conn: &Connection, note_id: &str) -> Result<Vec<LocalNotePage>, DbError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM note_pages
+         WHERE note_id = ?1 AND sync_status != 'deleted'
+         ORDER BY position, created_at",
+        NOTE_PAGE_COLUMNS
+    ))?;
+    let note_pages = stmt
+        .query_map([note_id], LocalNotePage::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(note_pages)
+}
+
+/// ノートの末尾（`position` を省略した場合）または指定位置にページを入れる
+///
+/// ノートが無い場合（同期の順序で未受信など）は何もしない。
+pub(crate) fn insert_note_page(
+    conn: &Connection,
+    id: &str,
+    note_id: &str,
+    page_id: &str,
+    position: Option<i64>,
+    now: &str,
+) -> Result<Option<LocalNotePage>, DbError> {
+    let note_exists = conn
+        .query_row("SELECT 1 FROM notes WHERE id = ?1", [note_id], |_| Ok(()))
+        .optional()?
+        .is_some();
+    if !note_exists {
+        return Ok(None);
+    }
+    if let Some(existing) = find_note_page(conn, note_id, page_id)? {
+        return Ok(Some(existing));
+    }
+
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM note_pages WHERE note_id = ?1 AND sync_status != 'deleted'",
+        [note_id],
+        |row| row.get(0),
+    )?;
+    let position = position.unwrap_or(count).clamp(0, count);
+    conn.execute(
+        r#"
+        UPDATE note_pages SET position = position + 1, updated_at = ?1, local_updated_at = ?1,
+            sync_status = 'pending'
+        WHERE note_id = ?2 AND sync_status != 'deleted' AND position >= ?3
+        "#,
+        rusqlite::params![now, note_id, position],
+    )?;
+    conn.execute(
+        r#"
+        INSERT INTO note_pages (
+            id, note_id, page_id, position, created_at, updated_at, sync_status, local_updated_at
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?5, 'pending', ?5)
+        "#,
+        rusqlite::params![id, note_id, page_id, position, now],
+    )?;
+    find_note_page(conn, note_id, page_id)
+}
+
+/// ノートからページを外し、後ろのページの並び順を詰める
+pub(crate) fn remove_note_page(
+    conn: &Connection,
+    note_id: &str,
+    page_id: &str,
+    now: &str,
+) -> Result<bool, DbError> {
+    let Some(note_page) = find_note_page(conn, note_id, page_id)? else {
+        return Ok(false);
+    };
+    conn.execute(
+        "UPDATE note_pages SET sync_status = 'deleted', local_updated_at = ?1 WHERE id = ?2",
+        rusqlite::params![now, note_page.id],
+    )?;
+    conn.execute(
+        r#"
+        UPDATE note_pages SET position = position - 1, updated_at = ?1, local_updated_at = ?1,
+            sync_status = 'pending'
+        WHERE note_id = ?2 AND sync_status != 'deleted' AND position > ?3
+        "#,
+        rusqlite::params![now, note_id, note_page.position],
+    )?;
+    Ok(true)
+}
+
+/// ノートの対応をジャーナルの対象にする（追加・削除で並び順が変わるため）
+pub(crate) fn track_note_pages(tx: &JournalTx<'_>, note_id: &str) -> Result<(), DbError> {
+    for note_page in note_pages_in_order(tx, note_id)? {
+        tx.track("note_pages", &note_page.id)?;
+    }
+    Ok(())
+}
+
+impl LocalDB {
+    /// ノート内の対応を並び順で取得
+    pub fn get_note_pages(&self, note_id: &str) -> Result<Vec<LocalNotePage>, DbError> {
+        let conn = self.connection()?;
+        note_pages_in_order(&conn, note_id)
+    }
+
+    /// ページが入っているノートを取得
+    pub fn get_notes_by_page(&self, page_id: &str) -> Result<Vec<LocalNote>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT n.id, n.owner_id, n.slug, n.title, n.description, n.visibility,
+                   n.created_at, n.updated_at, n.is_trashed, n.trashed_at,
+                   n.sync_status, n.synced_at, n.local_updated_at, n.server_updated_at
+            FROM note_pages np
+            JOIN notes n ON n.id = np.note_id
+            WHERE np.page_id = ?1 AND np.sync_status != 'deleted' AND n.sync_status != 'deleted'
+            ORDER BY n.title
+            "#,
+        )?;
+        let notes = stmt
+            .query_map([page_id], LocalNote::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(notes)
+    }
+
+    /// ノートにページを入れる（`position` を省略した場合は末尾）
+    ///
+    /// 既に入っている場合はそのまま返す。
+    pub fn link_page_to_note(
+        &self,
+        note_id: &str,
+        page_id: &str,
+        position: Option<i64>,
+    ) -> Result<LocalNotePage, DbError> {
+        let new_id = uuid::Uuid::new_v4().to_string();
+        self.with_journal("link_page_to_note", &[("note_pages", &new_id)], |tx| {
+            if let Some(existing) = find_note_page(tx, note_id, page_id)? {
+                return Ok(existing);
+            }
+            let page_exists = tx
+                .query_row(
+                    "SELECT 1 FROM pages WHERE id = ?1 AND sync_status != 'deleted'",
+                    [page_id],
+                    |_| Ok(()),
+                )
+                .optional()?
+                .is_some();
+            if !page_exists {
+                return Err(DbError::NotFound(format!("Page {}", page_id)));
+            }
+            if find_note(tx, note_id)?.is_none() {
+                return Err(DbError::NotFound(format!("Note {}", note_id)));
+            }
+            self.ensure_can_edit_note(tx, note_id)?;
+            track_note_pages(tx, note_id)?;
+
+            let now = chrono::Utc::now().to_rfc3339();
+            let note_page = insert_note_page(tx, &new_id, note_id, page_id, position, &now)?
+                .ok_or_else(|| DbError::NotFound(format!("Note {}", note_id)))?;
+            Ok(note_page)
+        })
+    }
+
+    /// ノートからページを外す
+    ///
+    /// ページそのものは削除しない（ホームのノートも変えない）。
+    pub fn unlink_page_from_note(&self, note_id: &str, page_id: &str) -> Result<bool, DbError> {
+        self.with_journal("unlink_page_from_note", &[], |tx| {
+            if find_note_page(tx, note_id, page_id)?.is_none() {
+                return Ok(false);
+            }
+            self.ensure_can_edit_note(tx, note_id)?;
+            track_note_pages(tx, note_id)?;
+
+            let now = chrono::Utc::now().to_rfc3339();
+            let removed = remove_note_page(tx, note_id, page_id, &now)?;
+            Ok(removed)
+        })
+    }
+
+    /// ノート内のページを並べ替える
+    ///
+    /// `page_ids` はノートに入っているページをすべて、新しい順序で指定する。
+    pub fn reorder_note_pages(
+        &self,
+        note_id: &str,
+        page_ids: &[String],
+    ) -> Result<Vec<LocalNotePage>, DbError> {
+        self.with_journal("reorder_note_pages", &[], |tx| {
+            let current = note_pages_in_order(tx, note_id)?;
+            let current_ids = current
+                .iter()
+                .map(|note_page| note_page.page_id.as_str())
+                .collect::<HashSet<_>>();
+            let requested_ids = page_ids.iter().map(String::as_str).collect::<HashSet<_>>();
+            if requested_ids.len() != page_ids.len() || requested_ids != current_ids {
+                return Err(DbError::ValidationError(format!(
+                    "page_ids must list every page of note {} exactly once",
+                    note_id
+                )));
+            }
+            self.ensure_can_edit_note(tx, note_id)?;
+            track_note_pages(tx, note_id)?;
+
+            let now = chrono::Utc::now().to_rfc3339();
+            for (position, page_id) in page_ids.iter().enumerate() {
+                // 並び順が変わった行だけを同期待ちにする
+                tx.execute(
+                    r#"
+                    UPDATE note_pages SET position = ?1, updated_at = ?2, local_updated_at = ?2,
+                        sync_status = 'pending'
+                    WHERE note_id = ?3 AND page_id = ?4 AND sync_status != 'deleted'
+                      AND position != ?1
+                    "#,
+                    rusqlite::params![position as i64, now, note_id, page_id],
+                )?;
+            }
+            note_pages_in_order(tx, note_id)
+        })
+    }
+
+    /// 同期待ちの対応を取得（外した対応も含む）
+    pub fn get_pending_sync_note_pages(&self) -> Result<Vec<LocalNotePage>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM note_pages WHERE sync_status IN ('pending', 'deleted')",
+            NOTE_PAGE_COLUMNS
+        ))?;
+        let note_pages = stmt
+            .query_map([], LocalNotePage::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(note_pages)
+    }
+
+    /// 対応を同期済みにマーク（外した対応は行を消す）
+    pub fn mark_note_page_synced(&self, id: &str, server_updated_at: &str) -> Result<(), DbError> {
+        let conn = self.connection()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "DELETE FROM note_pages WHERE id = ?1 AND sync_status = 'deleted'",
+            [id],
+        )?;
+        conn.execute(
+            "UPDATE note_pages SET sync_status = 'synced', synced_at = ?1, server_updated_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![now, server_updated_at, id],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{fixtures, LocalPage, PageUpdate};
+    use tempfile::TempDir;
+
+    fn page_ids(pages: Vec<LocalPage>) -> Vec<String> {
+        pages.into_iter().map(|page| page.id).collect()
+    }
+
+    #[test]
+    fn test_link_unlink_and_reorder_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_note(&fixtures::note("note-1")).unwrap();
+        db.insert_note(&fixtures::note("note-2")).unwrap();
+        for id in ["a", "b"] {
+            db.insert_page(&LocalPage {
+                sync_status: "pending".to_string(),
+                note_id: Some("note-1".to_string()),
+                ..fixtures::page(id)
+            })
+            .unwrap();
+        }
+        db.insert_page(&fixtures::page("c")).unwrap();
+        // サーバーから取得したページの対応は同期で届く
+        db.insert_page(&LocalPage {
+            note_id: Some("note-1".to_string()),
+            ..fixtures::page("pulled")
+        })
+        .unwrap();
+
+        // 新しいページは末尾に入る
+        assert_eq!(
+            page_ids(db.get_pages_by_note("note-1").unwrap()),
+            ["a", "b"]
+        );
+
+        // 同じページを複数のノートに入れられる
+        db.link_page_to_note("note-1", "c", Some(1)).unwrap();
+        db.link_page_to_note("note-2", "a", None).unwrap();
+        assert_eq!(
+            page_ids(db.get_pages_by_note("note-1").unwrap()),
+            ["a", "c", "b"]
+        );
+        assert_eq!(db.get_notes_by_page("a").unwrap().len(), 2);
+
+        let order = ["a", "b", "c"].map(String::from);
+        db.reorder_note_pages("note-1", &order).unwrap();
+        assert_eq!(
+            page_ids(db.get_pages_by_note("note-1").unwrap()),
+            ["a", "b", "c"]
+        );
+        assert!(db
+            .reorder_note_pages("note-1", &["a".to_string(), "b".to_string()])
+            .is_err());
+
+        assert!(db.unlink_page_from_note("note-1", "a").unwrap());
+        assert!(!db.unlink_page_from_note("note-1", "a").unwrap());
+        let positions = db
+            .get_note_pages("note-1")
+            .unwrap()
+            .into_iter()
+            .map(|note_page| note_page.position)
+            .collect::<Vec<_>>();
+        assert_eq!(positions, [0, 1]);
+
+        // ホームのノートを変えると対応も移り、新しいノートの末尾に入る
+        db.update_page(
+            "b",
+            PageUpdate {
+                note_id: Some(Some("note-2".to_string())),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(page_ids(db.get_pages_by_note("note-1").unwrap()), ["c"]);
+        assert_eq!(
+            page_ids(db.get_pages_by_note("note-2").unwrap()),
+            ["a", "b"]
+        );
+    }
+
+    #[test]
+    fn test_migrate_page_note_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_note(&fixtures::note("note-1")).unwrap();
+        db.insert_page(&LocalPage {
+            note_id: Some("note-1".to_string()),
+            ..fixtures::page("a")
+        })
+        .unwrap();
+        db.insert_page(&LocalPage {
+            note_id: Some("missing-note".to_string()),
+            ..fixtures::page("b")
+        })
+        .unwrap();
+
+        let conn = db.connection().unwrap();
+        conn.execute("DELETE FROM note_pages", []).unwrap();
+        assert_eq!(migrate_page_note_ids(&conn).unwrap(), 1);
+        // 2回目は何もしない
+        assert_eq!(migrate_page_note_ids(&conn).unwrap(), 0);
+        drop(conn);
+
+        assert_eq!(page_ids(db.get_pages_by_note("note-1").unwrap()), ["a"]);
+    }
+}
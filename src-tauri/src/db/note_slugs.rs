@@ -0,0 +1,425 @@
+//! ノートのスラッグ
+//!
+//! タイトルからURLに使うスラッグを作り、所有者内での重複は `-2`, `-3`, ... を付けて避ける。
+//! かなはヘボン式のローマ字に直し、漢字などローマ字にできない文字はそのまま残す。
+//! スラッグを変更したときは旧スラッグからのリダイレクトを残し、
+//! `resolve_note_slug` で古いURLからもノートを開けるようにする。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   ├─ src-tauri/src/db/guest.rs
+//!   └─ src-tauri/src/commands/notes_commands.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::history::{self, HistoryEntity, RevisionSource};
+use super::{find_note, DbError, LocalDB, LocalNote};
+use rusqlite::{Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+use unicode_normalization::UnicodeNormalization;
+
+/// スラッグの最大文字数
+pub const MAX_SLUG_CHARS: usize = 80;
+
+/// タイトルが空のときのスラッグ
+const UNTITLED_SLUG: &str = "untitled";
+
+/// ひらがな1文字のローマ字（ヘボン式）
+#[rustfmt::skip]
+fn kana_romaji(kana: char) -> Option<&'static str> {
+    let romaji = match kana {
+        'あ' => "a", 'い' => "i", 'う' => "u", 'え' => "e", 'お' => "o",
+        'か' => "ka", 'き' => "ki", 'く' => "ku", 'け' => "ke", 'こ' => "ko",
+        'が' => "ga", 'ぎ' => "gi", 'ぐ' => "gu", 'げ' => "ge", 'ご' => "go",
+        'さ' => "sa", 'し' => "shi", 'す' => "su", 'せ' => "se", 'そ' => "so",
+        'ざ' => "za", 'じ' => "ji", 'ず' => "zu", 'ぜ' => "ze", 'ぞ' => "zo",
+        'た' => "ta", 'ち' => "chi", 'つ' => "tsu", 'て' => "te", 'と' => "to",
+        'だ' => "da", 'ぢ' => "ji", 'づ' => "zu", 'で' => "de", 'ど' => "do",
+        'な' => "na", 'に' => "ni", 'ぬ' => "nu", 'ね' => "ne", 'の' => "no",
+        'は' => "ha", 'ひ' => "hi", 'ふ' => "fu", 'へ' => "he", 'ほ' => "ho",
+        'ば' => "ba", 'び' => "bi", 'ぶ' => "bu", 'べ' => "be", 'ぼ' => "bo",
+        'ぱ' => "pa", 'ぴ' => "pi", 'ぷ' => "pu", 'ぺ' => "pe", 'ぽ' => "po",
+        'ま' => "ma", 'み' => "mi", 'む' => "mu", 'め' => "me", 'も' => "mo",
+        'や' => "ya", 'ゆ' => "yu", 'よ' => "yo",
+        'ら' => "ra", 'り' => "ri", 'る' => "ru", 'れ' => "re", 'ろ' => "ro",
+        'わ' => "wa", 'ゐ' => "i", 'ゑ' => "e", 'を' => "o", 'ん' => "n",
+        'ゔ' => "vu",
+        _ => return None,
+    };
+    Some(romaji)
+}
+
+/// 小書きのかな（拗音・外来音）の母音部分
+#[rustfmt::skip]
+fn small_kana_vowel(kana: char) -> Option<&'static str> {
+    let vowel = match kana {
+        'ゃ' => "ya", 'ゅ' => "yu", 'ょ' => "yo", 'ゎ' => "wa",
+        'ぁ' => "a", 'ぃ' => "i", 'ぅ' => "u", 'ぇ' => "e", 'ぉ' => "o",
+        _ => return None,
+    };
+    Some(vowel)
+}
+
+/// カタカナをひらがなに揃える
+fn to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// かなの並びをローマ字にする
+fn romanize_kana(kana: &[char]) -> String {
+    let mut romaji = String::new();
+    let mut index = 0;
+    while index < kana.len() {
+        let c = kana[index];
+        // 促音は次の子音を重ねる（「っち」は tchi）
+        if c == 'っ' {
+            if let Some(next) = kana.get(index + 1).and_then(|&next| kana_romaji(next)) {
+                let consonant = if next.starts_with("ch") {
+                    't'
+                } else {
+                    next.chars().next().unwrap_or('t')
+                };
+                if !"aiueon".contains(consonant) {
+                    romaji.push(consonant);
+                }
+            }
+            index += 1;
+            continue;
+        }
+
+        let Some(base) = kana_romaji(c) else {
+            // 長音記号は読み飛ばし、単独の小書きのかなは母音だけにする
+            if let Some(vowel) = small_kana_vowel(c) {
+                romaji.push_str(vowel.trim_start_matches(['y', 'w']));
+            }
+            index += 1;
+            continue;
+        };
+
+        match kana.get(index + 1).and_then(|&next| small_kana_vowel(next)) {
+            // 拗音（きゃ → kya、しゃ → sha）
+            Some(small) if small.starts_with('y') && base.len() > 1 && base.ends_with('i') => {
+                let stem = &base[..base.len() - 1];
+                if stem.ends_with("sh") || stem.ends_with("ch") || stem == "j" {
+                    romaji.push_str(stem);
+                    romaji.push_str(&small[1..]);
+                } else {
+                    romaji.push_str(stem);
+                    romaji.push_str(small);
+                }
+                index += 2;
+            }
+            // 外来音（ふぁ → fa、てぃ → ti、ゔぁ → va）
+            Some(small) if !small.starts_with('y') && base.len() > 1 => {
+                romaji.push_str(&base[..base.len() - 1]);
+                romaji.push_str(small.trim_start_matches('w'));
+                index += 2;
+            }
+            _ => {
+                romaji.push_str(base);
+                index += 1;
+            }
+        }
+    }
+    romaji
+}
+
+/// ラテン文字の合字など、分解しても ASCII にならない文字
+fn latin_fallback(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        'ø' => "o",
+        'đ' | 'ð' => "d",
+        'þ' => "th",
+        'ł' => "l",
+        'ı' => "i",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+/// タイトルからスラッグを作る
+///
+/// - 全角英数字などは NFKC で半角にし、英字は小文字にする
+/// - アクセント付きのラテン文字はアクセントを除く（`é` → `e`）
+/// - かなはヘボン式のローマ字にする（`がくしゅう` → `gakushuu`）
+/// - 漢字・ハングルなどローマ字にできない文字はそのまま残す
+/// - それ以外の記号や空白は `-` にまとめる
+///
+/// 何も残らない場合（記号だけのタイトルなど）はタイトルのハッシュから作る。
+pub fn slugify_title(title: &str) -> String {
+    let chars = title
+        .nfkc()
+        .flat_map(char::to_lowercase)
+        .map(to_hiragana)
+        .collect::<Vec<_>>();
+
+    let mut slug = String::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        if kana_romaji(c).is_some() || small_kana_vowel(c).is_some() || c == 'っ' || c == 'ー' {
+            let start = index;
+            while index < chars.len()
+                && (kana_romaji(chars[index]).is_some()
+                    || small_kana_vowel(chars[index]).is_some()
+                    || matches!(chars[index], 'っ' | 'ー'))
+            {
+                index += 1;
+            }
+            slug.push_str(&romanize_kana(&chars[start..index]));
+            continue;
+        }
+
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if let Some(ascii) = latin_fallback(c) {
+            slug.push_str(ascii);
+        } else if c.is_alphanumeric() {
+            let decomposed = c
+                .to_string()
+                .nfd()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>();
+            if decomposed.is_empty() {
+                slug.push(c);
+            } else {
+                slug.push_str(&decomposed);
+            }
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+        index += 1;
+    }
+
+    let slug = slug
+        .trim_matches('-')
+        .chars()
+        .take(MAX_SLUG_CHARS)
+        .collect::<String>();
+    let slug = slug.trim_end_matches('-');
+    if !slug.is_empty() {
+        return slug.to_string();
+    }
+    if title.trim().is_empty() {
+        return UNTITLED_SLUG.to_string();
+    }
+    let digest = format!("{:x}", Sha256::digest(title.as_bytes()));
+    format!("note-{}", &digest[..8])
+}
+
+/// 所有者内で使われていないスラッグを求める（`slug`, `slug-2`, `slug-3`, ...）
+///
+/// `exclude_note_id` のノート自身のスラッグは使用中として扱わない。
+pub(crate) fn next_available_slug(
+    conn: &Connection,
+    owner_id: &str,
+    slug: &str,
+    exclude_note_id: Option<&str>,
+) -> Result<String, DbError> {
+    let mut stmt = conn
+        .prepare("SELECT 1 FROM main.notes WHERE owner_id = ?1 AND slug = ?2 AND id IS NOT ?3")?;
+
+    let mut candidate = slug.to_string();
+    let mut suffix = 2;
+    while stmt.exists(rusqlite::params![owner_id, candidate, exclude_note_id])? {
+        candidate = format!("{}-{}", slug, suffix);
+        suffix += 1;
+    }
+
+    Ok(candidate)
+}
+
+/// スラッグを使うノートができたら、同じスラッグのリダイレクトは消す
+pub(crate) fn clear_slug_redirect(
+    conn: &Connection,
+    owner_id: &str,
+    slug: &str,
+) -> Result<(), DbError> {
+    conn.execute(
+        "DELETE FROM note_slug_redirects WHERE owner_id = ?1 AND slug = ?2",
+        [owner_id, slug],
+    )?;
+    Ok(())
+}
+
+impl LocalDB {
+    /// タイトルから、所有者内で使われていないスラッグを提案する
+    pub fn suggest_note_slug(&self, owner_id: &str, title: &str) -> Result<String, DbError> {
+        let conn = self.connection()?;
+        next_available_slug(&conn, owner_id, &slugify_title(title), None)
+    }
+
+    /// ノートのスラッグを変更する
+    ///
+    /// `slug` を省略した場合は現在のタイトルから作る。重複する場合は番号を付ける。
+    /// 旧スラッグはリダイレクトとして残す。
+    pub fn change_note_slug(&self, id: &str, slug: Option<&str>) -> Result<LocalNote, DbError> {
+        self.with_journal("change_note_slug", &[("notes", id)], |tx| {
+            let current = find_note(tx, id)?
+                .filter(|note| note.sync_status != "deleted")
+                .ok_or_else(|| DbError::NotFound(format!("Note {}", id)))?;
+            self.ensure_can_edit_note(tx, id)?;
+
+            let base = slugify_title(slug.unwrap_or(&current.title));
+            let new_slug = next_available_slug(tx, &current.owner_id, &base, Some(id))?;
+            if new_slug == current.slug {
+                return Ok(current);
+            }
+
+            history::record_revision(
+                tx,
+                HistoryEntity::Note,
+                id,
+                &current,
+                RevisionSource::LocalEdit,
+                Some(&current.owner_id),
+            )?;
+
+            let now = chrono::Utc::now().to_rfc3339();
+            tx.execute(
+                r#"
+                UPDATE notes SET
+                    slug = ?1, updated_at = ?2, local_updated_at = ?2, sync_status = 'pending'
+                WHERE id = ?3
+                "#,
+                rusqlite::params![new_slug, now, id],
+            )?;
+            clear_slug_redirect(tx, &current.owner_id, &new_slug)?;
+            tx.execute(
+                r#"
+                INSERT OR REPLACE INTO note_slug_redirects (owner_id, slug, note_id, created_at)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                rusqlite::params![current.owner_id, current.slug, id, now],
+            )?;
+
+            Ok(LocalNote {
+                slug: new_slug,
+                updated_at: now.clone(),
+                local_updated_at: now,
+                sync_status: "pending".to_string(),
+                ..current
+            })
+        })
+    }
+
+    /// スラッグからノートを取得する（旧スラッグのリダイレクトもたどる）
+    pub fn resolve_note_slug(
+        &self,
+        owner_id: &str,
+        slug: &str,
+    ) -> Result<Option<LocalNote>, DbError> {
+        let note_id: Option<String> = {
+            let conn = self.connection()?;
+            conn.query_row(
+                r#"
+                SELECT id FROM notes
+                WHERE owner_id = ?1 AND slug = ?2 AND sync_status != 'deleted'
+                UNION ALL
+                SELECT r.note_id FROM note_slug_redirects r
+                JOIN notes n ON n.id = r.note_id
+                WHERE r.owner_id = ?1 AND r.slug = ?2 AND n.sync_status != 'deleted'
+                LIMIT 1
+                "#,
+                [owner_id, slug],
+                |row| row.get(0),
+            )
+            .optional()?
+        };
+
+        match note_id {
+            Some(note_id) => self.get_note_by_id(&note_id),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_slugify_title() {
+        assert_eq!(slugify_title("Hello, World!"), "hello-world");
+        assert_eq!(slugify_title("Ｒｕｓｔ　入門"), "rust-入門");
+        assert_eq!(slugify_title("Café crème"), "cafe-creme");
+        assert_eq!(slugify_title("がくしゅう ノート"), "gakushuu-noto");
+        assert_eq!(slugify_title("きょうのちゃっと"), "kyounochatto");
+        assert_eq!(slugify_title("ファイル"), "fairu");
+        assert_eq!(slugify_title(""), "untitled");
+        assert!(slugify_title("!!!").starts_with("note-"));
+        assert_eq!(
+            slugify_title(&"a".repeat(200)).chars().count(),
+            MAX_SLUG_CHARS
+        );
+    }
+
+    #[test]
+    fn test_slug_collisions_and_redirects() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+
+        // スラッグを省略したノートはタイトルから作り、重複には番号を付ける
+        let first = db
+            .insert_note(&LocalNote {
+                title: "英語 ノート".to_string(),
+                slug: "".to_string(),
+                sync_status: "pending".to_string(),
+                ..fixtures::note("n1")
+            })
+            .unwrap();
+        let second = db
+            .insert_note(&LocalNote {
+                title: "英語 ノート".to_string(),
+                slug: "".to_string(),
+                sync_status: "pending".to_string(),
+                ..fixtures::note("n2")
+            })
+            .unwrap();
+        assert_eq!(first.slug, "英語-noto");
+        assert_eq!(second.slug, "英語-noto-2");
+        assert_eq!(
+            db.suggest_note_slug("user-1", "英語ノート").unwrap(),
+            "英語noto"
+        );
+
+        let renamed = db.change_note_slug("n1", Some("English")).unwrap();
+        assert_eq!(renamed.slug, "english");
+        assert_eq!(
+            db.resolve_note_slug("user-1", "英語-noto")
+                .unwrap()
+                .unwrap()
+                .id,
+            "n1"
+        );
+
+        // 旧スラッグを別のノートが使うと、そちらが優先される
+        let renamed = db.change_note_slug("n2", Some("英語-noto")).unwrap();
+        assert_eq!(renamed.slug, "英語-noto");
+        assert_eq!(
+            db.resolve_note_slug("user-1", "英語-noto")
+                .unwrap()
+                .unwrap()
+                .id,
+            "n2"
+        );
+        assert_eq!(
+            db.resolve_note_slug("user-1", "英語-noto-2")
+                .unwrap()
+                .unwrap()
+                .id,
+            "n2"
+        );
+        assert!(db.resolve_note_slug("user-1", "missing").unwrap().is_none());
+    }
+}
@@ -0,0 +1,321 @@
+//! ページ本文の更新ログの圧縮
+//!
+//! 編集のたびに追記される Yjs の更新ログを、件数・サイズが閾値を超えたら
+//! 1つのスナップショットにまとめる。送信済みの更新と未送信のローカル更新は
+//! 別々にまとめるので、圧縮後もサーバーへの差分と未送信の判定は変わらない。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/page_documents_commands.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/page_documents.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::page_documents::merge_updates;
+use super::{DbError, LocalDB};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// 圧縮を行う更新件数の閾値
+pub const COMPACTION_UPDATE_COUNT: i64 = 100;
+
+/// 圧縮を行う更新ログの合計サイズの閾値（バイト）
+pub const COMPACTION_TOTAL_BYTES: i64 = 1024 * 1024;
+
+/// ページ本文の更新ログの統計
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageDocumentStats {
+    pub page_id: String,
+    pub update_count: i64,
+    pub total_bytes: i64,
+    /// 未送信のローカル更新
+    pub pending_update_count: i64,
+    pub pending_bytes: i64,
+    pub last_updated_at: Option<String>,
+}
+
+/// 圧縮結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageDocumentCompaction {
+    pub page_id: String,
+    pub updates_before: usize,
+    pub updates_after: usize,
+    pub bytes_before: usize,
+    pub bytes_after: usize,
+}
+
+/// ページごとの統計を集計する（`page_id` を省略すると全ページ）
+fn query_stats(
+    conn: &Connection,
+    page_id: Option<&str>,
+) -> Result<Vec<PageDocumentStats>, DbError> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT
+            u.page_id,
+            COUNT(*),
+            SUM(LENGTH(u.update_data)),
+            SUM(CASE WHEN u.origin = 'local' AND u.id > COALESCE(d.synced_update_id, 0) THEN 1 ELSE 0 END),
+            SUM(CASE WHEN u.origin = 'local' AND u.id > COALESCE(d.synced_update_id, 0) THEN LENGTH(u.update_data) ELSE 0 END),
+            MAX(u.created_at)
+        FROM page_document_updates u
+        LEFT JOIN page_documents d ON d.page_id = u.page_id
+        WHERE ?1 IS NULL OR u.page_id = ?1
+        GROUP BY u.page_id
+        ORDER BY u.page_id
+        "#,
+    )?;
+
+    let stats = stmt
+        .query_map([page_id], |row| {
+            Ok(PageDocumentStats {
+                page_id: row.get(0)?,
+                update_count: row.get(1)?,
+                total_bytes: row.get(2)?,
+                pending_update_count: row.get(3)?,
+                pending_bytes: row.get(4)?,
+                last_updated_at: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(stats)
+}
+
+/// 閾値を超えているか
+fn needs_compaction(stats: &PageDocumentStats) -> bool {
+    // 送信済み・未送信のスナップショット2件までは圧縮しても変わらない
+    stats.update_count > 2
+        && (stats.update_count >= COMPACTION_UPDATE_COUNT
+            || stats.total_bytes >= COMPACTION_TOTAL_BYTES)
+}
+
+/// ページの更新ログを送信済み・未送信の2つにまとめる
+fn compact_page(conn: &mut Connection, page_id: &str) -> Result<PageDocumentCompaction, DbError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let tx = conn.transaction()?;
+
+    let synced_update_id: i64 = tx.query_row(
+        "SELECT COALESCE((SELECT synced_update_id FROM page_documents WHERE page_id = ?1), 0)",
+        [page_id],
+        |row| row.get(0),
+    )?;
+
+    let updates = {
+        let mut stmt = tx.prepare(
+            "SELECT id, update_data, origin FROM page_document_updates WHERE page_id = ?1 ORDER BY id",
+        )?;
+        let updates = stmt
+            .query_map([page_id], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, Vec<u8>>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        updates
+    };
+
+    let bytes_before = updates.iter().map(|(_, update, _)| update.len()).sum();
+    let updates_before = updates.len();
+
+    let (synced, pending): (Vec<_>, Vec<_>) = updates
+        .into_iter()
+        .partition(|(id, _, origin)| origin == "server" || *id <= synced_update_id);
+    let synced = synced
+        .into_iter()
+        .map(|(id, update, _)| (id, update))
+        .collect::<Vec<_>>();
+    let pending = pending
+        .into_iter()
+        .map(|(id, update, _)| (id, update))
+        .collect::<Vec<_>>();
+
+    // サーバーが受け取り済みの内容は origin = 'server'、未送信分は 'local' として残す。
+    // 未送信分は新しいIDになるため、送信中に圧縮された場合は次回も差分に含まれる（Yjsの更新は冪等）
+    let mut snapshots = Vec::new();
+    if !synced.is_empty() {
+        snapshots.push((merge_updates(&synced)?, "server"));
+    }
+    if !pending.is_empty() {
+        snapshots.push((merge_updates(&pending)?, "local"));
+    }
+
+    tx.execute(
+        "DELETE FROM page_document_updates WHERE page_id = ?1",
+        [page_id],
+    )?;
+    for (update, origin) in &snapshots {
+        tx.execute(
+            r#"
+            INSERT INTO page_document_updates (page_id, update_data, origin, created_at)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+            rusqlite::params![page_id, update, origin, now],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(PageDocumentCompaction {
+        page_id: page_id.to_string(),
+        updates_before,
+        updates_after: snapshots.len(),
+        bytes_before,
+        bytes_after: snapshots.iter().map(|(update, _)| update.len()).sum(),
+    })
+}
+
+impl LocalDB {
+    /// ページ本文の更新ログの統計を取得（`page_id` を省略すると全ページ）
+    pub fn get_page_document_stats(
+        &self,
+        page_id: Option<&str>,
+    ) -> Result<Vec<PageDocumentStats>, DbError> {
+        let conn = self.connection()?;
+        query_stats(&conn, page_id)
+    }
+
+    /// ページの更新ログを圧縮する
+    pub fn compact_page_document(&self, page_id: &str) -> Result<PageDocumentCompaction, DbError> {
+        let mut conn = self.connection()?;
+        compact_page(&mut conn, page_id)
+    }
+
+    /// 閾値を超えたページの更新ログを圧縮する
+    pub fn compact_page_documents(&self) -> Result<Vec<PageDocumentCompaction>, DbError> {
+        let mut conn = self.connection()?;
+        let targets = query_stats(&conn, None)?
+            .into_iter()
+            .filter(needs_compaction)
+            .collect::<Vec<_>>();
+
+        let mut results = Vec::new();
+        for stats in targets {
+            results.push(compact_page(&mut conn, &stats.page_id)?);
+        }
+        Ok(results)
+    }
+
+    /// 閾値を超えていればページの更新ログを圧縮する
+    pub(crate) fn compact_page_document_if_needed(
+        &self,
+        page_id: &str,
+    ) -> Result<Option<PageDocumentCompaction>, DbError> {
+        let mut conn = self.connection()?;
+        let Some(stats) = query_stats(&conn, Some(page_id))?.pop() else {
+            return Ok(None);
+        };
+        if !needs_compaction(&stats) {
+            return Ok(None);
+        }
+
+        let result = compact_page(&mut conn, page_id)?;
+        log::info!(
+            "Compacted page document {}: {} updates ({} bytes) -> {} ({} bytes)",
+            page_id,
+            result.updates_before,
+            result.bytes_before,
+            result.updates_after,
+            result.bytes_after
+        );
+        Ok(Some(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{fixtures, DocumentUpdateOrigin};
+    use tempfile::TempDir;
+    use yrs::updates::decoder::Decode;
+    use yrs::{Doc, GetString, ReadTxn, Text, Transact, Update};
+
+    fn read_text(update: &[u8]) -> String {
+        let doc = Doc::new();
+        let content = doc.get_or_insert_text("content");
+        let mut txn = doc.transact_mut();
+        txn.apply_update(Update::decode_v1(update).unwrap())
+            .unwrap();
+        content.get_string(&txn)
+    }
+
+    #[test]
+    fn test_compaction_keeps_content_and_pending_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_page(&fixtures::page("page-1")).unwrap();
+
+        // 1文字ずつ入力した更新ログを作る
+        let doc = Doc::with_client_id(1);
+        let content = doc.get_or_insert_text("content");
+        let mut server_updates = Vec::new();
+        for (i, c) in "abcdef".chars().enumerate() {
+            let mut txn = doc.transact_mut();
+            let before = txn.state_vector();
+            content.insert(&mut txn, i as u32, &c.to_string());
+            let update = txn.encode_state_as_update_v1(&before);
+            db.apply_page_update("page-1", &update, DocumentUpdateOrigin::Local)
+                .unwrap();
+
+            // 3文字目まではサーバーに送信済み
+            if i <= 2 {
+                server_updates.push(update);
+            }
+            if i == 2 {
+                let diff = db.get_page_document_diff("page-1", None).unwrap();
+                let state_vector = db.get_page_state_vector("page-1").unwrap();
+                db.mark_page_document_synced("page-1", &state_vector, diff.up_to_update_id)
+                    .unwrap();
+            }
+        }
+
+        let result = db.compact_page_document("page-1").unwrap();
+        assert_eq!(result.updates_before, 6);
+        assert_eq!(result.updates_after, 2);
+
+        let stats = db.get_page_document_stats(Some("page-1")).unwrap();
+        assert_eq!(stats[0].update_count, 2);
+        assert_eq!(stats[0].pending_update_count, 1);
+
+        let document = db.get_page_document("page-1").unwrap();
+        assert_eq!(read_text(&document.update), "abcdef");
+        assert!(document.has_pending_changes);
+
+        // サーバーの内容に差分を適用すると最新の内容になる
+        let diff = db.get_page_document_diff("page-1", None).unwrap();
+        server_updates.push(diff.update);
+        let server_doc = yrs::merge_updates_v1(&server_updates).unwrap();
+        assert_eq!(read_text(&server_doc), "abcdef");
+    }
+
+    #[test]
+    fn test_compaction_only_runs_over_threshold() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_page(&fixtures::page("page-1")).unwrap();
+
+        let doc = Doc::with_client_id(1);
+        let content = doc.get_or_insert_text("content");
+        for i in 0..COMPACTION_UPDATE_COUNT {
+            let mut txn = doc.transact_mut();
+            let before = txn.state_vector();
+            content.insert(&mut txn, i as u32, "x");
+            let update = txn.encode_state_as_update_v1(&before);
+            db.apply_page_update("page-1", &update, DocumentUpdateOrigin::Local)
+                .unwrap();
+        }
+
+        // 閾値に達した時点で自動的に圧縮されている
+        let stats = db.get_page_document_stats(None).unwrap();
+        assert_eq!(stats[0].update_count, 1);
+        assert!(db.compact_page_documents().unwrap().is_empty());
+        assert_eq!(
+            read_text(&db.get_page_document("page-1").unwrap().update).len(),
+            COMPACTION_UPDATE_COUNT as usize
+        );
+    }
+}
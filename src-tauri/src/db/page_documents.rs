@@ -0,0 +1,367 @@
+//! ページ本文（Yjsドキュメント）のオフライン保存
+//!
+//! ページ本文はサーバーとYjsで同期するため、ローカルでも Yjs v1 形式の更新を
+//! `page_document_updates` に追記していく。ドキュメントは全更新をマージして復元し、
+//! サーバーへはサーバーの状態ベクトルとの差分だけを送る。
+//! Yjsの更新は可換なので、オフライン中の編集も再接続時にそのままマージできる。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   ├─ src-tauri/src/db/page_links.rs
+//!   ├─ src-tauri/src/db/page_rename.rs
+//!   └─ src-tauri/src/commands/page_documents_commands.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/note_members.rs (ローカルの編集の権限の確認)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{find_page, page_links, DbError, LocalDB};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// 空のドキュメントを表す Yjs v1 の更新（構造体0件・削除集合0件）
+pub const EMPTY_UPDATE_V1: [u8; 2] = [0, 0];
+
+/// 空の状態ベクトル（クライアント0件）
+pub const EMPTY_STATE_VECTOR_V1: [u8; 1] = [0];
+
+/// 更新の発生元
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentUpdateOrigin {
+    /// ローカルでの編集（サーバーへの送信対象）
+    Local,
+    /// サーバーから受信した更新
+    Server,
+}
+
+impl DocumentUpdateOrigin {
+    /// `page_document_updates.origin` に保存する値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocumentUpdateOrigin::Local => "local",
+            DocumentUpdateOrigin::Server => "server",
+        }
+    }
+}
+
+/// ページ本文の現在の状態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageDocumentState {
+    pub page_id: String,
+    /// 全更新をマージした Yjs v1 の更新（`Y.applyUpdate` でそのまま読み込める）
+    pub update: Vec<u8>,
+    /// Yjs v1 の状態ベクトル
+    pub state_vector: Vec<u8>,
+    /// サーバーに未送信のローカル更新があるか
+    pub has_pending_changes: bool,
+}
+
+/// サーバーに送る差分
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageDocumentDiff {
+    pub page_id: String,
+    /// サーバーの状態ベクトルとの差分（Yjs v1 の更新）
+    pub update: Vec<u8>,
+    /// 差分に含まれる最後の更新ログID（送信完了時に `mark_page_document_synced` へ渡す）
+    pub up_to_update_id: i64,
+}
+
+/// Yjsのエンコード・デコードエラーを変換する
+pub(crate) fn document_error(err: impl std::fmt::Display) -> DbError {
+    DbError::DocumentError(err.to_string())
+}
+
+/// ページの全更新を読み込む（古い順）
+pub(crate) fn load_updates(
+    conn: &Connection,
+    page_id: &str,
+) -> Result<Vec<(i64, Vec<u8>)>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, update_data FROM page_document_updates WHERE page_id = ?1 ORDER BY id",
+    )?;
+    let updates = stmt
+        .query_map([page_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(updates)
+}
+
+/// 更新を1つにマージする
+pub(crate) fn merge_updates(updates: &[(i64, Vec<u8>)]) -> Result<Vec<u8>, DbError> {
+    match updates {
+        [] => Ok(EMPTY_UPDATE_V1.to_vec()),
+        [(_, update)] => Ok(update.clone()),
+        _ => {
+            let updates = updates
+                .iter()
+                .map(|(_, update)| update.as_slice())
+                .collect::<Vec<_>>();
+            yrs::merge_updates_v1(&updates).map_err(document_error)
+        }
+    }
+}
+
+/// サーバーに未送信のローカル更新があるか
+fn has_pending_changes(conn: &Connection, page_id: &str) -> Result<bool, DbError> {
+    let pending: bool = conn.query_row(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM page_document_updates u
+            WHERE u.page_id = ?1
+              AND u.origin = 'local'
+              AND u.id > COALESCE(
+                (SELECT synced_update_id FROM page_documents WHERE page_id = ?1), 0
+              )
+        )
+        "#,
+        [page_id],
+        |row| row.get(0),
+    )?;
+    Ok(pending)
+}
+
+/// サーバーに未送信のローカル更新があるページID
+pub(crate) fn pending_page_document_ids(conn: &Connection) -> Result<Vec<String>, DbError> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT DISTINCT u.page_id FROM page_document_updates u
+        LEFT JOIN page_documents d ON d.page_id = u.page_id
+        WHERE u.origin = 'local' AND u.id > COALESCE(d.synced_update_id, 0)
+        ORDER BY u.page_id
+        "#,
+    )?;
+    let page_ids = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(page_ids)
+}
+
+impl LocalDB {
+    /// ページ本文を取得
+    pub fn get_page_document(&self, page_id: &str) -> Result<PageDocumentState, DbError> {
+        let conn = self.connection()?;
+        let update = merge_updates(&load_updates(&conn, page_id)?)?;
+        let state_vector =
+            yrs::encode_state_vector_from_update_v1(&update).map_err(document_error)?;
+
+        Ok(PageDocumentState {
+            page_id: page_id.to_string(),
+            update,
+            state_vector,
+            has_pending_changes: has_pending_changes(&conn, page_id)?,
+        })
+    }
+
+    /// ページ本文の状態ベクトルを取得
+    pub fn get_page_state_vector(&self, page_id: &str) -> Result<Vec<u8>, DbError> {
+        let conn = self.connection()?;
+        let updates = load_updates(&conn, page_id)?;
+        if updates.is_empty() {
+            return Ok(EMPTY_STATE_VECTOR_V1.to_vec());
+        }
+        let update = merge_updates(&updates)?;
+        yrs::encode_state_vector_from_update_v1(&update).map_err(document_error)
+    }
+
+    /// ページ本文に更新を適用する
+    ///
+    /// 更新は検証したうえで更新ログに追記する。マージは読み込み時に行い、
+    /// 更新ログが閾値を超えたら圧縮する。
+    pub fn apply_page_update(
+        &self,
+        page_id: &str,
+        update: &[u8],
+        origin: DocumentUpdateOrigin,
+    ) -> Result<(), DbError> {
+        // 壊れた更新を保存するとドキュメント全体が読めなくなるため、先にデコードしておく
+        yrs::encode_state_vector_from_update_v1(update).map_err(document_error)?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut conn = self.connection()?;
+        let tx = conn.transaction()?;
+
+        let page = find_page(&tx, page_id)?
+            .ok_or_else(|| DbError::NotFound(format!("Page {}", page_id)))?;
+        // サーバーからの更新は他のメンバーの編集なので、自分の権限では確かめない
+        if origin == DocumentUpdateOrigin::Local {
+            self.ensure_can_edit_page(&tx, &page)?;
+        }
+
+        tx.execute(
+            r#"
+            INSERT INTO page_document_updates (page_id, update_data, origin, created_at)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+            rusqlite::params![page_id, update, origin.as_str(), now],
+        )?;
+        tx.execute(
+            "INSERT OR IGNORE INTO page_documents (page_id) VALUES (?1)",
+            [page_id],
+        )?;
+        page_links::index_page_document(&tx, page_id)?;
+        tx.commit()?;
+        drop(conn);
+
+        self.compact_page_document_if_needed(page_id)?;
+
+        Ok(())
+    }
+
+    /// サーバーに送る差分を作成する
+    ///
+    /// `server_state_vector` を省略した場合は、最後に送信完了したときの状態ベクトルを使う。
+    pub fn get_page_document_diff(
+        &self,
+        page_id: &str,
+        server_state_vector: Option<&[u8]>,
+    ) -> Result<PageDocumentDiff, DbError> {
+        let conn = self.connection()?;
+        let updates = load_updates(&conn, page_id)?;
+        let up_to_update_id = updates.last().map(|(id, _)| *id).unwrap_or(0);
+        let merged = merge_updates(&updates)?;
+
+        let stored_state_vector: Option<Vec<u8>> = conn
+            .query_row(
+                "SELECT server_state_vector FROM page_documents WHERE page_id = ?1",
+                [page_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+
+        let update = match server_state_vector.or(stored_state_vector.as_deref()) {
+            Some(state_vector) => {
+                yrs::diff_updates_v1(&merged, state_vector).map_err(document_error)?
+            }
+            None => merged,
+        };
+
+        Ok(PageDocumentDiff {
+            page_id: page_id.to_string(),
+            update,
+            up_to_update_id,
+        })
+    }
+
+    /// 差分の送信完了を記録する
+    ///
+    /// `up_to_update_id` より後に追加されたローカル更新は引き続き未送信として扱う。
+    pub fn mark_page_document_synced(
+        &self,
+        page_id: &str,
+        server_state_vector: &[u8],
+        up_to_update_id: i64,
+    ) -> Result<(), DbError> {
+        let conn = self.connection()?;
+        let now = chrono::Utc::now().to_rfc3339();
+
+        conn.execute(
+            r#"
+            INSERT INTO page_documents (page_id, server_state_vector, synced_update_id, synced_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(page_id) DO UPDATE SET
+                server_state_vector = excluded.server_state_vector,
+                synced_update_id = MAX(synced_update_id, excluded.synced_update_id),
+                synced_at = excluded.synced_at
+            "#,
+            rusqlite::params![page_id, server_state_vector, up_to_update_id, now],
+        )?;
+
+        Ok(())
+    }
+
+    /// サーバーに未送信のローカル更新があるページIDを取得
+    pub fn get_pending_page_documents(&self) -> Result<Vec<String>, DbError> {
+        let conn = self.connection()?;
+        pending_page_document_ids(&conn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures;
+    use tempfile::TempDir;
+    use yrs::updates::decoder::Decode;
+    use yrs::{Doc, GetString, ReadTxn, Text, Transact, Update};
+
+    /// 指定したクライアントIDで文字列を挿入した更新を作る
+    fn insert_text(client_id: u64, index: u32, text: &str, base: &[u8]) -> Vec<u8> {
+        let doc = Doc::with_client_id(client_id);
+        let content = doc.get_or_insert_text("content");
+        let mut txn = doc.transact_mut();
+        txn.apply_update(Update::decode_v1(base).unwrap()).unwrap();
+        let before = txn.state_vector();
+        content.insert(&mut txn, index, text);
+        txn.encode_state_as_update_v1(&before)
+    }
+
+    fn read_text(update: &[u8]) -> String {
+        let doc = Doc::new();
+        let content = doc.get_or_insert_text("content");
+        let mut txn = doc.transact_mut();
+        txn.apply_update(Update::decode_v1(update).unwrap())
+            .unwrap();
+        content.get_string(&txn)
+    }
+
+    #[test]
+    fn test_offline_updates_merge_with_server_updates() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_page(&fixtures::page("page-1")).unwrap();
+
+        let base = insert_text(1, 0, "hello", &EMPTY_UPDATE_V1);
+        db.apply_page_update("page-1", &base, DocumentUpdateOrigin::Server)
+            .unwrap();
+        let server_state = db.get_page_state_vector("page-1").unwrap();
+        db.mark_page_document_synced("page-1", &server_state, 0)
+            .unwrap();
+        assert!(db.get_pending_page_documents().unwrap().is_empty());
+
+        // オフライン中のローカル編集と、同時に行われたサーバー側の編集
+        let local = insert_text(2, 5, " world", &base);
+        db.apply_page_update("page-1", &local, DocumentUpdateOrigin::Local)
+            .unwrap();
+        let remote = insert_text(3, 0, ">", &base);
+        db.apply_page_update("page-1", &remote, DocumentUpdateOrigin::Server)
+            .unwrap();
+
+        let document = db.get_page_document("page-1").unwrap();
+        assert!(document.has_pending_changes);
+        assert_eq!(read_text(&document.update), ">hello world");
+
+        // 差分にはサーバーが持っていないローカル編集が含まれる
+        let diff = db.get_page_document_diff("page-1", None).unwrap();
+        let server_doc =
+            yrs::merge_updates_v1(&[base.as_slice(), remote.as_slice(), diff.update.as_slice()])
+                .unwrap();
+        assert_eq!(read_text(&server_doc), ">hello world");
+
+        let server_state = yrs::encode_state_vector_from_update_v1(&server_doc).unwrap();
+        db.mark_page_document_synced("page-1", &server_state, diff.up_to_update_id)
+            .unwrap();
+        assert!(db.get_pending_page_documents().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_update_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_page(&fixtures::page("page-1")).unwrap();
+
+        let result =
+            db.apply_page_update("page-1", &[0xff, 0xff, 0xff], DocumentUpdateOrigin::Local);
+        assert!(matches!(result, Err(DbError::DocumentError(_))));
+
+        let update = insert_text(1, 0, "x", &EMPTY_UPDATE_V1);
+        let result = db.apply_page_update("missing", &update, DocumentUpdateOrigin::Local);
+        assert!(matches!(result, Err(DbError::NotFound(_))));
+
+        let document = db.get_page_document("page-1").unwrap();
+        assert_eq!(document.update, EMPTY_UPDATE_V1.to_vec());
+    }
+}
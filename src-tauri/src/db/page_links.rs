@@ -0,0 +1,425 @@
+//! ページリンクのインデックス（バックリンク・未作成リンク・2ホップリンク）
+//!
+//! ページ本文を保存するたびに本文の `[タイトル]`・`#タグ` を取り出し、
+//! `page_links` に記録し直す。リンク先は正規化したタイトル（key）で持ち、
+//! ページとの照合は問い合わせ時に行う（後からページを作っても繋がる）。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   ├─ src-tauri/src/db/page_documents.rs
+//!   └─ src-tauri/src/commands/page_links_commands.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/content/links.rs
+//!   └─ src-tauri/src/content/yjs.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::page_documents::{load_updates, merge_updates};
+use super::{DbError, LocalDB, LocalPage};
+use crate::content::{
+    extract_links, normalize_title_to_key, yjs_update_to_tiptap, ExtractedLink, LinkVariant,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// ページの列（`LocalPage::from_row` の順）
+const PAGE_COLUMNS: &str = "p.id, p.user_id, p.note_id, p.title, p.thumbnail_url, p.is_public,
+    p.scrapbox_page_id, p.scrapbox_page_list_synced_at, p.scrapbox_page_content_synced_at,
+    p.created_at, p.updated_at,
+    p.sync_status, p.synced_at, p.local_updated_at, p.server_updated_at";
+
+/// ページからのリンク
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageLink {
+    pub key: String,
+    /// 最初に書かれたときのタイトル
+    pub title: String,
+    pub variant: LinkVariant,
+    /// 本文中の出現回数
+    pub occurrences: i64,
+    /// リンク先のページ（未作成なら None）
+    pub target_page_id: Option<String>,
+}
+
+/// リンク先のページがまだ無いリンク
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedLink {
+    pub key: String,
+    pub title: String,
+    /// リンクしているページ
+    pub source_page_ids: Vec<String>,
+}
+
+/// 2ホップリンク（同じリンク先を持つページのまとまり）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoHopLink {
+    pub key: String,
+    pub title: String,
+    /// 共通のリンク先のページ（未作成なら None）
+    pub target_page_id: Option<String>,
+    /// 同じリンク先にリンクしている他のページ
+    pub pages: Vec<LocalPage>,
+}
+
+/// ページのリンクを記録し直す
+pub(crate) fn replace_page_links(
+    conn: &Connection,
+    page_id: &str,
+    links: &[ExtractedLink],
+) -> Result<(), DbError> {
+    conn.execute(
+        "DELETE FROM page_links WHERE source_page_id = ?1",
+        [page_id],
+    )?;
+    let mut stmt = conn.prepare(
+        "INSERT INTO page_links (source_page_id, position, target_key, target_title, variant)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    for link in links {
+        stmt.execute(params![
+            page_id,
+            link.position as i64,
+            link.key,
+            link.title,
+            link.variant.as_str(),
+        ])?;
+    }
+    Ok(())
+}
+
+/// ページ本文（Yjsドキュメント）からリンクを取り出して記録し直す
+///
+/// 本文が Tiptap の構造で読めない場合は、更新の保存を妨げないよう記録を飛ばす。
+pub(crate) fn index_page_document(conn: &Connection, page_id: &str) -> Result<usize, DbError> {
+    let updates = load_updates(conn, page_id)?;
+    let links = if updates.is_empty() {
+        Vec::new()
+    } else {
+        match yjs_update_to_tiptap(&merge_updates(&updates)?) {
+            Ok(doc) => extract_links(&doc),
+            Err(err) => {
+                log::warn!("Failed to index links of page {}: {}", page_id, err);
+                return Ok(0);
+            }
+        }
+    };
+    replace_page_links(conn, page_id, &links)?;
+    Ok(links.len())
+}
+
+/// 本文を持つ全ページのリンクを記録し直す
+pub(crate) fn rebuild_page_links(conn: &Connection) -> Result<usize, DbError> {
+    let page_ids = {
+        let mut stmt = conn.prepare("SELECT page_id FROM page_documents")?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        ids
+    };
+    for page_id in &page_ids {
+        index_page_document(conn, page_id)?;
+    }
+    Ok(page_ids.len())
+}
+
+/// ユーザーのページを正規化したタイトルで引く表（key → ページID）
+///
+/// 同じタイトルのページが複数あれば古いものを優先する
+pub(crate) fn user_page_keys(
+    conn: &Connection,
+    user_id: &str,
+) -> Result<HashMap<String, String>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, title FROM pages
+         WHERE user_id = ?1 AND sync_status != 'deleted'
+         ORDER BY created_at",
+    )?;
+    let pages = stmt
+        .query_map([user_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut keys = HashMap::new();
+    for (id, title) in pages {
+        keys.entry(normalize_title_to_key(&title)).or_insert(id);
+    }
+    Ok(keys)
+}
+
+/// ページの所有者とリンクのキーを取得
+fn page_owner_and_key(conn: &Connection, page_id: &str) -> Result<(String, String), DbError> {
+    let page: Option<(String, String)> = conn
+        .query_row(
+            "SELECT user_id, title FROM pages WHERE id = ?1",
+            [page_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let (user_id, title) = page.ok_or_else(|| DbError::NotFound(format!("Page {}", page_id)))?;
+    Ok((user_id, normalize_title_to_key(&title)))
+}
+
+/// リンクのキーへリンクしているページを取得（`exclude_page_id` を除く）
+fn linking_pages(
+    conn: &Connection,
+    user_id: &str,
+    exclude_page_id: &str,
+    key: &str,
+) -> Result<Vec<LocalPage>, DbError> {
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT {} FROM pages p
+         WHERE p.user_id = ?1 AND p.sync_status != 'deleted' AND p.id != ?2
+           AND EXISTS (
+               SELECT 1 FROM page_links l
+               WHERE l.source_page_id = p.id AND l.target_key = ?3
+           )
+         ORDER BY p.updated_at DESC",
+        PAGE_COLUMNS
+    ))?;
+    let pages = stmt
+        .query_map(params![user_id, exclude_page_id, key], LocalPage::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(pages)
+}
+
+impl LocalDB {
+    /// ページからのリンクを取得（本文の出現順）
+    pub fn get_outgoing_links(&self, page_id: &str) -> Result<Vec<PageLink>, DbError> {
+        let conn = self.connection()?;
+        let (user_id, _) = page_owner_and_key(&conn, page_id)?;
+        let page_keys = user_page_keys(&conn, &user_id)?;
+
+        let mut stmt = conn.prepare(
+            "SELECT target_key, target_title, variant, COUNT(*), MIN(position)
+             FROM page_links
+             WHERE source_page_id = ?1
+             GROUP BY target_key, variant
+             ORDER BY MIN(position)",
+        )?;
+        let links = stmt
+            .query_map([page_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(links
+            .into_iter()
+            .filter_map(|(key, title, variant, occurrences)| {
+                Some(PageLink {
+                    target_page_id: page_keys.get(&key).cloned(),
+                    variant: LinkVariant::parse(&variant)?,
+                    key,
+                    title,
+                    occurrences,
+                })
+            })
+            .collect())
+    }
+
+    /// ページへリンクしているページ（バックリンク）を取得
+    pub fn get_backlinks(&self, page_id: &str) -> Result<Vec<LocalPage>, DbError> {
+        let conn = self.connection()?;
+        let (user_id, key) = page_owner_and_key(&conn, page_id)?;
+
+        linking_pages(&conn, &user_id, page_id, &key)
+    }
+
+    /// リンク先のページがまだ無いリンクを取得（リンク元の多い順）
+    pub fn get_unresolved_links(&self, user_id: &str) -> Result<Vec<UnresolvedLink>, DbError> {
+        let conn = self.connection()?;
+        let page_keys = user_page_keys(&conn, user_id)?;
+
+        let mut stmt = conn.prepare(
+            "SELECT l.target_key, l.target_title, l.source_page_id
+             FROM page_links l
+             JOIN pages p ON p.id = l.source_page_id
+             WHERE p.user_id = ?1 AND p.sync_status != 'deleted'
+             ORDER BY l.source_page_id, l.position",
+        )?;
+        let rows = stmt
+            .query_map([user_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut unresolved: Vec<UnresolvedLink> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for (key, title, source_page_id) in rows {
+            if page_keys.contains_key(&key) {
+                continue;
+            }
+            let position = *index.entry(key.clone()).or_insert_with(|| {
+                unresolved.push(UnresolvedLink {
+                    key,
+                    title,
+                    source_page_ids: Vec::new(),
+                });
+                unresolved.len() - 1
+            });
+            let link = &mut unresolved[position];
+            if !link.source_page_ids.contains(&source_page_id) {
+                link.source_page_ids.push(source_page_id);
+            }
+        }
+
+        unresolved.sort_by(|a, b| {
+            b.source_page_ids
+                .len()
+                .cmp(&a.source_page_ids.len())
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        Ok(unresolved)
+    }
+
+    /// 2ホップリンクを取得（Scrapbox の関連ページ）
+    ///
+    /// ページのリンク先ごとに、同じリンク先へリンクしている他のページをまとめる。
+    pub fn get_two_hop_links(&self, page_id: &str) -> Result<Vec<TwoHopLink>, DbError> {
+        let outgoing = self.get_outgoing_links(page_id)?;
+        let conn = self.connection()?;
+        let (user_id, _) = page_owner_and_key(&conn, page_id)?;
+
+        let mut groups = Vec::new();
+        let mut seen = HashSet::new();
+        for link in outgoing {
+            // ページリンクとタグで同じキーを指していれば1つにまとめる
+            if !seen.insert(link.key.clone()) {
+                continue;
+            }
+            let mut pages = linking_pages(&conn, &user_id, page_id, &link.key)?;
+            pages.retain(|page| Some(&page.id) != link.target_page_id.as_ref());
+            if pages.is_empty() {
+                continue;
+            }
+            groups.push(TwoHopLink {
+                key: link.key,
+                title: link.title,
+                target_page_id: link.target_page_id,
+                pages,
+            });
+        }
+        Ok(groups)
+    }
+
+    /// 全ページのリンクを記録し直す（記録したページ数を返す）
+    pub fn rebuild_page_links(&self) -> Result<usize, DbError> {
+        let conn = self.connection()?;
+        rebuild_page_links(&conn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures;
+    use tempfile::TempDir;
+
+    fn links(titles: &[&str]) -> Vec<ExtractedLink> {
+        titles
+            .iter()
+            .enumerate()
+            .map(|(position, title)| ExtractedLink {
+                key: normalize_title_to_key(title),
+                title: title.to_string(),
+                variant: LinkVariant::Bracket,
+                position,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_backlinks_and_unresolved_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_page(&LocalPage {
+            title: "Rust".to_string(),
+            ..fixtures::page("rust")
+        })
+        .unwrap();
+        db.insert_page(&LocalPage {
+            title: "A".to_string(),
+            ..fixtures::page("a")
+        })
+        .unwrap();
+        db.insert_page(&LocalPage {
+            title: "B".to_string(),
+            ..fixtures::page("b")
+        })
+        .unwrap();
+        {
+            let conn = db.connection().unwrap();
+            replace_page_links(&conn, "a", &links(&["Rust", "Cargo", "Rust"])).unwrap();
+            replace_page_links(&conn, "b", &links(&["Rust", "Cargo"])).unwrap();
+        }
+
+        let backlinks = db.get_backlinks("rust").unwrap();
+        let mut ids = backlinks
+            .iter()
+            .map(|page| page.id.as_str())
+            .collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, vec!["a", "b"]);
+
+        let outgoing = db.get_outgoing_links("a").unwrap();
+        assert_eq!(outgoing.len(), 2);
+        assert_eq!(outgoing[0].occurrences, 2);
+        assert_eq!(outgoing[0].target_page_id.as_deref(), Some("rust"));
+        assert!(outgoing[1].target_page_id.is_none());
+
+        let unresolved = db.get_unresolved_links("user-1").unwrap();
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].key, "Cargo");
+        assert_eq!(unresolved[0].source_page_ids.len(), 2);
+
+        // ページを作るとリンクが繋がる
+        db.insert_page(&LocalPage {
+            title: "Cargo".to_string(),
+            ..fixtures::page("cargo")
+        })
+        .unwrap();
+        assert!(db.get_unresolved_links("user-1").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_two_hop_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        for (id, title) in [("a", "A"), ("b", "B"), ("c", "C"), ("rust", "Rust")] {
+            db.insert_page(&LocalPage {
+                title: title.to_string(),
+                ..fixtures::page(id)
+            })
+            .unwrap();
+        }
+        {
+            let conn = db.connection().unwrap();
+            replace_page_links(&conn, "a", &links(&["Rust", "Memo"])).unwrap();
+            replace_page_links(&conn, "b", &links(&["Rust"])).unwrap();
+            replace_page_links(&conn, "c", &links(&["Memo"])).unwrap();
+            replace_page_links(&conn, "rust", &links(&["Rust"])).unwrap();
+        }
+
+        let groups = db.get_two_hop_links("a").unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].key, "Rust");
+        assert_eq!(groups[0].target_page_id.as_deref(), Some("rust"));
+        // リンク先のページ自身は含めない
+        assert_eq!(groups[0].pages.len(), 1);
+        assert_eq!(groups[0].pages[0].id, "b");
+        assert_eq!(groups[1].pages[0].id, "c");
+    }
+}
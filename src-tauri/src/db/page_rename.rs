@@ -0,0 +1,513 @@
+//! ページ名の変更とリンクの書き換え
+//!
+//! `pages.title` はリンク（`[タイトル]`・`#タグ`）の参照先なので、タイトルを変えると
+//! そのページへのリンクがすべて切れてしまう。名前の変更と同時に、リンクしている
+//! ページ本文（Yjsドキュメント）とカードの内容（Tiptap JSON）を書き換える。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/pages_commands.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/content/links.rs
+//!   ├─ src-tauri/src/content/yjs.rs
+//!   └─ src-tauri/src/db/note_members.rs (権限の確認)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::history::{self, HistoryEntity, RevisionSource};
+use super::page_documents::{document_error, load_updates, merge_updates, DocumentUpdateOrigin};
+use super::{find_page, page_links, DbError, LocalCard, LocalDB, LocalPage, CARD_COLUMNS};
+use crate::content::{normalize_title_to_key, rename_links, rename_links_in_yjs, TiptapNode};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// ページ名の変更結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageRenameResult {
+    pub page: LocalPage,
+    /// 本文を書き換えたページのID
+    pub rewritten_page_ids: Vec<String>,
+    /// 内容を書き換えたカードのID
+    pub rewritten_card_ids: Vec<String>,
+    /// 書き換えたリンクの総数
+    pub rewritten_links: usize,
+}
+
+/// 書き換え後のカードの内容
+struct CardRewrite {
+    card: LocalCard,
+    front_content: String,
+    back_content: String,
+    links: usize,
+}
+
+/// Tiptap JSON の文字列のリンクを書き換える（書き換えが無ければ None）
+fn rename_links_in_json(content: &str, old_key: &str, new_title: &str) -> Option<(String, usize)> {
+    // リンクを含まない内容は解析しない
+    if !content.contains("unilink") {
+        return None;
+    }
+    let mut doc: TiptapNode = serde_json::from_str(content).ok()?;
+    let count = rename_links(&mut doc, old_key, new_title);
+    if count == 0 {
+        return None;
+    }
+    Some((serde_json::to_string(&doc).ok()?, count))
+}
+
+/// 同じキーのタイトルを持つ別のページを探す
+///
+/// ノートに属さないページは同じユーザーの個人ページと比べる。ノートのページは
+/// `pages.note_id` のノートに加えて `note_pages` で入っているすべてのノートの
+/// ページと比べる。
+fn find_title_conflict(
+    conn: &Connection,
+    page: &LocalPage,
+    key: &str,
+) -> Result<Option<String>, DbError> {
+    let mut stmt = conn.prepare(
+        r#"
+        WITH scope(note_id) AS (
+            SELECT ?2 WHERE ?2 IS NOT NULL
+            UNION
+            SELECT note_id FROM note_pages WHERE page_id = ?3 AND sync_status != 'deleted'
+        )
+        SELECT p.id, p.title FROM pages p
+        WHERE p.id != ?3 AND p.sync_status != 'deleted'
+          AND (
+            (?2 IS NULL AND p.user_id = ?1 AND p.note_id IS NULL)
+            OR p.note_id IN (SELECT note_id FROM scope)
+            OR p.id IN (
+                SELECT np.page_id FROM note_pages np
+                WHERE np.note_id IN (SELECT note_id FROM scope) AND np.sync_status != 'deleted'
+            )
+          )
+        "#,
+    )?;
+    let pages = stmt
+        .query_map(
+            rusqlite::params![page.user_id, page.note_id, page.id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(pages
+        .into_iter()
+        .find(|(_, title)| normalize_title_to_key(title) == key)
+        .map(|(id, _)| id))
+}
+
+/// リンクを書き換えるカードを探す
+fn card_rewrites(
+    conn: &Connection,
+    user_id: &str,
+    old_key: &str,
+    new_title: &str,
+) -> Result<Vec<CardRewrite>, DbError> {
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT {}
+        FROM cards
+        WHERE user_id = ?1 AND sync_status != 'deleted'
+          AND (front_content LIKE '%unilink%' OR back_content LIKE '%unilink%')
+        "#,
+        CARD_COLUMNS
+    ))?;
+    let cards = stmt
+        .query_map([user_id], LocalCard::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(cards
+        .into_iter()
+        .filter_map(|card| {
+            let front = rename_links_in_json(&card.front_content, old_key, new_title);
+            let back = rename_links_in_json(&card.back_content, old_key, new_title);
+            if front.is_none() && back.is_none() {
+                return None;
+            }
+            let links =
+                front.as_ref().map_or(0, |(_, n)| *n) + back.as_ref().map_or(0, |(_, n)| *n);
+            Some(CardRewrite {
+                front_content: front.map_or_else(|| card.front_content.clone(), |(c, _)| c),
+                back_content: back.map_or_else(|| card.back_content.clone(), |(c, _)| c),
+                card,
+                links,
+            })
+        })
+        .collect())
+}
+
+/// リンクの書き換えが必要なページ本文（ページID）
+fn linking_page_ids(conn: &Connection, user_id: &str, key: &str) -> Result<Vec<String>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT l.source_page_id FROM page_links l
+         JOIN pages p ON p.id = l.source_page_id
+         WHERE p.user_id = ?1 AND p.sync_status != 'deleted' AND l.target_key = ?2
+         ORDER BY l.source_page_id",
+    )?;
+    let ids = stmt
+        .query_map(rusqlite::params![user_id, key], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ids)
+}
+
+/// ページ名を変更し、このページへのリンクを書き換える（トランザクションの中で呼ぶ）
+///
+/// 変更前のページは `source` のリビジョンとして履歴に残す。権限は呼び出し側で確かめる。
+pub(crate) fn rename_in_transaction(
+    tx: &Connection,
+    current: &LocalPage,
+    new_title: &str,
+    rewrite_links: bool,
+    source: RevisionSource,
+) -> Result<PageRenameResult, DbError> {
+    let id = current.id.as_str();
+    let new_key = normalize_title_to_key(new_title);
+    let old_key = normalize_title_to_key(&current.title);
+
+    if let Some(conflict) = find_title_conflict(tx, current, &new_key)? {
+        return Err(DbError::Conflict(format!(
+            "Page {} already has the title \"{}\"",
+            conflict, new_title
+        )));
+    }
+    // 同じタイトルの古いページがあれば、リンクはそちらを指すので書き換えない
+    let links_resolve_here = page_links::user_page_keys(tx, &current.user_id)?
+        .get(&old_key)
+        .is_some_and(|page_id| page_id == id);
+    let (page_ids, cards) = if rewrite_links && old_key != new_key && links_resolve_here {
+        (
+            linking_page_ids(tx, &current.user_id, &old_key)?,
+            card_rewrites(tx, &current.user_id, &old_key, new_title)?,
+        )
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    history::record_revision(
+        tx,
+        HistoryEntity::Page,
+        id,
+        current,
+        source,
+        Some(&current.user_id),
+    )?;
+    tx.execute(
+        r#"
+        UPDATE pages SET
+            title = ?1, updated_at = ?2, local_updated_at = ?2, sync_status = 'pending'
+        WHERE id = ?3
+        "#,
+        rusqlite::params![new_title, now, id],
+    )?;
+
+    let mut rewritten_links = 0;
+
+    // ページ本文は差分の更新を追記する（他の端末の編集ともマージできる）
+    let mut rewritten_page_ids = Vec::new();
+    for page_id in &page_ids {
+        let updates = load_updates(tx, page_id)?;
+        if updates.is_empty() {
+            continue;
+        }
+        let renamed = rename_links_in_yjs(&merge_updates(&updates)?, &old_key, new_title)
+            .map_err(document_error)?;
+        let Some((update, count)) = renamed else {
+            continue;
+        };
+        tx.execute(
+            r#"
+            INSERT INTO page_document_updates (page_id, update_data, origin, created_at)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+            rusqlite::params![page_id, update, DocumentUpdateOrigin::Local.as_str(), now],
+        )?;
+        tx.execute(
+            "INSERT OR IGNORE INTO page_documents (page_id) VALUES (?1)",
+            [page_id],
+        )?;
+        page_links::index_page_document(tx, page_id)?;
+        rewritten_page_ids.push(page_id.clone());
+        rewritten_links += count;
+    }
+
+    let mut rewritten_card_ids = Vec::new();
+    for rewrite in &cards {
+        history::record_revision(
+            tx,
+            HistoryEntity::Card,
+            &rewrite.card.id,
+            &rewrite.card,
+            RevisionSource::LocalEdit,
+            Some(&rewrite.card.user_id),
+        )?;
+        tx.execute(
+            r#"
+            UPDATE cards SET
+                front_content = ?1, back_content = ?2,
+                updated_at = ?3, local_updated_at = ?3, sync_status = 'pending'
+            WHERE id = ?4
+            "#,
+            rusqlite::params![
+                rewrite.front_content,
+                rewrite.back_content,
+                now,
+                rewrite.card.id
+            ],
+        )?;
+        rewritten_card_ids.push(rewrite.card.id.clone());
+        rewritten_links += rewrite.links;
+    }
+
+    let page = find_page(tx, id)?.ok_or_else(|| DbError::NotFound(format!("Page {}", id)))?;
+    Ok(PageRenameResult {
+        page,
+        rewritten_page_ids,
+        rewritten_card_ids,
+        rewritten_links,
+    })
+}
+
+impl LocalDB {
+    /// ページ名を変更する
+    ///
+    /// `rewrite_links` が true なら、このページへのリンクをページ本文とカードの内容で
+    /// 新しいタイトルに書き換える。リンクはユーザーのページのうち同じタイトルの
+    /// 最も古いページを指すため、このページを指しているリンクだけを書き換える。タイトルの変更と書き換えは1つのトランザクションで行う。
+    /// 書き換えたページ本文は元に戻せないため、名前の変更は元に戻せない操作とし、
+    /// このセッションの元に戻す・やり直す履歴を削除する。
+    /// 同じユーザー・同じノートに同じタイトル（正規化したキー）のページがある場合は
+    /// `DbError::Conflict` を返す。
+    pub fn rename_page(
+        &self,
+        id: &str,
+        new_title: &str,
+        rewrite_links: bool,
+    ) -> Result<PageRenameResult, DbError> {
+        let new_title = new_title.trim();
+        if new_title.is_empty() {
+            return Err(DbError::ValidationError(
+                "Page title must not be empty".to_string(),
+            ));
+        }
+
+        let result = self.with_journal("rename_page", &[("pages", id)], |tx| {
+            let current = find_page(tx, id)?
+                .filter(|page| page.sync_status != "deleted")
+                .ok_or_else(|| DbError::NotFound(format!("Page {}", id)))?;
+            self.ensure_can_edit_page(tx, &current)?;
+            let result = rename_in_transaction(
+                tx,
+                &current,
+                new_title,
+                rewrite_links,
+                RevisionSource::LocalEdit,
+            )?;
+            // 書き換えたページ本文の更新はジャーナルで戻せない
+            tx.mark_irreversible();
+            Ok(result)
+        })?;
+
+        for page_id in &result.rewritten_page_ids {
+            self.compact_page_document_if_needed(page_id)?;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::tiptap::bracket_link_mark;
+    use crate::content::{extract_links, TiptapNode};
+    use crate::db::fixtures;
+    use tempfile::TempDir;
+
+    fn open_db() -> (TempDir, LocalDB) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        (temp_dir, db)
+    }
+
+    #[test]
+    fn test_rename_page_rewrites_card_links() {
+        let (_temp_dir, db) = open_db();
+        db.insert_page(&LocalPage {
+            title: "Rust".to_string(),
+            ..fixtures::page("rust")
+        })
+        .unwrap();
+        let front = TiptapNode::new(
+            "doc",
+            vec![TiptapNode::new(
+                "paragraph",
+                vec![TiptapNode::text("Rust", vec![bracket_link_mark("Rust")])],
+            )],
+        );
+        db.insert_card(&LocalCard {
+            front_content: serde_json::to_string(&front).unwrap(),
+            back_content: r#"{"type":"doc","content":[]}"#.to_string(),
+            ..fixtures::card("card-1", "deck-1")
+        })
+        .unwrap();
+
+        let result = db.rename_page("rust", "Rust 言語", true).unwrap();
+        assert_eq!(result.page.title, "Rust 言語");
+        assert_eq!(result.page.sync_status, "pending");
+        assert_eq!(result.rewritten_card_ids, vec!["card-1"]);
+        assert_eq!(result.rewritten_links, 1);
+
+        let card = db.get_card_by_id("card-1").unwrap().unwrap();
+        assert_eq!(card.sync_status, "pending");
+        let doc: TiptapNode = serde_json::from_str(&card.front_content).unwrap();
+        assert_eq!(extract_links(&doc)[0].key, "Rust 言語");
+
+        // 書き換えない場合はタイトルだけ変わる
+        let result = db.rename_page("rust", "Rust lang", false).unwrap();
+        assert_eq!(result.rewritten_links, 0);
+        let card = db.get_card_by_id("card-1").unwrap().unwrap();
+        let doc: TiptapNode = serde_json::from_str(&card.front_content).unwrap();
+        assert_eq!(extract_links(&doc)[0].key, "Rust 言語");
+    }
+
+    #[test]
+    fn test_rename_page_detects_title_conflict() {
+        let (_temp_dir, db) = open_db();
+        db.insert_page(&LocalPage {
+            title: "A".to_string(),
+            ..fixtures::page("a")
+        })
+        .unwrap();
+        db.insert_page(&LocalPage {
+            title: "B  page".to_string(),
+            ..fixtures::page("b")
+        })
+        .unwrap();
+        db.insert_page(&LocalPage {
+            title: "C".to_string(),
+            note_id: Some("note-1".to_string()),
+            ..fixtures::page("c")
+        })
+        .unwrap();
+
+        assert!(matches!(
+            db.rename_page("a", "B_page", true),
+            Err(DbError::Conflict(_))
+        ));
+        assert!(matches!(
+            db.rename_page("a", "  ", true),
+            Err(DbError::ValidationError(_))
+        ));
+        // 別のノートのページとは衝突しない
+        assert!(db.rename_page("c", "B page", true).is_ok());
+        assert_eq!(db.get_page_by_id("a").unwrap().unwrap().title, "A");
+    }
+
+    #[test]
+    fn test_rename_page_checks_every_note_the_page_is_in() {
+        let (_temp_dir, db) = open_db();
+        db.insert_note(&fixtures::note("note-2")).unwrap();
+        db.insert_page(&LocalPage {
+            title: "A".to_string(),
+            note_id: Some("note-1".to_string()),
+            ..fixtures::page("a")
+        })
+        .unwrap();
+        db.insert_page(&LocalPage {
+            title: "B".to_string(),
+            note_id: Some("note-2".to_string()),
+            ..fixtures::page("b")
+        })
+        .unwrap();
+        db.insert_page(&LocalPage {
+            title: "C".to_string(),
+            ..fixtures::page("c")
+        })
+        .unwrap();
+        db.link_page_to_note("note-2", "a", None).unwrap();
+        db.link_page_to_note("note-2", "c", None).unwrap();
+
+        // note-2 にも入っているので、note-2 のページと衝突する
+        assert!(matches!(
+            db.rename_page("a", "B", true),
+            Err(DbError::Conflict(_))
+        ));
+        // note-2 に入っている別のページとも衝突する
+        assert!(matches!(
+            db.rename_page("c", "A", true),
+            Err(DbError::Conflict(_))
+        ));
+        assert!(matches!(
+            db.rename_page("b", "C", true),
+            Err(DbError::Conflict(_))
+        ));
+        assert!(db.rename_page("a", "A2", true).is_ok());
+    }
+
+    #[test]
+    fn test_rename_page_only_rewrites_links_to_that_page() {
+        let (_temp_dir, db) = open_db();
+        db.insert_page(&LocalPage {
+            created_at: "2020-01-01T00:00:00+00:00".to_string(),
+            title: "Rust".to_string(),
+            note_id: Some("note-1".to_string()),
+            ..fixtures::page("older")
+        })
+        .unwrap();
+        db.insert_page(&LocalPage {
+            title: "Rust".to_string(),
+            note_id: Some("note-2".to_string()),
+            ..fixtures::page("newer")
+        })
+        .unwrap();
+        let front = TiptapNode::new(
+            "doc",
+            vec![TiptapNode::new(
+                "paragraph",
+                vec![TiptapNode::text("Rust", vec![bracket_link_mark("Rust")])],
+            )],
+        );
+        db.insert_card(&LocalCard {
+            front_content: serde_json::to_string(&front).unwrap(),
+            back_content: r#"{"type":"doc","content":[]}"#.to_string(),
+            ..fixtures::card("card-1", "deck-1")
+        })
+        .unwrap();
+
+        // リンクは古いページを指しているので、新しいページの名前を変えても書き換えない
+        let result = db.rename_page("newer", "Rust 2", true).unwrap();
+        assert!(result.rewritten_card_ids.is_empty());
+
+        let result = db.rename_page("older", "Rust 言語", true).unwrap();
+        assert_eq!(result.rewritten_card_ids, vec!["card-1"]);
+    }
+
+    #[test]
+    fn test_rename_page_clears_undo_history() {
+        let (_temp_dir, db) = open_db();
+        db.insert_page(&LocalPage {
+            title: "Rust".to_string(),
+            ..fixtures::page("page-1")
+        })
+        .unwrap();
+        db.insert_page(&LocalPage {
+            title: "Go".to_string(),
+            ..fixtures::page("page-2")
+        })
+        .unwrap();
+        db.delete_page("page-2").unwrap();
+        assert!(db.get_undo_state().unwrap().can_undo);
+
+        // 名前の変更は元に戻せず、それより前の削除も元に戻せなくなる
+        db.rename_page("page-1", "Rust 言語", true).unwrap();
+        let state = db.get_undo_state().unwrap();
+        assert!(!state.can_undo);
+        assert!(!state.can_redo);
+    }
+}
@@ -0,0 +1,311 @@
+//! プロファイル（ユーザー別DB）管理
+//!
+//! 同じPCで複数アカウントを使っても同期待ちデータが混ざらないよう、
+//! ユーザーごとに独立したSQLiteファイルを持たせる。
+//!
+//! ```text
+//! <app_data_dir>/
+//!   ├─ local.db                  プロファイル未選択時のDB
+//!   ├─ active_profile            最後に選択したユーザーID
+//!   └─ profiles/<user_id>/local.db
+//! ```
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/profiles_commands.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{journal, DbError, LocalDB, DB_FILE_NAME};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// プロファイルDBを格納するディレクトリ名
+const PROFILES_DIR: &str = "profiles";
+
+/// 最後に選択したプロファイルを記録するファイル名
+const ACTIVE_PROFILE_FILE: &str = "active_profile";
+
+/// 封印されたプロファイルに置くマーカーファイル名
+const SEALED_MARKER_FILE: &str = "sealed";
+
+/// ローカルに存在するプロファイル
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalProfile {
+    pub user_id: String,
+    pub db_path: String,
+    /// DBファイルとWALの合計サイズ
+    pub size_bytes: u64,
+    pub last_modified_at: Option<String>,
+    pub is_active: bool,
+    /// 再認証するまで開けない状態か
+    pub is_sealed: bool,
+}
+
+/// プロファイルのディレクトリを取得
+pub(crate) fn profile_dir(data_dir: &Path, user_id: &str) -> PathBuf {
+    data_dir.join(PROFILES_DIR).join(user_id)
+}
+
+/// プロファイルが封印されているか
+pub(crate) fn is_sealed(data_dir: &Path, user_id: &str) -> bool {
+    profile_dir(data_dir, user_id)
+        .join(SEALED_MARKER_FILE)
+        .exists()
+}
+
+/// プロファイルの封印状態を設定する
+pub(crate) fn set_sealed(data_dir: &Path, user_id: &str, sealed: bool) -> Result<(), DbError> {
+    let marker = profile_dir(data_dir, user_id).join(SEALED_MARKER_FILE);
+    let result = if sealed {
+        std::fs::write(&marker, chrono::Utc::now().to_rfc3339())
+    } else if marker.exists() {
+        std::fs::remove_file(&marker)
+    } else {
+        Ok(())
+    };
+    result.map_err(|e| DbError::IoError(e.to_string()))
+}
+
+/// 最後にアクティブだったプロファイルを読み込む
+pub(crate) fn read_active_profile(data_dir: &Path) -> Result<Option<String>, DbError> {
+    let path = data_dir.join(ACTIVE_PROFILE_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let user_id = std::fs::read_to_string(&path).map_err(|e| DbError::IoError(e.to_string()))?;
+    let user_id = user_id.trim();
+
+    // 壊れた記録は無視して共有DBで起動する
+    if validate_user_id(user_id).is_err() {
+        log::warn!("Ignoring invalid active profile record: {:?}", user_id);
+        return Ok(None);
+    }
+
+    // 封印されたプロファイルは自動では開かない
+    if is_sealed(data_dir, user_id) {
+        return Ok(None);
+    }
+
+    Ok(Some(user_id.to_string()))
+}
+
+/// アクティブなプロファイルを記録する
+fn write_active_profile(data_dir: &Path, user_id: Option<&str>) -> Result<(), DbError> {
+    let path = data_dir.join(ACTIVE_PROFILE_FILE);
+    let result = match user_id {
+        Some(user_id) => std::fs::write(&path, user_id),
+        None if path.exists() => std::fs::remove_file(&path),
+        None => Ok(()),
+    };
+    result.map_err(|e| DbError::IoError(e.to_string()))
+}
+
+/// ユーザーIDがディレクトリ名として安全か検証
+///
+/// SupabaseのユーザーIDはUUIDなので、英数字・ハイフン・アンダースコアのみ許可する。
+pub(crate) fn validate_user_id(user_id: &str) -> Result<(), DbError> {
+    let is_valid = !user_id.is_empty()
+        && user_id.len() <= 128
+        && user_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(DbError::ValidationError(format!(
+            "Invalid profile id: {:?}",
+            user_id
+        )))
+    }
+}
+
+/// DBファイルとWAL/SHMの合計サイズを取得
+fn db_files_size(db_path: &Path) -> u64 {
+    ["", "-wal", "-shm"]
+        .iter()
+        .filter_map(|suffix| {
+            let mut path = db_path.as_os_str().to_owned();
+            path.push(suffix);
+            std::fs::metadata(PathBuf::from(path)).ok()
+        })
+        .map(|meta| meta.len())
+        .sum()
+}
+
+impl LocalDB {
+    /// 現在のプロファイルのユーザーIDを取得
+    pub fn active_profile(&self) -> Result<Option<String>, DbError> {
+        let active = self
+            .active_profile
+            .lock()
+            .map_err(|e| DbError::LockError(e.to_string()))?;
+        Ok(active.clone())
+    }
+
+    /// プロファイルを切り替える
+    ///
+    /// 対象ユーザーのDBを開いてマイグレーションを適用し、
+    /// 成功した場合のみ管理中の接続を差し替える。
+    /// 封印されたプロファイルは `unseal_profile` でのみ開ける。
+    pub fn switch_profile(&self, user_id: &str) -> Result<LocalProfile, DbError> {
+        validate_user_id(user_id)?;
+        if is_sealed(&self.data_dir, user_id) {
+            return Err(DbError::ProfileSealed(user_id.to_string()));
+        }
+
+        let db_path = Self::get_db_path(&self.data_dir, Some(user_id));
+        let new_conn = Self::open_connection(&db_path)?;
+        self.changes.install_hooks(&new_conn);
+        journal::clear_other_sessions(&new_conn, &self.session_id)?;
+
+        {
+            let mut conn = self.connection()?;
+            let mut active = self
+                .active_profile
+                .lock()
+                .map_err(|e| DbError::LockError(e.to_string()))?;
+
+            // 古い接続はここでクローズされる
+            *conn = new_conn;
+            *active = Some(user_id.to_string());
+        }
+
+        write_active_profile(&self.data_dir, Some(user_id))?;
+        log::info!("Switched local profile to {}", user_id);
+
+        Ok(self.describe_profile(user_id, &db_path, true))
+    }
+
+    /// プロファイル未選択（共有DB）に戻す
+    pub fn close_profile(&self) -> Result<(), DbError> {
+        let db_path = Self::get_db_path(&self.data_dir, None);
+        let new_conn = Self::open_connection(&db_path)?;
+        self.changes.install_hooks(&new_conn);
+        journal::clear_other_sessions(&new_conn, &self.session_id)?;
+
+        {
+            let mut conn = self.connection()?;
+            let mut active = self
+                .active_profile
+                .lock()
+                .map_err(|e| DbError::LockError(e.to_string()))?;
+
+            *conn = new_conn;
+            *active = None;
+        }
+
+        write_active_profile(&self.data_dir, None)
+    }
+
+    /// ローカルに存在するプロファイル一覧を取得
+    pub fn list_local_profiles(&self) -> Result<Vec<LocalProfile>, DbError> {
+        let profiles_dir = self.data_dir.join(PROFILES_DIR);
+        if !profiles_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let active = self.active_profile()?;
+        let entries =
+            std::fs::read_dir(&profiles_dir).map_err(|e| DbError::IoError(e.to_string()))?;
+
+        let mut profiles = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| DbError::IoError(e.to_string()))?;
+            let Some(user_id) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if validate_user_id(&user_id).is_err() {
+                continue;
+            }
+
+            let db_path = entry.path().join(DB_FILE_NAME);
+            if !db_path.exists() {
+                continue;
+            }
+
+            let is_active = active.as_deref() == Some(user_id.as_str());
+            profiles.push(self.describe_profile(&user_id, &db_path, is_active));
+        }
+
+        profiles.sort_by(|a, b| b.last_modified_at.cmp(&a.last_modified_at));
+
+        Ok(profiles)
+    }
+
+    /// プロファイル情報を組み立てる
+    fn describe_profile(&self, user_id: &str, db_path: &Path, is_active: bool) -> LocalProfile {
+        let last_modified_at = std::fs::metadata(db_path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .map(|time| chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339());
+
+        LocalProfile {
+            user_id: user_id.to_string(),
+            db_path: db_path.to_string_lossy().to_string(),
+            size_bytes: db_files_size(db_path),
+            last_modified_at,
+            is_active,
+            is_sealed: is_sealed(&self.data_dir, user_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{fixtures, LocalDeck};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_switch_profile_isolates_pending_rows() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+
+        db.switch_profile("user-a").unwrap();
+        db.insert_deck(&LocalDeck {
+            user_id: "user-a".to_string(),
+            sync_status: "pending".to_string(),
+            ..fixtures::deck("deck-a")
+        })
+        .unwrap();
+        assert_eq!(db.get_pending_sync_decks().unwrap().len(), 1);
+
+        db.switch_profile("user-b").unwrap();
+        assert!(db.get_pending_sync_decks().unwrap().is_empty());
+
+        db.switch_profile("user-a").unwrap();
+        assert_eq!(db.get_pending_sync_decks().unwrap()[0].id, "deck-a");
+    }
+
+    #[test]
+    fn test_active_profile_is_restored_on_open() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+            db.switch_profile("user-a").unwrap();
+            db.switch_profile("user-b").unwrap();
+        }
+
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(db.active_profile().unwrap().as_deref(), Some("user-b"));
+
+        let profiles = db.list_local_profiles().unwrap();
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles.iter().filter(|p| p.is_active).count(), 1);
+    }
+
+    #[test]
+    fn test_switch_profile_rejects_path_like_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+
+        assert!(db.switch_profile("../other").is_err());
+        assert!(db.switch_profile("").is_err());
+        assert_eq!(db.active_profile().unwrap(), None);
+    }
+}
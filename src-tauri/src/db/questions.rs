@@ -0,0 +1,569 @@
+//! 問題（クイズ）
+//!
+//! カードから作成した多肢選択・正誤・穴埋め入力の問題を保存する。
+//! クイズモードの学習ログは `question_id` で問題を参照し、問題ごとの
+//! 回答数・正答率・平均回答時間を学習ログから集計する。
+//!
+//! 学習ログの `question_id` は問題への外部キー（問題の行が消えたら NULL）。
+//! 学習ログと問題は別々に同期されるため、問題より先に届いた学習ログは
+//! 問題IDを `pending_question_id` に置いておき、問題が届いた時点で `question_id` に移す。
+//! 学習ログを読むときは、どちらか設定されている方を `question_id` として返す。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/questions_commands.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{schema, DbError, LocalDB, LocalQuestion, QuestionUpdate};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+const QUESTION_COLUMNS: &str = "id, card_id, user_id, question_type, prompt, options,
+    correct_answers, explanation, created_at, updated_at,
+    sync_status, synced_at, local_updated_at, server_updated_at";
+
+const LEARNING_LOG_COLUMNS: &str = "id, user_id, card_id, question_id, pending_question_id,
+    answered_at, is_correct, user_answer, practice_mode, review_interval, next_review_at,
+    quality, response_time, effort_time, attempt_count,
+    sync_status, synced_at, local_updated_at, server_updated_at";
+
+/// 問題の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionType {
+    MultipleChoice,
+    TrueFalse,
+    FillIn,
+}
+
+impl QuestionType {
+    /// `questions.question_type` に保存する値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuestionType::MultipleChoice => "multiple_choice",
+            QuestionType::TrueFalse => "true_false",
+            QuestionType::FillIn => "fill_in",
+        }
+    }
+
+    /// 保存された値から復元
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "multiple_choice" => Some(QuestionType::MultipleChoice),
+            "true_false" => Some(QuestionType::TrueFalse),
+            "fill_in" => Some(QuestionType::FillIn),
+            _ => None,
+        }
+    }
+}
+
+/// 問題ごとの成績
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestionStats {
+    pub question_id: String,
+    pub attempts: i64,
+    pub correct_count: i64,
+    /// 正答率（0〜1。回答が無い場合は 0）
+    pub accuracy: f64,
+    /// 平均回答時間（回答が無い場合は None）
+    pub average_response_time: Option<f64>,
+}
+
+/// 文字列のJSON配列を読む
+fn parse_string_list(raw: &str, field: &str) -> Result<Vec<String>, DbError> {
+    serde_json::from_str(raw).map_err(|_| {
+        DbError::ValidationError(format!(
+            "{} must be a JSON array of strings: {}",
+            field, raw
+        ))
+    })
+}
+
+/// 問題の種類に合わせて選択肢と正解を確かめる
+fn validate_question(question: &LocalQuestion) -> Result<(), DbError> {
+    let question_type = QuestionType::parse(&question.question_type).ok_or_else(|| {
+        DbError::ValidationError(format!("Unknown question type: {}", question.question_type))
+    })?;
+    if question.prompt.trim().is_empty() {
+        return Err(DbError::ValidationError(
+            "Question prompt must not be empty".to_string(),
+        ));
+    }
+    let options = parse_string_list(&question.options, "options")?;
+    let answers = parse_string_list(&question.correct_answers, "correct_answers")?;
+    if answers.is_empty() || answers.iter().any(|answer| answer.trim().is_empty()) {
+        return Err(DbError::ValidationError(
+            "At least one non-empty correct answer is required".to_string(),
+        ));
+    }
+
+    match question_type {
+        QuestionType::MultipleChoice => {
+            if options.len() < 2 {
+                return Err(DbError::ValidationError(
+                    "Multiple choice questions need at least two options".to_string(),
+                ));
+            }
+            if let Some(answer) = answers.iter().find(|answer| !options.contains(answer)) {
+                return Err(DbError::ValidationError(format!(
+                    "Correct answer is not one of the options: {}",
+                    answer
+                )));
+            }
+        }
+        QuestionType::TrueFalse => {
+            if answers.len() != 1 || !matches!(answers[0].as_str(), "true" | "false") {
+                return Err(DbError::ValidationError(
+                    "True/false questions need exactly one answer: \"true\" or \"false\""
+                        .to_string(),
+                ));
+            }
+        }
+        QuestionType::FillIn => {}
+    }
+    Ok(())
+}
+
+/// 学習ログの表を作り直して `question_id` に外部キーを付ける（v18 のマイグレーション）
+///
+/// SQLite では既存の表に外部キーを追加できないため、新しいスキーマの表に行を移す。
+/// ローカルに無い問題を参照していた `question_id` は `pending_question_id` に移す。
+pub(crate) fn rebuild_learning_logs(conn: &Connection) -> Result<(), DbError> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(
+        r#"
+        ALTER TABLE learning_logs RENAME TO learning_logs_v17;
+        DROP INDEX IF EXISTS idx_learning_logs_user;
+        DROP INDEX IF EXISTS idx_learning_logs_card;
+        DROP INDEX IF EXISTS idx_learning_logs_question;
+        DROP INDEX IF EXISTS idx_learning_logs_pending_question;
+        DROP INDEX IF EXISTS idx_learning_logs_sync_status;
+        DROP INDEX IF EXISTS idx_learning_logs_answered;
+        "#,
+    )?;
+    tx.execute_batch(schema::LEARNING_LOGS_TABLE)?;
+    tx.execute_batch(&format!(
+        r#"
+        INSERT INTO learning_logs ({})
+        SELECT id, user_id, card_id,
+               CASE WHEN question_id IN (SELECT id FROM questions) THEN question_id END,
+               COALESCE(
+                   pending_question_id,
+                   CASE WHEN question_id NOT IN (SELECT id FROM questions) THEN question_id END
+               ),
+               answered_at, is_correct, user_answer, practice_mode, review_interval,
+               next_review_at, quality, response_time, effort_time, attempt_count,
+               sync_status, synced_at, local_updated_at, server_updated_at
+        FROM learning_logs_v17;
+        DROP TABLE learning_logs_v17;
+        "#,
+        LEARNING_LOG_COLUMNS
+    ))?;
+    tx.commit()?;
+    Ok(())
+}
+
+impl LocalDB {
+    /// カードの問題を取得
+    pub fn get_questions_by_card(&self, card_id: &str) -> Result<Vec<LocalQuestion>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM questions
+             WHERE card_id = ?1 AND sync_status != 'deleted'
+             ORDER BY created_at",
+            QUESTION_COLUMNS
+        ))?;
+        let questions = stmt
+            .query_map([card_id], LocalQuestion::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(questions)
+    }
+
+    /// IDで問題を取得
+    pub fn get_question(&self, id: &str) -> Result<Option<LocalQuestion>, DbError> {
+        let conn = self.connection()?;
+        let question = conn
+            .query_row(
+                &format!("SELECT {} FROM questions WHERE id = ?1", QUESTION_COLUMNS),
+                [id],
+                LocalQuestion::from_row,
+            )
+            .optional()?;
+        Ok(question)
+    }
+
+    /// 問題を作成
+    ///
+    /// 先に届いていた学習ログがあれば、この問題を参照させる。
+    pub fn insert_question(&self, question: &LocalQuestion) -> Result<(), DbError> {
+        validate_question(question)?;
+        let mut conn = self.connection()?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            r#"
+            INSERT INTO questions (
+                id, card_id, user_id, question_type, prompt, options,
+                correct_answers, explanation, created_at, updated_at,
+                sync_status, synced_at, local_updated_at, server_updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+            "#,
+            rusqlite::params![
+                question.id,
+                question.card_id,
+                question.user_id,
+                question.question_type,
+                question.prompt,
+                question.options,
+                question.correct_answers,
+                question.explanation,
+                question.created_at,
+                question.updated_at,
+                question.sync_status,
+                question.synced_at,
+                question.local_updated_at,
+                question.server_updated_at,
+            ],
+        )?;
+        tx.execute(
+            "UPDATE learning_logs SET question_id = ?1, pending_question_id = NULL
+             WHERE pending_question_id = ?1",
+            [&question.id],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 問題を更新
+    pub fn update_question(
+        &self,
+        id: &str,
+        updates: QuestionUpdate,
+    ) -> Result<Option<LocalQuestion>, DbError> {
+        self.with_journal("update_question", &[("questions", id)], |conn| {
+            let Some(current) = self
+                .get_question(id)?
+                .filter(|question| question.sync_status != "deleted")
+            else {
+                return Ok(None);
+            };
+
+            let now = chrono::Utc::now().to_rfc3339();
+            let updated = LocalQuestion {
+                question_type: updates.question_type.unwrap_or(current.question_type),
+                prompt: updates.prompt.unwrap_or(current.prompt),
+                options: updates.options.unwrap_or(current.options),
+                correct_answers: updates.correct_answers.unwrap_or(current.correct_answers),
+                explanation: updates.explanation.unwrap_or(current.explanation),
+                updated_at: now.clone(),
+                local_updated_at: now,
+                sync_status: "pending".to_string(),
+                ..current
+            };
+            validate_question(&updated)?;
+
+            conn.execute(
+                r#"
+                UPDATE questions SET
+                    question_type = ?1, prompt = ?2, options = ?3, correct_answers = ?4,
+                    explanation = ?5, updated_at = ?6, local_updated_at = ?7, sync_status = ?8
+                WHERE id = ?9
+                "#,
+                rusqlite::params![
+                    updated.question_type,
+                    updated.prompt,
+                    updated.options,
+                    updated.correct_answers,
+                    updated.explanation,
+                    updated.updated_at,
+                    updated.local_updated_at,
+                    updated.sync_status,
+                    id,
+                ],
+            )?;
+            Ok(Some(updated))
+        })
+    }
+
+    /// 問題を削除（論理削除）
+    ///
+    /// 学習ログの参照は、同期して行を消すときに外れる（それまでは集計からだけ外れる）。
+    pub fn delete_question(&self, id: &str) -> Result<bool, DbError> {
+        self.with_journal("delete_question", &[("questions", id)], |conn| {
+            let now = chrono::Utc::now().to_rfc3339();
+            let rows_affected = conn.execute(
+                "UPDATE questions SET sync_status = 'deleted', local_updated_at = ?1
+                 WHERE id = ?2 AND sync_status != 'deleted'",
+                rusqlite::params![now, id],
+            )?;
+            Ok(rows_affected > 0)
+        })
+    }
+
+    /// カードの問題ごとの成績を取得
+    pub fn get_question_stats(&self, card_id: &str) -> Result<Vec<QuestionStats>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT q.id, COUNT(l.id), COALESCE(SUM(l.is_correct), 0), AVG(l.response_time)
+            FROM questions q
+            LEFT JOIN learning_logs l
+              ON l.question_id = q.id AND l.sync_status != 'deleted'
+            WHERE q.card_id = ?1 AND q.sync_status != 'deleted'
+            GROUP BY q.id
+            ORDER BY q.created_at
+            "#,
+        )?;
+        let stats = stmt
+            .query_map([card_id], |row| {
+                let attempts: i64 = row.get(1)?;
+                let correct_count: i64 = row.get(2)?;
+                Ok(QuestionStats {
+                    question_id: row.get(0)?,
+                    attempts,
+                    correct_count,
+                    accuracy: if attempts > 0 {
+                        correct_count as f64 / attempts as f64
+                    } else {
+                        0.0
+                    },
+                    average_response_time: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(stats)
+    }
+
+    /// 同期待ちの問題を取得（削除した問題も含む）
+    pub fn get_pending_sync_questions(&self) -> Result<Vec<LocalQuestion>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM questions WHERE sync_status IN ('pending', 'deleted')",
+            QUESTION_COLUMNS
+        ))?;
+        let questions = stmt
+            .query_map([], LocalQuestion::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(questions)
+    }
+
+    /// 問題を同期済みにマーク（削除した問題は行を消す）
+    pub fn mark_question_synced(&self, id: &str, server_updated_at: &str) -> Result<(), DbError> {
+        let conn = self.connection()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "DELETE FROM questions WHERE id = ?1 AND sync_status = 'deleted'",
+            [id],
+        )?;
+        conn.execute(
+            "UPDATE questions SET sync_status = 'synced', synced_at = ?1, server_updated_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![now, server_updated_at, id],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{fixtures, LocalLearningLog};
+    use tempfile::TempDir;
+
+    fn setup() -> (LocalDB, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        db.insert_card(&fixtures::card("card-1", "deck-1")).unwrap();
+        (db, temp_dir)
+    }
+
+    fn sample_question(
+        id: &str,
+        question_type: &str,
+        options: &str,
+        answers: &str,
+    ) -> LocalQuestion {
+        let now = chrono::Utc::now().to_rfc3339();
+        LocalQuestion {
+            id: id.to_string(),
+            card_id: "card-1".to_string(),
+            user_id: "user-1".to_string(),
+            question_type: question_type.to_string(),
+            prompt: "日本の首都は？".to_string(),
+            options: options.to_string(),
+            correct_answers: answers.to_string(),
+            explanation: None,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            sync_status: "pending".to_string(),
+            synced_at: None,
+            local_updated_at: now,
+            server_updated_at: None,
+        }
+    }
+
+    fn sample_log(
+        id: &str,
+        question_id: Option<&str>,
+        is_correct: bool,
+        response_time: i32,
+    ) -> LocalLearningLog {
+        let now = chrono::Utc::now().to_rfc3339();
+        LocalLearningLog {
+            id: id.to_string(),
+            user_id: "user-1".to_string(),
+            card_id: "card-1".to_string(),
+            question_id: question_id.map(str::to_string),
+            answered_at: now.clone(),
+            is_correct,
+            user_answer: None,
+            practice_mode: "quiz".to_string(),
+            review_interval: None,
+            next_review_at: None,
+            quality: if is_correct { 4 } else { 1 },
+            response_time,
+            effort_time: 0,
+            attempt_count: 1,
+            sync_status: "pending".to_string(),
+            synced_at: None,
+            local_updated_at: now,
+            server_updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_question_validation_and_stats() {
+        let (db, _temp_dir) = setup();
+        assert!(matches!(
+            db.insert_question(&sample_question(
+                "bad",
+                "multiple_choice",
+                r#"["東京","大阪"]"#,
+                r#"["京都"]"#
+            )),
+            Err(DbError::ValidationError(_))
+        ));
+        db.insert_question(&sample_question(
+            "q1",
+            "multiple_choice",
+            r#"["東京","大阪","京都"]"#,
+            r#"["東京"]"#,
+        ))
+        .unwrap();
+        db.insert_question(&sample_question("q2", "fill_in", "[]", r#"["東京"]"#))
+            .unwrap();
+
+        db.insert_learning_log(&sample_log("l1", Some("q1"), true, 3000))
+            .unwrap();
+        db.insert_learning_log(&sample_log("l2", Some("q1"), false, 5000))
+            .unwrap();
+        db.insert_learning_log(&sample_log("l3", Some("q1"), true, 1000))
+            .unwrap();
+        // 問題より先に届いた学習ログも記録できる
+        db.insert_learning_log(&sample_log("l4", Some("q3"), true, 0))
+            .unwrap();
+
+        let stats = db.get_question_stats("card-1").unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].question_id, "q1");
+        assert_eq!((stats[0].attempts, stats[0].correct_count), (3, 2));
+        assert!((stats[0].accuracy - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats[0].average_response_time, Some(3000.0));
+        assert_eq!(stats[1].attempts, 0);
+        assert_eq!(stats[1].average_response_time, None);
+    }
+
+    #[test]
+    fn test_learning_logs_wait_for_questions_that_arrive_later() {
+        let (db, _temp_dir) = setup();
+        // 問題より先に届いた学習ログ
+        db.insert_learning_log(&sample_log("l1", Some("q1"), true, 2000))
+            .unwrap();
+        assert_eq!(
+            db.get_learning_logs_by_card("card-1").unwrap()[0]
+                .question_id
+                .as_deref(),
+            Some("q1")
+        );
+
+        db.insert_question(&sample_question("q1", "true_false", "[]", r#"["true"]"#))
+            .unwrap();
+        let stats = db.get_question_stats("card-1").unwrap();
+        assert_eq!((stats[0].attempts, stats[0].correct_count), (1, 1));
+        let pending: Option<String> = db
+            .connection()
+            .unwrap()
+            .query_row(
+                "SELECT pending_question_id FROM learning_logs WHERE id = 'l1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(pending.is_none());
+    }
+
+    #[test]
+    fn test_rebuild_learning_logs_adds_question_foreign_key() {
+        let (db, _temp_dir) = setup();
+        db.insert_question(&sample_question("q1", "true_false", "[]", r#"["true"]"#))
+            .unwrap();
+        db.insert_learning_log(&sample_log("l1", Some("q1"), true, 0))
+            .unwrap();
+
+        {
+            let conn = db.connection().unwrap();
+            // v17 までの外部キーの無い表を再現する
+            conn.execute_batch(
+                r#"
+                PRAGMA foreign_keys=OFF;
+                CREATE TABLE legacy AS
+                SELECT id, user_id, card_id, question_id, answered_at, is_correct,
+                       user_answer, practice_mode, review_interval, next_review_at,
+                       quality, response_time, effort_time, attempt_count,
+                       sync_status, synced_at, local_updated_at, server_updated_at
+                FROM learning_logs;
+                DROP TABLE learning_logs;
+                ALTER TABLE legacy RENAME TO learning_logs;
+                INSERT INTO learning_logs
+                SELECT 'l2', user_id, card_id, 'server-only', answered_at, is_correct,
+                       user_answer, practice_mode, review_interval, next_review_at,
+                       quality, response_time, effort_time, attempt_count,
+                       sync_status, synced_at, local_updated_at, server_updated_at
+                FROM learning_logs WHERE id = 'l1';
+                ALTER TABLE learning_logs ADD COLUMN pending_question_id TEXT;
+                PRAGMA foreign_keys=ON;
+                "#,
+            )
+            .unwrap();
+            rebuild_learning_logs(&conn).unwrap();
+            assert_eq!(
+                conn.query_row("PRAGMA foreign_key_check(learning_logs)", [], |_| Ok(()))
+                    .optional()
+                    .unwrap(),
+                None
+            );
+        }
+
+        let question_of = |id: &str| {
+            db.get_learning_logs_by_card("card-1")
+                .unwrap()
+                .into_iter()
+                .find(|log| log.id == id)
+                .and_then(|log| log.question_id)
+        };
+        assert_eq!(question_of("l1"), Some("q1".to_string()));
+        // ローカルに無い問題は届くまで待つ
+        assert_eq!(question_of("l2"), Some("server-only".to_string()));
+
+        // 削除を同期して問題の行が消えると参照も外れる
+        db.mark_question_synced("q1", "2025-01-01T00:00:00Z")
+            .unwrap();
+        db.delete_question("q1").unwrap();
+        db.mark_question_synced("q1", "2025-01-02T00:00:00Z")
+            .unwrap();
+        assert_eq!(question_of("l1"), None);
+    }
+}
@@ -0,0 +1,516 @@
+//! 多肢選択クイズの生成とタイピング回答の採点
+//!
+//! カードの裏面を正解とし、同じデッキの他のカードの裏面から誤答（ダミーの選択肢）を選ぶ。
+//! 誤答は正解と文字数・文字種（ひらがな・カタカナ・漢字・ラテン文字など）・
+//! 文字の並び（バイグラム）が近いものを優先し、正解と同じもの・重複は除く。
+//! 同じカードノートの兄弟カードは正解そのものや手掛かりになるため候補にしない。
+//!
+//! 選択肢の選び方と並び順はシードで決まり、同じシードなら同じ問題になる。
+//!
+//! タイピングの採点では裏面（穴埋めカードは対象の番号の答え）を正解として
+//! `content::grading` で採点する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/questions_commands.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/content/plain_text.rs
+//!   ├─ src-tauri/src/content/cloze.rs
+//!   └─ src-tauri/src/content/grading.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{DbError, LocalCard, LocalDB};
+use crate::content::{
+    grade_answer, parse_cloze, split_accepted_answers, tiptap_to_plain_text, AnswerGrade,
+    ClozeSegment, GradeOptions, TiptapNode,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use unicode_normalization::UnicodeNormalization;
+
+/// 既定の選択肢の数（正解を含む）
+pub const DEFAULT_CHOICE_COUNT: usize = 4;
+
+/// 文字数・文字種・文字の並びの重み
+const LENGTH_WEIGHT: f64 = 0.35;
+const SCRIPT_WEIGHT: f64 = 0.35;
+const LEXICAL_WEIGHT: f64 = 0.3;
+/// 似た候補の中で順位を入れ替えるための揺らぎの幅
+const JITTER: f64 = 0.05;
+
+/// 選択肢
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuizChoice {
+    /// 選択肢の裏面を持つカード
+    pub card_id: String,
+    pub text: String,
+    pub is_correct: bool,
+}
+
+/// 表示できる多肢選択の問題
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuizQuestion {
+    pub card_id: String,
+    /// 表面のテキスト
+    pub prompt: String,
+    pub choices: Vec<QuizChoice>,
+    /// 正解の選択肢の位置
+    pub correct_index: usize,
+    pub seed: u64,
+}
+
+/// 誤答の候補
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistractorCandidate {
+    pub card_id: String,
+    pub text: String,
+}
+
+/// シード付きの乱数（SplitMix64）
+struct SeededRng(u64);
+
+impl SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// 0 以上 1 未満
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// 並びをシャッフルする（Fisher-Yates）
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// 文字種
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Script {
+    Hiragana,
+    Katakana,
+    Han,
+    Hangul,
+    Latin,
+    Digit,
+    Other,
+}
+
+/// 文字の文字種（空白・記号は None）
+fn script_of(c: char) -> Option<Script> {
+    match c {
+        c if c.is_whitespace() || c.is_ascii_punctuation() => None,
+        '\u{3041}'..='\u{309F}' => Some(Script::Hiragana),
+        '\u{30A0}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' => Some(Script::Katakana),
+        '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' => {
+            Some(Script::Han)
+        }
+        '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' => Some(Script::Hangul),
+        c if c.is_ascii_digit() => Some(Script::Digit),
+        c if c.is_alphabetic() && (c.is_ascii() || ('\u{00C0}'..='\u{024F}').contains(&c)) => {
+            Some(Script::Latin)
+        }
+        c if c.is_alphanumeric() => Some(Script::Other),
+        _ => None,
+    }
+}
+
+/// 重複の判定に使う形（NFKC・小文字・空白の正規化）
+fn normalize_choice(text: &str) -> String {
+    text.nfkc()
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 文字種ごとの割合
+fn script_profile(text: &str) -> HashMap<Script, f64> {
+    let mut counts: HashMap<Script, f64> = HashMap::new();
+    let mut total = 0.0;
+    for script in text.chars().filter_map(script_of) {
+        *counts.entry(script).or_default() += 1.0;
+        total += 1.0;
+    }
+    for count in counts.values_mut() {
+        *count /= total;
+    }
+    counts
+}
+
+/// 文字のバイグラム（1文字の場合はその文字）
+fn bigrams(text: &str) -> HashSet<(char, char)> {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    match chars.len() {
+        0 => HashSet::new(),
+        1 => HashSet::from([(chars[0], chars[0])]),
+        _ => chars.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+    }
+}
+
+/// 正解との近さ（0〜1）
+fn similarity(answer: &str, candidate: &str) -> f64 {
+    let (answer_len, candidate_len) = (answer.chars().count(), candidate.chars().count());
+    let length = if answer_len.max(candidate_len) == 0 {
+        1.0
+    } else {
+        answer_len.min(candidate_len) as f64 / answer_len.max(candidate_len) as f64
+    };
+
+    let answer_scripts = script_profile(answer);
+    let candidate_scripts = script_profile(candidate);
+    let script: f64 = answer_scripts
+        .iter()
+        .map(|(script, share)| share.min(*candidate_scripts.get(script).unwrap_or(&0.0)))
+        .sum();
+
+    let (answer_bigrams, candidate_bigrams) = (bigrams(answer), bigrams(candidate));
+    let lexical = if answer_bigrams.is_empty() && candidate_bigrams.is_empty() {
+        0.0
+    } else {
+        2.0 * answer_bigrams.intersection(&candidate_bigrams).count() as f64
+            / (answer_bigrams.len() + candidate_bigrams.len()) as f64
+    };
+
+    LENGTH_WEIGHT * length + SCRIPT_WEIGHT * script + LEXICAL_WEIGHT * lexical
+}
+
+/// 候補から正解に近い誤答を選ぶ（近い順）
+///
+/// `answers` は正解（先頭が選択肢に表示する正解）。近さは先頭の正解と比べる。
+/// 空の候補・いずれかの正解と同じ候補・重複した候補は除く。
+pub fn pick_distractors(
+    answers: &[String],
+    candidates: &[DistractorCandidate],
+    count: usize,
+    seed: u64,
+) -> Vec<DistractorCandidate> {
+    let mut rng = SeededRng(seed);
+    let answer_key = answers
+        .first()
+        .map(|answer| normalize_choice(answer))
+        .unwrap_or_default();
+    let mut seen: HashSet<String> = answers
+        .iter()
+        .map(|answer| normalize_choice(answer))
+        .collect();
+    let mut scored: Vec<(f64, &DistractorCandidate)> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            let key = normalize_choice(&candidate.text);
+            (!key.is_empty() && seen.insert(key.clone())).then_some((key, candidate))
+        })
+        .map(|(key, candidate)| {
+            let score = similarity(&answer_key, &key) + rng.next_f64() * JITTER;
+            (score, candidate)
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored
+        .into_iter()
+        .take(count)
+        .map(|(_, candidate)| candidate.clone())
+        .collect()
+}
+
+/// カードの内容（Tiptap JSON）をテキストにする
+fn card_text(content: &str) -> String {
+    match serde_json::from_str::<TiptapNode>(content) {
+        Ok(doc) => tiptap_to_plain_text(&doc),
+        Err(_) => content.to_string(),
+    }
+    .trim()
+    .to_string()
+}
+
+impl LocalDB {
+    /// カードから多肢選択の問題を作る
+    ///
+    /// `choice_count` は正解を含む選択肢の数。同じデッキに誤答の候補が足りない場合は
+    /// 選択肢が少なくなり、1つも無い場合はエラーにする。
+    ///
+    /// 正解は `accepted_answers` の先頭（穴埋めカードは対象の番号の答え）。
+    /// 裏面に複数の正解を書いたカードは先頭を選択肢にし、別の正解のどれかが
+    /// このカードの正解と同じものは誤答にしない。
+    pub fn generate_quiz_question(
+        &self,
+        card_id: &str,
+        choice_count: usize,
+        seed: u64,
+    ) -> Result<QuizQuestion, DbError> {
+        let card = self
+            .get_card_by_id(card_id)?
+            .filter(|card| card.sync_status != "deleted")
+            .ok_or_else(|| DbError::NotFound(format!("Card {}", card_id)))?;
+        let answers = self.accepted_answers(&card)?;
+        let Some(answer) = answers.first().cloned() else {
+            return Err(DbError::ValidationError(format!(
+                "Card {} has no answer on its back",
+                card_id
+            )));
+        };
+        let answer_keys: HashSet<String> = answers
+            .iter()
+            .map(|answer| normalize_choice(answer))
+            .collect();
+
+        let is_sibling = |other: &LocalCard| {
+            card.card_note_id.is_some() && other.card_note_id == card.card_note_id
+        };
+        let candidates: Vec<DistractorCandidate> = self
+            .get_cards_by_deck(&card.deck_id)?
+            .into_iter()
+            .filter(|other| other.id != card.id && !is_sibling(other))
+            .filter_map(|other| {
+                let alternatives = split_accepted_answers(&card_text(&other.back_content));
+                if alternatives
+                    .iter()
+                    .any(|alternative| answer_keys.contains(&normalize_choice(alternative)))
+                {
+                    return None;
+                }
+                Some(DistractorCandidate {
+                    text: alternatives.into_iter().next()?,
+                    card_id: other.id,
+                })
+            })
+            .collect();
+        let distractors =
+            pick_distractors(&answers, &candidates, choice_count.saturating_sub(1), seed);
+        if distractors.is_empty() {
+            return Err(DbError::ValidationError(format!(
+                "Not enough cards in deck {} to build a multiple-choice question",
+                card.deck_id
+            )));
+        }
+
+        let mut choices: Vec<QuizChoice> = distractors
+            .into_iter()
+            .map(|distractor| QuizChoice {
+                card_id: distractor.card_id,
+                text: distractor.text,
+                is_correct: false,
+            })
+            .collect();
+        choices.push(QuizChoice {
+            card_id: card.id.clone(),
+            text: answer,
+            is_correct: true,
+        });
+        // 誤答の選択とは別の系列で並べ替える
+        SeededRng(seed ^ 0xA5A5_A5A5_A5A5_A5A5).shuffle(&mut choices);
+        let correct_index = choices
+            .iter()
+            .position(|choice| choice.is_correct)
+            .expect("the correct answer is always included");
+
+        Ok(QuizQuestion {
+            card_id: card.id,
+            prompt: card_text(&card.front_content),
+            choices,
+            correct_index,
+            seed,
+        })
+    }
+
+    /// カードの正解（複数可）
+    ///
+    /// 穴埋めカードはカードノートの対象の番号の答え、それ以外は裏面のテキスト。
+    fn accepted_answers(&self, card: &LocalCard) -> Result<Vec<String>, DbError> {
+        if card.card_kind == "cloze" {
+            if let (Some(note_id), Some(ordinal)) = (&card.card_note_id, card.ordinal) {
+                if let Some(note) = self.get_card_note(note_id)? {
+                    return Ok(parse_cloze(&note.source_text)
+                        .into_iter()
+                        .filter_map(|segment| match segment {
+                            ClozeSegment::Cloze(deletion)
+                                if i64::from(deletion.ordinal) == i64::from(ordinal) =>
+                            {
+                                Some(deletion.answer)
+                            }
+                            _ => None,
+                        })
+                        .flat_map(|answer| split_accepted_answers(&answer))
+                        .collect());
+                }
+            }
+        }
+        Ok(split_accepted_answers(&card_text(&card.back_content)))
+    }
+
+    /// タイピングの回答を採点する
+    pub fn grade_typing_answer(
+        &self,
+        card_id: &str,
+        user_answer: &str,
+        options: &GradeOptions,
+    ) -> Result<AnswerGrade, DbError> {
+        let card = self
+            .get_card_by_id(card_id)?
+            .filter(|card| card.sync_status != "deleted")
+            .ok_or_else(|| DbError::NotFound(format!("Card {}", card_id)))?;
+        let accepted = self.accepted_answers(&card)?;
+        if accepted.is_empty() {
+            return Err(DbError::ValidationError(format!(
+                "Card {} has no answer on its back",
+                card_id
+            )));
+        }
+        Ok(grade_answer(user_answer, &accepted, options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::plain_text_to_tiptap;
+    use crate::db::fixtures;
+    use tempfile::TempDir;
+
+    fn candidate(card_id: &str, text: &str) -> DistractorCandidate {
+        DistractorCandidate {
+            card_id: card_id.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_pick_distractors_prefers_similar_and_skips_duplicates() {
+        let candidates = vec![
+            candidate("c1", "Osaka"),
+            candidate("c2", "東京"),
+            candidate("c3", "ＴＯＫＹＯ"),
+            candidate("c4", "Kyoto"),
+            candidate("c5", "osaka"),
+            candidate("c6", "The capital city of France is Paris"),
+            candidate("c7", ""),
+        ];
+
+        let tokyo = vec!["Tokyo".to_string()];
+        let picked = pick_distractors(&tokyo, &candidates, 3, 7);
+        let ids: Vec<_> = picked.iter().map(|c| c.card_id.as_str()).collect();
+        assert_eq!(ids, vec!["c4", "c1", "c6"]);
+        assert_eq!(pick_distractors(&tokyo, &candidates, 3, 7), picked);
+
+        // 漢字の答えには漢字の候補を優先する
+        let picked = pick_distractors(&["京都".to_string()], &candidates, 1, 7);
+        assert_eq!(picked[0].card_id, "c2");
+
+        // 別の正解と同じ候補も除く
+        let answers = vec!["Tokyo".to_string(), "東京".to_string()];
+        let picked = pick_distractors(&answers, &candidates, 6, 7);
+        assert!(picked
+            .iter()
+            .all(|c| c.card_id != "c2" && c.card_id != "c3"));
+    }
+
+    #[test]
+    fn test_generate_quiz_question_is_deterministic_for_seed() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        for (id, front, back) in [
+            ("japan", "日本の首都", "東京"),
+            ("france", "フランスの首都", "パリ"),
+            ("uk", "イギリスの首都", "ロンドン"),
+            ("china", "中国の首都", "北京"),
+            ("korea", "韓国の首都", "ソウル"),
+        ] {
+            db.insert_card(&LocalCard {
+                front_content: serde_json::to_string(&plain_text_to_tiptap(front)).unwrap(),
+                back_content: serde_json::to_string(&plain_text_to_tiptap(back)).unwrap(),
+                ..fixtures::card(id, "deck-1")
+            })
+            .unwrap();
+        }
+
+        let question = db.generate_quiz_question("japan", 4, 42).unwrap();
+        assert_eq!(question.prompt, "日本の首都");
+        assert_eq!(question.choices.len(), 4);
+        assert_eq!(question.choices[question.correct_index].text, "東京");
+        assert_eq!(question.choices.iter().filter(|c| c.is_correct).count(), 1);
+        // 漢字の答えには漢字の誤答を優先する
+        assert!(question.choices.iter().any(|c| c.text == "北京"));
+        assert_eq!(db.generate_quiz_question("japan", 4, 42).unwrap(), question);
+    }
+
+    #[test]
+    fn test_generate_quiz_question_uses_cloze_answer_and_skips_alternatives() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        for (id, back) in [
+            ("osaka", "大阪"),
+            ("reading", "とうきょう"),
+            ("kyoto", "京都；きょうと"),
+        ] {
+            db.insert_card(&LocalCard {
+                front_content: serde_json::to_string(&plain_text_to_tiptap(id)).unwrap(),
+                back_content: serde_json::to_string(&plain_text_to_tiptap(back)).unwrap(),
+                ..fixtures::card(id, "deck-1")
+            })
+            .unwrap();
+        }
+        let created = db
+            .create_card_note(
+                "deck-1",
+                "user-1",
+                crate::db::CardNoteKind::Cloze,
+                "{{c1::東京;とうきょう}}は{{c2::日本}}の首都",
+                None,
+                None,
+            )
+            .unwrap();
+        let first = created.cards.iter().find(|c| c.ordinal == Some(1)).unwrap();
+
+        let question = db.generate_quiz_question(&first.id, 4, 42).unwrap();
+        assert_eq!(question.choices[question.correct_index].text, "東京");
+        // 別の正解（とうきょう）と兄弟カードの答え（日本）は誤答にしない
+        let mut texts: Vec<_> = question.choices.iter().map(|c| c.text.as_str()).collect();
+        texts.sort();
+        assert_eq!(texts, vec!["京都", "大阪", "東京"]);
+    }
+
+    #[test]
+    fn test_grade_typing_answer_uses_cloze_answer_for_ordinal() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        let created = db
+            .create_card_note(
+                "deck-1",
+                "user-1",
+                crate::db::CardNoteKind::Cloze,
+                "{{c1::東京;とうきょう}}は{{c2::日本}}の首都",
+                None,
+                None,
+            )
+            .unwrap();
+        let first = created.cards.iter().find(|c| c.ordinal == Some(1)).unwrap();
+
+        let options = GradeOptions::default();
+        let grade = db
+            .grade_typing_answer(&first.id, "とうきょう", &options)
+            .unwrap();
+        assert_eq!(
+            (grade.quality, grade.expected.as_deref()),
+            (5, Some("とうきょう"))
+        );
+        // 他の番号の答えや文全体は正解にしない
+        let grade = db.grade_typing_answer(&first.id, "日本", &options).unwrap();
+        assert!(!grade.is_correct);
+    }
+}
@@ -0,0 +1,514 @@
+//! 復習のスケジュール
+//!
+//! SM-2（`lib/utils/sm2.ts` と同じ計算）を基に、デッキの学習オプションに従って
+//! 次回の復習日時を決める。
+//!
+//! - 新規カードと失敗したカードは学習ステップ（分）に沿って短い間隔で出題する
+//! - 学習ステップを終えたカードは SM-2 の間隔（1日 → 6日 → 間隔 × EF）にする
+//! - 目標保持率が 0.9 以外の場合は間隔を伸縮し、最大間隔で頭打ちにする
+//!
+//! 1日の新規カード数・最大復習数は学習ログから今日の学習数を数えて適用する。
+//! 学習オプションで兄弟カードを延期する場合、同じカードノートのカードは1日に1枚だけ出題する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   ├─ src-tauri/src/db/deck_tree.rs
+//!   └─ src-tauri/src/commands/cards_commands.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/deck_configs.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::deck_configs::{deck_study_options, DeckStudyOptions};
+use super::deck_tree::subtree_deck_ids;
+use super::{find_card, DbError, LocalCard, LocalDB, LocalLearningLog};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// 目標保持率の基準（SM-2 の間隔はこの保持率を想定する）
+const BASE_RETENTION: f64 = 0.9;
+
+/// 次回の復習のスケジュール
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardSchedule {
+    pub ease_factor: f64,
+    pub repetition_count: i32,
+    /// 次回までの間隔（日）。学習ステップ中は 0
+    pub review_interval: i32,
+    pub next_review_at: String,
+}
+
+/// 復習の結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewResult {
+    pub card: LocalCard,
+    pub log: LocalLearningLog,
+}
+
+/// 回答の品質（0〜5）から次回の復習を決める
+pub fn schedule_review(
+    card: &LocalCard,
+    quality: i32,
+    options: &DeckStudyOptions,
+    now: DateTime<Utc>,
+) -> CardSchedule {
+    let q = f64::from(quality.clamp(0, 5));
+    let ease_factor = (card.ease_factor + (0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02))).max(1.3);
+    let steps = &options.learning_steps;
+
+    let repetition_count = if quality < 3 {
+        0
+    } else {
+        card.repetition_count + 1
+    };
+
+    // 学習ステップ中（失敗した直後、または新規カードのステップの途中）
+    let step = if quality < 3 {
+        steps.first()
+    } else {
+        steps.get(repetition_count as usize)
+    };
+    if let Some(minutes) = step {
+        return CardSchedule {
+            ease_factor,
+            repetition_count,
+            review_interval: 0,
+            next_review_at: (now + Duration::minutes(i64::from(*minutes))).to_rfc3339(),
+        };
+    }
+
+    // 学習ステップを終えた後の回数で SM-2 の間隔を決める
+    let graduated = if quality < 3 {
+        0
+    } else {
+        repetition_count - (steps.len() as i32).max(1)
+    };
+    let interval = match graduated {
+        0 => 1.0,
+        1 => 6.0,
+        _ => (f64::from(card.review_interval.max(1)) * ease_factor).ceil(),
+    };
+    let scale = options.desired_retention.ln() / BASE_RETENTION.ln();
+    let review_interval =
+        ((interval * scale).round() as i32).clamp(1, options.maximum_interval.max(1));
+
+    CardSchedule {
+        ease_factor,
+        repetition_count,
+        review_interval,
+        next_review_at: (now + Duration::days(i64::from(review_interval))).to_rfc3339(),
+    }
+}
+
+/// 今日から `days` 日後の始まり（ローカル時刻の0時）
+fn local_midnight(days: i64) -> Option<DateTime<Utc>> {
+    (chrono::Local::now().date_naive() + Duration::days(days))
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(chrono::Local).earliest())
+        .map(|midnight| midnight.with_timezone(&Utc))
+}
+
+/// 今日の始まり（ローカル時刻の0時）をUTCで表す
+pub(crate) fn day_start() -> String {
+    local_midnight(0)
+        .unwrap_or_else(|| Utc::now() - Duration::days(1))
+        .to_rfc3339()
+}
+
+/// 次の学習日の始まり（明日のローカル時刻の0時）をUTCで表す
+pub(crate) fn next_day_start() -> String {
+    local_midnight(1)
+        .unwrap_or_else(|| Utc::now() + Duration::days(1))
+        .to_rfc3339()
+}
+
+/// 学習ステップ中のカード（1日の上限の対象外）
+fn is_learning(card: &LocalCard) -> bool {
+    card.last_reviewed_at.is_some() && card.review_interval == 0
+}
+
+/// 今日学習した新規カード数と復習数
+fn studied_today(
+    conn: &Connection,
+    deck_ids: &[String],
+    day_start: &str,
+) -> Result<(i64, i64), DbError> {
+    if deck_ids.is_empty() {
+        return Ok((0, 0));
+    }
+    let placeholders = vec!["?"; deck_ids.len()].join(", ");
+    let (new, total): (i64, i64) = conn.query_row(
+        &format!(
+            r#"
+            SELECT
+                COUNT(DISTINCT CASE WHEN NOT EXISTS (
+                    SELECT 1 FROM learning_logs p
+                    WHERE p.card_id = l.card_id AND p.answered_at < ?1
+                ) THEN l.card_id END),
+                COUNT(DISTINCT l.card_id)
+            FROM learning_logs l
+            JOIN cards c ON c.id = l.card_id
+            WHERE l.answered_at >= ?1 AND c.deck_id IN ({})
+            "#,
+            placeholders
+        ),
+        rusqlite::params_from_iter(
+            std::iter::once(day_start).chain(deck_ids.iter().map(String::as_str)),
+        ),
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    Ok((new, total - new))
+}
+
+/// 今日あと何枚学習できるか
+#[derive(Debug, Clone, Copy)]
+struct Remaining {
+    new: i64,
+    reviews: i64,
+}
+
+impl Remaining {
+    fn new(options: &DeckStudyOptions, (new, reviews): (i64, i64)) -> Self {
+        Self {
+            new: (i64::from(options.new_cards_per_day) - new).max(0),
+            reviews: (i64::from(options.max_reviews_per_day) - reviews).max(0),
+        }
+    }
+
+    fn has_room(&self, card: &LocalCard) -> bool {
+        if card.last_reviewed_at.is_none() {
+            self.new > 0
+        } else {
+            self.reviews > 0
+        }
+    }
+
+    fn take(&mut self, card: &LocalCard) {
+        if card.last_reviewed_at.is_none() {
+            self.new -= 1;
+        } else {
+            self.reviews -= 1;
+        }
+    }
+}
+
+/// 兄弟カード（同じカードノートから生成したカード）を翌日に延期する
+///
+/// 学習オプションで延期する場合、今日すでに兄弟カードを復習したカードと、
+/// 一覧の中で同じカードノートの2枚目以降のカードを除く。
+pub(crate) fn bury_siblings(
+    conn: &Connection,
+    cards: Vec<LocalCard>,
+) -> Result<Vec<LocalCard>, DbError> {
+    let day_start = day_start();
+    let mut bury_by_deck: HashMap<String, bool> = HashMap::new();
+    let mut reviewed_by_note: HashMap<String, Vec<String>> = HashMap::new();
+    let mut shown_notes = HashSet::new();
+    let mut kept = Vec::with_capacity(cards.len());
+    for card in cards {
+        let Some(note_id) = card.card_note_id.clone() else {
+            kept.push(card);
+            continue;
+        };
+        if !bury_by_deck.contains_key(&card.deck_id) {
+            let options = deck_study_options(conn, &card.deck_id)?;
+            bury_by_deck.insert(card.deck_id.clone(), options.bury_siblings);
+        }
+        if !bury_by_deck[&card.deck_id] {
+            kept.push(card);
+            continue;
+        }
+        if !reviewed_by_note.contains_key(&note_id) {
+            let mut stmt = conn.prepare_cached(
+                "SELECT id FROM cards
+                 WHERE card_note_id = ?1 AND sync_status != 'deleted' AND last_reviewed_at >= ?2",
+            )?;
+            let reviewed = stmt
+                .query_map([&note_id, &day_start], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+            reviewed_by_note.insert(note_id.clone(), reviewed);
+        }
+        let sibling_reviewed = reviewed_by_note[&note_id]
+            .iter()
+            .any(|reviewed_id| *reviewed_id != card.id);
+        if sibling_reviewed || !shown_notes.insert(note_id) {
+            continue;
+        }
+        kept.push(card);
+    }
+    Ok(kept)
+}
+
+/// デッキの1日の新規カード数・最大復習数に収まるカードだけを残す
+///
+/// `top_deck_id` を指定した場合（親デッキの学習）は、そのデッキの上限も子孫を合わせて適用する。
+/// 学習ステップ中のカードは上限の対象外。
+pub(crate) fn apply_daily_limits(
+    conn: &Connection,
+    cards: Vec<LocalCard>,
+    top_deck_id: Option<&str>,
+) -> Result<Vec<LocalCard>, DbError> {
+    let day_start = day_start();
+    let mut top = match top_deck_id {
+        Some(deck_id) => {
+            let options = deck_study_options(conn, deck_id)?;
+            let deck_ids = subtree_deck_ids(conn, deck_id)?;
+            Some(Remaining::new(
+                &options,
+                studied_today(conn, &deck_ids, &day_start)?,
+            ))
+        }
+        None => None,
+    };
+
+    let mut remaining: HashMap<String, Remaining> = HashMap::new();
+    let mut limited = Vec::with_capacity(cards.len());
+    for card in cards {
+        if is_learning(&card) {
+            limited.push(card);
+            continue;
+        }
+        let deck = match remaining.entry(card.deck_id.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let options = deck_study_options(conn, &card.deck_id)?;
+                let studied = studied_today(conn, std::slice::from_ref(&card.deck_id), &day_start)?;
+                entry.insert(Remaining::new(&options, studied))
+            }
+        };
+        if !deck.has_room(&card) || top.as_ref().is_some_and(|top| !top.has_room(&card)) {
+            continue;
+        }
+        deck.take(&card);
+        if let Some(top) = top.as_mut() {
+            top.take(&card);
+        }
+        limited.push(card);
+    }
+    Ok(limited)
+}
+
+impl LocalDB {
+    /// カードを復習する
+    ///
+    /// デッキの学習オプションに従って次回の復習を決め、学習ログを残す。
+    pub fn review_card(
+        &self,
+        id: &str,
+        quality: i32,
+        practice_mode: &str,
+    ) -> Result<ReviewResult, DbError> {
+        if !(0..=5).contains(&quality) {
+            return Err(DbError::ValidationError(format!(
+                "quality must be between 0 and 5: {}",
+                quality
+            )));
+        }
+        let log_id = uuid::Uuid::new_v4().to_string();
+
+        self.with_journal(
+            "review_card",
+            &[("cards", id), ("learning_logs", &log_id)],
+            |tx| {
+                let card = find_card(tx, id)?
+                    .filter(|card| card.sync_status != "deleted")
+                    .ok_or_else(|| DbError::NotFound(format!("Card {}", id)))?;
+                let now = Utc::now();
+                let now_str = now.to_rfc3339();
+                let options = deck_study_options(tx, &card.deck_id)?;
+                let schedule = schedule_review(&card, quality, &options, now);
+
+                tx.execute(
+                    r#"
+                    UPDATE cards SET
+                        ease_factor = ?1, repetition_count = ?2, review_interval = ?3,
+                        next_review_at = ?4, last_reviewed_at = ?5,
+                        updated_at = ?5, local_updated_at = ?5, sync_status = 'pending'
+                    WHERE id = ?6
+                    "#,
+                    rusqlite::params![
+                        schedule.ease_factor,
+                        schedule.repetition_count,
+                        schedule.review_interval,
+                        schedule.next_review_at,
+                        now_str,
+                        id,
+                    ],
+                )?;
+
+                let log = LocalLearningLog {
+                    id: log_id.clone(),
+                    user_id: card.user_id.clone(),
+                    card_id: id.to_string(),
+                    question_id: None,
+                    answered_at: now_str.clone(),
+                    is_correct: quality >= 3,
+                    user_answer: None,
+                    practice_mode: practice_mode.to_string(),
+                    review_interval: Some(schedule.review_interval),
+                    next_review_at: Some(schedule.next_review_at.clone()),
+                    quality,
+                    response_time: 0,
+                    effort_time: 0,
+                    attempt_count: 1,
+                    sync_status: "pending".to_string(),
+                    synced_at: None,
+                    local_updated_at: now_str.clone(),
+                    server_updated_at: None,
+                };
+                tx.execute(
+                    r#"
+                    INSERT INTO learning_logs (
+                        id, user_id, card_id, answered_at, is_correct, practice_mode,
+                        review_interval, next_review_at, quality, response_time, effort_time,
+                        attempt_count, sync_status, local_updated_at
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 0, 0, 1, 'pending', ?4)
+                    "#,
+                    rusqlite::params![
+                        log.id,
+                        log.user_id,
+                        log.card_id,
+                        log.answered_at,
+                        log.is_correct,
+                        log.practice_mode,
+                        log.review_interval,
+                        log.next_review_at,
+                        log.quality,
+                    ],
+                )?;
+
+                Ok(ReviewResult {
+                    card: LocalCard {
+                        ease_factor: schedule.ease_factor,
+                        repetition_count: schedule.repetition_count,
+                        review_interval: schedule.review_interval,
+                        next_review_at: Some(schedule.next_review_at),
+                        last_reviewed_at: Some(now_str.clone()),
+                        updated_at: now_str.clone(),
+                        local_updated_at: now_str,
+                        sync_status: "pending".to_string(),
+                        ..card
+                    },
+                    log,
+                })
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{fixtures, LocalDeckConfig};
+    use tempfile::TempDir;
+
+    fn sample_config(id: &str, new_cards_per_day: i32) -> LocalDeckConfig {
+        let now = Utc::now().to_rfc3339();
+        LocalDeckConfig {
+            id: id.to_string(),
+            user_id: "user-1".to_string(),
+            name: id.to_string(),
+            new_cards_per_day,
+            max_reviews_per_day: 200,
+            learning_steps: "[]".to_string(),
+            desired_retention: 0.9,
+            maximum_interval: 30,
+            bury_siblings: false,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            sync_status: "pending".to_string(),
+            synced_at: None,
+            local_updated_at: now,
+            server_updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_schedule_review_with_learning_steps() {
+        let now = Utc::now();
+        let options = DeckStudyOptions::default();
+        let mut card = fixtures::card("c1", "deck-1");
+
+        // 新規カード: 1回目は次のステップ（10分）、2回目で卒業（1日）、3回目は6日
+        let first = schedule_review(&card, 4, &options, now);
+        assert_eq!((first.repetition_count, first.review_interval), (1, 0));
+        card.repetition_count = first.repetition_count;
+        let second = schedule_review(&card, 4, &options, now);
+        assert_eq!(second.review_interval, 1);
+        card.repetition_count = second.repetition_count;
+        card.review_interval = second.review_interval;
+        assert_eq!(schedule_review(&card, 4, &options, now).review_interval, 6);
+
+        // 失敗すると最初のステップに戻る
+        let failed = schedule_review(&card, 1, &options, now);
+        assert_eq!((failed.repetition_count, failed.review_interval), (0, 0));
+
+        // 最大間隔で頭打ちにし、目標保持率が高いほど間隔を短くする
+        card.repetition_count = 10;
+        card.review_interval = 100;
+        let capped = DeckStudyOptions {
+            maximum_interval: 30,
+            ..DeckStudyOptions::default()
+        };
+        assert_eq!(schedule_review(&card, 5, &capped, now).review_interval, 30);
+        let strict = DeckStudyOptions {
+            desired_retention: 0.95,
+            ..DeckStudyOptions::default()
+        };
+        assert!(
+            schedule_review(&card, 5, &strict, now).review_interval
+                < schedule_review(&card, 5, &options, now).review_interval
+        );
+    }
+
+    #[test]
+    fn test_deck_config_limits_study_cards() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+        for id in ["c1", "c2", "c3"] {
+            db.insert_card(&fixtures::card(id, "deck-1")).unwrap();
+        }
+        assert_eq!(db.get_study_cards("deck-1").unwrap().len(), 3);
+
+        db.insert_deck_config(&sample_config("two-a-day", 2))
+            .unwrap();
+        db.set_deck_config("deck-1", Some("two-a-day")).unwrap();
+        assert_eq!(
+            db.get_deck_study_options("deck-1")
+                .unwrap()
+                .new_cards_per_day,
+            2
+        );
+        assert_eq!(db.get_study_cards("deck-1").unwrap().len(), 2);
+
+        // 学習ステップが無いので1回で卒業し、今日の新規カードの枠を1つ使う
+        let result = db.review_card("c1", 4, "flashcard").unwrap();
+        assert_eq!(result.card.review_interval, 1);
+        assert!(result.log.is_correct);
+        assert_eq!(db.get_study_cards("deck-1").unwrap().len(), 1);
+
+        // プリセットを削除すると既定値に戻る
+        db.delete_deck_config("two-a-day").unwrap();
+        assert_eq!(
+            db.get_deck_study_options("deck-1").unwrap(),
+            DeckStudyOptions::default()
+        );
+        assert!(db.get_pending_sync_deck_configs().unwrap().is_empty());
+        let deleted = db.get_deleted_deck_configs().unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].id, "two-a-day");
+        assert!(db
+            .insert_deck_config(&LocalDeckConfig {
+                learning_steps: "[0]".to_string(),
+                ..sample_config("invalid", 10)
+            })
+            .is_err());
+    }
+}
@@ -11,7 +11,7 @@
 //! Issue: https://github.com/otomatty/for-all-learners/issues/191
 
 /// データベースバージョン
-pub const DB_VERSION: i32 = 1;
+pub const DB_VERSION: i32 = 18;
 
 /// Notes テーブルスキーマ
 pub const NOTES_TABLE: &str = r#"
@@ -42,6 +42,7 @@ CREATE UNIQUE INDEX IF NOT EXISTS idx_notes_owner_slug ON notes(owner_id, slug);
 
 /// Pages テーブルスキーマ
 /// 注意: content_tiptap はリアルタイム同期（Yjs）のため、ここには含めない
+/// （本文は `page_document_updates` にYjsの更新として保存する）
 pub const PAGES_TABLE: &str = r#"
 CREATE TABLE IF NOT EXISTS pages (
     id TEXT PRIMARY KEY NOT NULL,
@@ -84,14 +85,80 @@ CREATE TABLE IF NOT EXISTS decks (
     sync_status TEXT NOT NULL DEFAULT 'pending' CHECK (sync_status IN ('pending', 'synced', 'conflict', 'deleted')),
     synced_at TEXT,
     local_updated_at TEXT NOT NULL,
-    server_updated_at TEXT
+    server_updated_at TEXT,
+    
+    -- 親デッキ（v12 で追加。同期の順序で親が未受信の場合もあるため外部キーにしない）
+    parent_deck_id TEXT,
+    -- 学習オプションのプリセット（v13 で追加。NULL は既定値）
+    config_id TEXT
 );
 
 CREATE INDEX IF NOT EXISTS idx_decks_user ON decks(user_id);
+CREATE INDEX IF NOT EXISTS idx_decks_parent ON decks(parent_deck_id);
 CREATE INDEX IF NOT EXISTS idx_decks_sync_status ON decks(sync_status);
 CREATE INDEX IF NOT EXISTS idx_decks_updated ON decks(local_updated_at);
 "#;
 
+/// Deck Configs テーブルスキーマ
+/// デッキの学習オプションのプリセット（複数のデッキで共有できる）
+/// `learning_steps` は学習ステップ（分）のJSON配列
+pub const DECK_CONFIGS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS deck_configs (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    new_cards_per_day INTEGER NOT NULL DEFAULT 20 CHECK (new_cards_per_day >= 0),
+    max_reviews_per_day INTEGER NOT NULL DEFAULT 200 CHECK (max_reviews_per_day >= 0),
+    learning_steps TEXT NOT NULL DEFAULT '[1,10]',
+    desired_retention REAL NOT NULL DEFAULT 0.9 CHECK (desired_retention > 0 AND desired_retention < 1),
+    maximum_interval INTEGER NOT NULL DEFAULT 36500 CHECK (maximum_interval >= 1),
+    bury_siblings INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    
+    -- 同期メタデータ
+    sync_status TEXT NOT NULL DEFAULT 'pending' CHECK (sync_status IN ('pending', 'synced', 'conflict', 'deleted')),
+    synced_at TEXT,
+    local_updated_at TEXT NOT NULL,
+    server_updated_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_deck_configs_user ON deck_configs(user_id);
+CREATE INDEX IF NOT EXISTS idx_deck_configs_sync_status ON deck_configs(sync_status);
+"#;
+
+/// Card Notes テーブルスキーマ
+/// 複数のカード（兄弟カード）を生成する元のテキスト
+/// 穴埋めカードでは `{{c1::...}}` の番号ごとに1枚のカードを生成する
+/// 表裏のカードでは `source_text`（表）と `back_text`（裏）から順方向・逆方向のカードを生成する
+pub const CARD_NOTES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS card_notes (
+    id TEXT PRIMARY KEY NOT NULL,
+    deck_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    source_text TEXT NOT NULL,
+    extra TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    
+    -- 同期メタデータ
+    sync_status TEXT NOT NULL DEFAULT 'pending' CHECK (sync_status IN ('pending', 'synced', 'conflict', 'deleted')),
+    synced_at TEXT,
+    local_updated_at TEXT NOT NULL,
+    server_updated_at TEXT,
+    
+    -- 裏面の入力欄（v15 で追加。穴埋めでは NULL）
+    back_text TEXT,
+    
+    FOREIGN KEY (deck_id) REFERENCES decks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_card_notes_deck ON card_notes(deck_id);
+CREATE INDEX IF NOT EXISTS idx_card_notes_user ON card_notes(user_id);
+CREATE INDEX IF NOT EXISTS idx_card_notes_sync_status ON card_notes(sync_status);
+"#;
+
 /// Cards テーブルスキーマ
 pub const CARDS_TABLE: &str = r#"
 CREATE TABLE IF NOT EXISTS cards (
@@ -120,10 +187,23 @@ CREATE TABLE IF NOT EXISTS cards (
     local_updated_at TEXT NOT NULL,
     server_updated_at TEXT,
     
+    -- カードの種類と生成元のカードノート（v14 で追加。手動で作成したカードは basic）
+    -- 種類は basic / cloze / forward / reverse
+    card_kind TEXT NOT NULL DEFAULT 'basic',
+    card_note_id TEXT,
+    ordinal INTEGER,
+    
+    -- 出題から外す状態と色のフラグ（v17 で追加）
+    -- buried_until を過ぎる（次の学習日になる）と自動的に出題に戻る
+    is_suspended INTEGER NOT NULL DEFAULT 0,
+    buried_until TEXT,
+    flag TEXT CHECK (flag IN ('red', 'orange', 'green', 'blue', 'pink', 'turquoise', 'purple')),
+    
     FOREIGN KEY (deck_id) REFERENCES decks(id) ON DELETE CASCADE
 );
 
 CREATE INDEX IF NOT EXISTS idx_cards_deck ON cards(deck_id);
+CREATE INDEX IF NOT EXISTS idx_cards_card_note ON cards(card_note_id);
 CREATE INDEX IF NOT EXISTS idx_cards_user ON cards(user_id);
 CREATE INDEX IF NOT EXISTS idx_cards_sync_status ON cards(sync_status);
 CREATE INDEX IF NOT EXISTS idx_cards_next_review ON cards(next_review_at);
@@ -156,13 +236,46 @@ CREATE INDEX IF NOT EXISTS idx_study_goals_sync_status ON study_goals(sync_statu
 CREATE INDEX IF NOT EXISTS idx_study_goals_status ON study_goals(status);
 "#;
 
+/// Questions テーブルスキーマ
+/// カードから作成した問題（クイズモードの学習ログが参照する）
+/// `options` は選択肢、`correct_answers` は正解のJSON配列
+pub const QUESTIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS questions (
+    id TEXT PRIMARY KEY NOT NULL,
+    card_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    question_type TEXT NOT NULL CHECK (question_type IN ('multiple_choice', 'true_false', 'fill_in')),
+    prompt TEXT NOT NULL,
+    options TEXT NOT NULL DEFAULT '[]',
+    correct_answers TEXT NOT NULL,
+    explanation TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    
+    -- 同期メタデータ
+    sync_status TEXT NOT NULL DEFAULT 'pending' CHECK (sync_status IN ('pending', 'synced', 'conflict', 'deleted')),
+    synced_at TEXT,
+    local_updated_at TEXT NOT NULL,
+    server_updated_at TEXT,
+    
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_questions_card ON questions(card_id);
+CREATE INDEX IF NOT EXISTS idx_questions_user ON questions(user_id);
+CREATE INDEX IF NOT EXISTS idx_questions_sync_status ON questions(sync_status);
+"#;
+
 /// Learning Logs テーブルスキーマ
+/// `question_id` の外部キーは v18 で追加（既存の表は作り直す）
 pub const LEARNING_LOGS_TABLE: &str = r#"
 CREATE TABLE IF NOT EXISTS learning_logs (
     id TEXT PRIMARY KEY NOT NULL,
     user_id TEXT NOT NULL,
     card_id TEXT NOT NULL,
     question_id TEXT,
+    -- 問題より先に届いた学習ログの問題ID（問題が届いたら question_id に移す）
+    pending_question_id TEXT,
     answered_at TEXT NOT NULL,
     is_correct INTEGER NOT NULL,
     user_answer TEXT,
@@ -180,11 +293,14 @@ CREATE TABLE IF NOT EXISTS learning_logs (
     local_updated_at TEXT NOT NULL,
     server_updated_at TEXT,
     
-    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+    FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE,
+    FOREIGN KEY (question_id) REFERENCES questions(id) ON DELETE SET NULL
 );
 
 CREATE INDEX IF NOT EXISTS idx_learning_logs_user ON learning_logs(user_id);
 CREATE INDEX IF NOT EXISTS idx_learning_logs_card ON learning_logs(card_id);
+CREATE INDEX IF NOT EXISTS idx_learning_logs_question ON learning_logs(question_id);
+CREATE INDEX IF NOT EXISTS idx_learning_logs_pending_question ON learning_logs(pending_question_id);
 CREATE INDEX IF NOT EXISTS idx_learning_logs_sync_status ON learning_logs(sync_status);
 CREATE INDEX IF NOT EXISTS idx_learning_logs_answered ON learning_logs(answered_at);
 "#;
@@ -244,6 +360,255 @@ CREATE INDEX IF NOT EXISTS idx_user_settings_user ON user_settings(user_id);
 CREATE INDEX IF NOT EXISTS idx_user_settings_sync_status ON user_settings(sync_status);
 "#;
 
+/// Revisions テーブルスキーマ
+/// ノート・ページ・カードの変更前の内容を保存する（ローカル専用のため同期メタデータは持たない）
+pub const REVISIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS revisions (
+    id TEXT PRIMARY KEY NOT NULL,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('note', 'page', 'card')),
+    entity_id TEXT NOT NULL,
+    snapshot TEXT NOT NULL,
+    source TEXT NOT NULL CHECK (source IN ('local_edit', 'server_overwrite', 'restore')),
+    changed_by TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_revisions_entity ON revisions(entity_type, entity_id, created_at);
+"#;
+
+/// Page Document Updates テーブルスキーマ
+/// ページ本文（Yjsドキュメント）の更新ログ。`update_data` は Yjs v1 形式のバイナリ
+pub const PAGE_DOCUMENT_UPDATES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS page_document_updates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    page_id TEXT NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+    update_data BLOB NOT NULL,
+    origin TEXT NOT NULL CHECK (origin IN ('local', 'server')),
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_page_document_updates_page ON page_document_updates(page_id, id);
+"#;
+
+/// Page Documents テーブルスキーマ
+/// ページ本文の同期状態。`synced_update_id` まではサーバーに送信済み
+pub const PAGE_DOCUMENTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS page_documents (
+    page_id TEXT PRIMARY KEY NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+    server_state_vector BLOB,
+    synced_update_id INTEGER NOT NULL DEFAULT 0,
+    synced_at TEXT
+);
+"#;
+
+/// Page Links テーブルスキーマ
+/// ページ本文のリンク。`target_key` は正規化したリンク先のタイトル
+pub const PAGE_LINKS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS page_links (
+    source_page_id TEXT NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    target_key TEXT NOT NULL,
+    target_title TEXT NOT NULL,
+    variant TEXT NOT NULL CHECK (variant IN ('bracket', 'tag')),
+    PRIMARY KEY (source_page_id, position)
+);
+
+CREATE INDEX IF NOT EXISTS idx_page_links_target ON page_links(target_key);
+"#;
+
+/// Note Slug Redirects テーブルスキーマ
+/// スラッグを変更したノートの旧スラッグ（ローカルのみ、同期しない）
+pub const NOTE_SLUG_REDIRECTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS note_slug_redirects (
+    owner_id TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    note_id TEXT NOT NULL REFERENCES notes(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (owner_id, slug)
+);
+
+CREATE INDEX IF NOT EXISTS idx_note_slug_redirects_note ON note_slug_redirects(note_id);
+"#;
+
+/// Note Pages テーブルスキーマ
+/// ノートとページの多対多の対応（`position` はノート内の並び順）。
+/// `pages.note_id` はページを作成したノート（ホーム）として残す
+pub const NOTE_PAGES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS note_pages (
+    id TEXT PRIMARY KEY NOT NULL,
+    note_id TEXT NOT NULL REFERENCES notes(id) ON DELETE CASCADE,
+    page_id TEXT NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    
+    -- 同期メタデータ
+    sync_status TEXT NOT NULL DEFAULT 'pending' CHECK (sync_status IN ('pending', 'synced', 'conflict', 'deleted')),
+    synced_at TEXT,
+    local_updated_at TEXT NOT NULL,
+    server_updated_at TEXT
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_note_pages_unique ON note_pages(note_id, page_id) WHERE sync_status != 'deleted';
+CREATE INDEX IF NOT EXISTS idx_note_pages_note ON note_pages(note_id, position);
+CREATE INDEX IF NOT EXISTS idx_note_pages_page ON note_pages(page_id);
+CREATE INDEX IF NOT EXISTS idx_note_pages_sync_status ON note_pages(sync_status);
+"#;
+
+/// Note Members テーブルスキーマ
+/// ノートに招待したユーザーと権限（`accepted_at` が NULL の間は招待中）
+pub const NOTE_MEMBERS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS note_members (
+    id TEXT PRIMARY KEY NOT NULL,
+    note_id TEXT NOT NULL REFERENCES notes(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL,
+    role TEXT NOT NULL CHECK (role IN ('owner', 'editor', 'viewer')),
+    invited_by TEXT,
+    invited_at TEXT NOT NULL,
+    accepted_at TEXT,
+    updated_at TEXT NOT NULL,
+    
+    -- 同期メタデータ
+    sync_status TEXT NOT NULL DEFAULT 'pending' CHECK (sync_status IN ('pending', 'synced', 'conflict', 'deleted')),
+    synced_at TEXT,
+    local_updated_at TEXT NOT NULL,
+    server_updated_at TEXT
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_note_members_unique ON note_members(note_id, user_id) WHERE sync_status != 'deleted';
+CREATE INDEX IF NOT EXISTS idx_note_members_user ON note_members(user_id);
+CREATE INDEX IF NOT EXISTS idx_note_members_sync_status ON note_members(sync_status);
+"#;
+
+/// Note Share Links テーブルスキーマ
+/// 共有リンク（トークンを知っているユーザーが指定の権限で参加できる）
+pub const NOTE_SHARE_LINKS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS note_share_links (
+    id TEXT PRIMARY KEY NOT NULL,
+    note_id TEXT NOT NULL REFERENCES notes(id) ON DELETE CASCADE,
+    token TEXT NOT NULL UNIQUE,
+    role TEXT NOT NULL CHECK (role IN ('editor', 'viewer')),
+    created_by TEXT NOT NULL,
+    expires_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    
+    -- 同期メタデータ
+    sync_status TEXT NOT NULL DEFAULT 'pending' CHECK (sync_status IN ('pending', 'synced', 'conflict', 'deleted')),
+    synced_at TEXT,
+    local_updated_at TEXT NOT NULL,
+    server_updated_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_note_share_links_note ON note_share_links(note_id);
+CREATE INDEX IF NOT EXISTS idx_note_share_links_sync_status ON note_share_links(sync_status);
+"#;
+
+/// Tags テーブルスキーマ
+/// 名前は `親::子` の形で階層を表す（削除済みを除きユーザー内で一意）
+pub const TAGS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS tags (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    
+    -- 同期メタデータ
+    sync_status TEXT NOT NULL DEFAULT 'pending' CHECK (sync_status IN ('pending', 'synced', 'conflict', 'deleted')),
+    synced_at TEXT,
+    local_updated_at TEXT NOT NULL,
+    server_updated_at TEXT
+);
+
+-- 削除済み（同期待ち）のタグと同じ名前でも作り直せるよう、削除済みは一意性の対象外
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tags_user_name ON tags(user_id, name) WHERE sync_status != 'deleted';
+CREATE INDEX IF NOT EXISTS idx_tags_user ON tags(user_id);
+CREATE INDEX IF NOT EXISTS idx_tags_sync_status ON tags(sync_status);
+"#;
+
+/// Entity Tags テーブルスキーマ
+/// ノート・ページ・カードとタグの対応
+pub const ENTITY_TAGS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS entity_tags (
+    id TEXT PRIMARY KEY NOT NULL,
+    tag_id TEXT NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('note', 'page', 'card')),
+    entity_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    
+    -- 同期メタデータ
+    sync_status TEXT NOT NULL DEFAULT 'pending' CHECK (sync_status IN ('pending', 'synced', 'conflict', 'deleted')),
+    synced_at TEXT,
+    local_updated_at TEXT NOT NULL,
+    server_updated_at TEXT
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_entity_tags_unique ON entity_tags(tag_id, entity_type, entity_id) WHERE sync_status != 'deleted';
+
+CREATE INDEX IF NOT EXISTS idx_entity_tags_entity ON entity_tags(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_entity_tags_sync_status ON entity_tags(sync_status);
+"#;
+
+/// Media テーブルスキーマ
+/// ローカルに保存したメディア。ファイルは `media/<先頭2文字>/<hash>` に置く
+pub const MEDIA_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS media (
+    hash TEXT PRIMARY KEY NOT NULL,
+    mime_type TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    source_url TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_source_url ON media(source_url);
+"#;
+
+/// Media Refs テーブルスキーマ
+/// カード・ページのフィールドからメディアへの参照（参照数の集計とGCに使う）
+pub const MEDIA_REFS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS media_refs (
+    hash TEXT NOT NULL REFERENCES media(hash) ON DELETE CASCADE,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('card', 'page')),
+    entity_id TEXT NOT NULL,
+    field TEXT NOT NULL,
+    PRIMARY KEY (entity_type, entity_id, field)
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_refs_hash ON media_refs(hash);
+"#;
+
+/// Media Variants テーブルスキーマ
+/// 元のメディアから生成した派生メディア（サムネイルなど）のキャッシュ。
+/// `source_hash` は取り込んでいない画像のハッシュのこともあるため外部キーにしない
+pub const MEDIA_VARIANTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS media_variants (
+    source_hash TEXT NOT NULL,
+    variant TEXT NOT NULL,
+    hash TEXT NOT NULL REFERENCES media(hash) ON DELETE CASCADE,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (source_hash, variant)
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_variants_hash ON media_variants(hash);
+"#;
+
+/// 操作ジャーナルテーブル
+/// 元に戻す / やり直すのため、操作前後の行の内容をセッション単位で保存する
+pub const OPERATION_JOURNAL_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS _operation_journal (
+    id TEXT PRIMARY KEY NOT NULL,
+    session_id TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    changes TEXT NOT NULL,
+    state TEXT NOT NULL DEFAULT 'done' CHECK (state IN ('done', 'undone')),
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_operation_journal_session ON _operation_journal(session_id, state);
+"#;
+
 /// メタデータテーブル（DBバージョン管理用）
 pub const METADATA_TABLE: &str = r#"
 CREATE TABLE IF NOT EXISTS _metadata (
@@ -259,16 +624,33 @@ pub fn get_all_schemas() -> Vec<&'static str> {
         NOTES_TABLE,
         PAGES_TABLE,
         DECKS_TABLE,
+        DECK_CONFIGS_TABLE,
+        CARD_NOTES_TABLE,
         CARDS_TABLE,
+        QUESTIONS_TABLE,
         STUDY_GOALS_TABLE,
         LEARNING_LOGS_TABLE,
         MILESTONES_TABLE,
         USER_SETTINGS_TABLE,
+        REVISIONS_TABLE,
+        OPERATION_JOURNAL_TABLE,
+        PAGE_DOCUMENT_UPDATES_TABLE,
+        PAGE_DOCUMENTS_TABLE,
+        PAGE_LINKS_TABLE,
+        MEDIA_TABLE,
+        MEDIA_REFS_TABLE,
+        MEDIA_VARIANTS_TABLE,
+        TAGS_TABLE,
+        ENTITY_TAGS_TABLE,
+        NOTE_PAGES_TABLE,
+        NOTE_SLUG_REDIRECTS_TABLE,
+        NOTE_MEMBERS_TABLE,
+        NOTE_SHARE_LINKS_TABLE,
     ]
 }
 
 /// テーブル名の一覧
-pub const TABLE_NAMES: [&str; 8] = [
+pub const TABLE_NAMES: [&str; 16] = [
     "notes",
     "pages",
     "decks",
@@ -277,5 +659,13 @@ pub const TABLE_NAMES: [&str; 8] = [
     "learning_logs",
     "milestones",
     "user_settings",
+    "tags",
+    "entity_tags",
+    "note_pages",
+    "note_members",
+    "note_share_links",
+    "deck_configs",
+    "card_notes",
+    "questions",
 ];
 
@@ -0,0 +1,275 @@
+//! サインアウト時のローカルデータ処理
+//!
+//! サインアウト後にローカルDBの内容が読めてしまわないよう、
+//! プロファイルを閉じたうえで「保持」「消去」「封印」を選べるようにする。
+//!
+//! 封印はアプリ内のロックで、DBファイルは暗号化しない。ディスク上のファイルは
+//! 他のツールからそのまま読めるため、端末から読めなくするには消去を選ぶ。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/profiles_commands.rs
+//!
+//! Dependencies:
+//!   ├─ src-tauri/src/db/profiles.rs
+//!   ├─ src-tauri/src/db/page_documents.rs (未送信のページ本文)
+//!   └─ src-tauri/src/auth.rs (再認証の検証)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::page_documents::pending_page_document_ids;
+use super::profiles::{self, LocalProfile};
+use super::{schema, DbError, LocalDB};
+use crate::auth::VerifiedUser;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// サインアウト方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignOutMode {
+    /// データを残したままプロファイルを閉じる
+    Keep,
+    /// ユーザーのデータとWALを安全に消去する
+    Wipe,
+    /// 再認証するまでアプリで開けないようにする（ファイルは暗号化しない）
+    Seal,
+}
+
+/// サインアウト結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignOutResult {
+    pub mode: SignOutMode,
+    pub user_id: Option<String>,
+    /// 処理を実行したか（未同期データがあり消去を見送った場合は false）
+    pub completed: bool,
+    /// 未同期（pending / conflict / 送信前の削除 deleted、未送信のページ本文）の変更件数
+    pub unsynced_changes: usize,
+    /// テーブル名ごとの未同期件数
+    pub unsynced_by_table: BTreeMap<String, usize>,
+    /// サインアウト後もDBファイルがディスク上に読める形で残るか（保持・封印は true）
+    pub readable_on_disk: bool,
+}
+
+/// テーブルごとの未同期件数を数える
+///
+/// 論理削除（deleted）はサーバーに送るまで残っているため、未同期として数える。
+/// ページ本文は行の `sync_status` ではなく未送信のYjs更新で判断し、
+/// 更新が残っているページの数を `page_documents` として数える。
+fn count_unsynced(conn: &Connection) -> Result<BTreeMap<String, usize>, DbError> {
+    let mut counts = BTreeMap::new();
+    for table in schema::TABLE_NAMES.iter() {
+        let count: i64 = conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM {} WHERE sync_status IN ('pending', 'conflict', 'deleted')",
+                table
+            ),
+            [],
+            |row| row.get(0),
+        )?;
+        if count > 0 {
+            counts.insert(table.to_string(), count as usize);
+        }
+    }
+
+    let pending_documents = pending_page_document_ids(conn)?.len();
+    if pending_documents > 0 {
+        counts.insert("page_documents".to_string(), pending_documents);
+    }
+    Ok(counts)
+}
+
+/// 全テーブルの行を上書き消去し、VACUUMとWALの切り詰めを行う
+fn wipe_connection(conn: &Connection) -> Result<(), DbError> {
+    // 削除した領域をゼロで上書きする
+    conn.execute_batch("PRAGMA secure_delete=ON; PRAGMA foreign_keys=OFF;")?;
+
+    let tables = {
+        let mut stmt = conn.prepare(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name != '_metadata'",
+        )?;
+        let tables = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        tables
+    };
+
+    conn.execute_batch("BEGIN")?;
+    for table in &tables {
+        conn.execute(&format!("DELETE FROM {}", table), [])?;
+    }
+    conn.execute_batch("COMMIT")?;
+
+    conn.execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE); PRAGMA foreign_keys=ON;")?;
+    Ok(())
+}
+
+impl LocalDB {
+    /// 現在のプロファイルの未同期件数を取得
+    pub fn count_unsynced_changes(&self) -> Result<BTreeMap<String, usize>, DbError> {
+        let conn = self.connection()?;
+        count_unsynced(&conn)
+    }
+
+    /// サインアウトする
+    ///
+    /// `Wipe` は未同期の変更が残っている場合、`force` が無ければ何もせずに件数だけを返す。
+    /// いずれのモードでも完了後はプロファイル未選択（共有DB）に戻る。
+    pub fn sign_out(&self, mode: SignOutMode, force: bool) -> Result<SignOutResult, DbError> {
+        let user_id = self.active_profile()?;
+        let unsynced_by_table = self.count_unsynced_changes()?;
+        let mut result = SignOutResult {
+            mode,
+            user_id: user_id.clone(),
+            completed: false,
+            unsynced_changes: unsynced_by_table.values().sum(),
+            unsynced_by_table,
+            readable_on_disk: true,
+        };
+
+        if mode == SignOutMode::Wipe && result.unsynced_changes > 0 && !force {
+            log::warn!(
+                "Refusing to wipe local data with {} unsynced changes",
+                result.unsynced_changes
+            );
+            return Ok(result);
+        }
+
+        match (mode, user_id.as_deref()) {
+            (SignOutMode::Keep, _) => self.close_profile()?,
+            (SignOutMode::Wipe, Some(user_id)) => {
+                wipe_connection(&*self.connection()?)?;
+                self.close_profile()?;
+                std::fs::remove_dir_all(profiles::profile_dir(&self.data_dir, user_id))
+                    .map_err(|e| DbError::IoError(e.to_string()))?;
+            }
+            // プロファイル未選択時は共有DBの中身を消去する
+            (SignOutMode::Wipe, None) => wipe_connection(&*self.connection()?)?,
+            (SignOutMode::Seal, Some(user_id)) => {
+                self.close_profile()?;
+                profiles::set_sealed(&self.data_dir, user_id, true)?;
+            }
+            (SignOutMode::Seal, None) => {
+                return Err(DbError::ValidationError(
+                    "No active profile to seal".to_string(),
+                ))
+            }
+        }
+
+        result.completed = true;
+        result.readable_on_disk = mode != SignOutMode::Wipe;
+        log::info!("Signed out ({:?}) from {:?}", mode, result.user_id);
+
+        Ok(result)
+    }
+
+    /// 封印されたプロファイルを開く
+    ///
+    /// アクセストークンを検証したユーザー自身のプロファイルのみ開ける。
+    pub fn unseal_profile(&self, user: &VerifiedUser) -> Result<LocalProfile, DbError> {
+        let user_id = user.user_id();
+        profiles::validate_user_id(user_id)?;
+        profiles::set_sealed(&self.data_dir, user_id, false)?;
+        self.switch_profile(user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{fixtures, LocalDeck};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_wipe_refuses_with_pending_rows_unless_forced() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.switch_profile("user-1").unwrap();
+        db.insert_deck(&LocalDeck {
+            sync_status: "pending".to_string(),
+            ..fixtures::deck("deck-1")
+        })
+        .unwrap();
+        db.insert_deck(&fixtures::deck("deck-2")).unwrap();
+        db.insert_deck(&LocalDeck {
+            sync_status: "deleted".to_string(),
+            ..fixtures::deck("deck-3")
+        })
+        .unwrap();
+
+        let result = db.sign_out(SignOutMode::Wipe, false).unwrap();
+        assert!(!result.completed);
+        assert_eq!(result.unsynced_changes, 2);
+        assert_eq!(db.active_profile().unwrap().as_deref(), Some("user-1"));
+
+        let result = db.sign_out(SignOutMode::Wipe, true).unwrap();
+        assert!(result.completed);
+        assert!(!result.readable_on_disk);
+        assert_eq!(db.active_profile().unwrap(), None);
+        assert!(db.list_local_profiles().unwrap().is_empty());
+
+        // 再ログインしても以前のデータは残っていない
+        db.switch_profile("user-1").unwrap();
+        assert!(db.get_deck_by_id("deck-2").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_unsent_page_bodies_count_as_unsynced() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        let now = chrono::Utc::now().to_rfc3339();
+        db.insert_page(&fixtures::page("page-1")).unwrap();
+        let update_id = {
+            let conn = db.connection().unwrap();
+            conn.execute(
+                "INSERT INTO page_document_updates (page_id, update_data, origin, created_at)
+                 VALUES ('page-1', x'00', 'local', ?1)",
+                [&now],
+            )
+            .unwrap();
+            conn.last_insert_rowid()
+        };
+
+        let result = db.sign_out(SignOutMode::Wipe, false).unwrap();
+        assert!(!result.completed);
+        assert_eq!(result.unsynced_by_table.get("page_documents"), Some(&1));
+
+        // 送信済みになれば数えない
+        db.connection()
+            .unwrap()
+            .execute(
+                "INSERT INTO page_documents (page_id, synced_update_id) VALUES ('page-1', ?1)",
+                [update_id],
+            )
+            .unwrap();
+        assert!(db.count_unsynced_changes().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sealed_profile_requires_unseal() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        db.switch_profile("user-1").unwrap();
+        db.insert_deck(&fixtures::deck("deck-1")).unwrap();
+
+        let result = db.sign_out(SignOutMode::Seal, false).unwrap();
+        assert!(result.completed);
+        // 封印はアプリ内のロックだけで、ファイルはそのまま残る
+        assert!(result.readable_on_disk);
+        assert!(matches!(
+            db.switch_profile("user-1"),
+            Err(DbError::ProfileSealed(_))
+        ));
+
+        // 再起動しても自動では開かれない
+        let reopened = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(reopened.active_profile().unwrap(), None);
+
+        db.unseal_profile(&VerifiedUser::for_test("user-1"))
+            .unwrap();
+        assert!(db.get_deck_by_id("deck-1").unwrap().is_some());
+    }
+}
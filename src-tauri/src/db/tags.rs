@@ -0,0 +1,797 @@
+//! タグ
+//!
+//! ノート・ページ・カードにタグを付ける。タグ名は `親::子` の形で階層を表し、
+//! 親のタグで検索すると子孫のタグが付いたものも取得できる。
+//! `tags`・`entity_tags` は他のテーブルと同じ同期メタデータを持ち、
+//! 削除は論理削除（`sync_status = 'deleted'`）としてサーバーに伝える。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/db/mod.rs
+//!   └─ src-tauri/src/commands/tags_commands.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use super::{DbError, LocalDB, LocalEntityTag, LocalTag};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+/// 階層の区切り
+pub const TAG_SEPARATOR: &str = "::";
+
+const TAG_COLUMNS: &str = "id, user_id, name, created_at, updated_at,
+    sync_status, synced_at, local_updated_at, server_updated_at";
+
+const ENTITY_TAG_COLUMNS: &str = "id, tag_id, entity_type, entity_id, user_id, created_at,
+    sync_status, synced_at, local_updated_at, server_updated_at";
+
+/// タグを付けられるデータの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagEntity {
+    Note,
+    Page,
+    Card,
+}
+
+impl TagEntity {
+    /// `entity_tags.entity_type` に保存する値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TagEntity::Note => "note",
+            TagEntity::Page => "page",
+            TagEntity::Card => "card",
+        }
+    }
+
+    /// 保存された値から復元
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "note" => Some(TagEntity::Note),
+            "page" => Some(TagEntity::Page),
+            "card" => Some(TagEntity::Card),
+            _ => None,
+        }
+    }
+
+    /// 所有者を取得するクエリ
+    fn owner_query(&self) -> &'static str {
+        match self {
+            TagEntity::Note => {
+                "SELECT owner_id FROM notes WHERE id = ?1 AND sync_status != 'deleted'"
+            }
+            TagEntity::Page => {
+                "SELECT user_id FROM pages WHERE id = ?1 AND sync_status != 'deleted'"
+            }
+            TagEntity::Card => {
+                "SELECT user_id FROM cards WHERE id = ?1 AND sync_status != 'deleted'"
+            }
+        }
+    }
+}
+
+/// タグと使用数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagSummary {
+    pub tag: LocalTag,
+    /// このタグが付いているデータの数（子孫のタグは含まない）
+    pub usage_count: usize,
+}
+
+/// タグが付いたデータ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaggedEntity {
+    pub entity_type: TagEntity,
+    pub entity_id: String,
+    /// 検索に一致したタグ名（子孫のタグを含む）
+    pub tag_names: Vec<String>,
+}
+
+/// タグの統合結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagMergeResult {
+    pub target: LocalTag,
+    /// 統合先に付け替えたタグ付けの数
+    pub moved_entities: usize,
+}
+
+/// タグ名を正規化する
+///
+/// 先頭の `#` を除き、NFCに揃え、階層ごとに前後の空白を除いて連続する空白を1つにする。
+/// 空の階層（`a::::b` など）は不正とする。
+pub fn normalize_tag_name(raw: &str) -> Result<String, DbError> {
+    let name = raw.trim().trim_start_matches('#').nfc().collect::<String>();
+    let segments = name
+        .split(TAG_SEPARATOR)
+        .map(|segment| segment.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        return Err(DbError::ValidationError(format!(
+            "Invalid tag name: {:?}",
+            raw
+        )));
+    }
+    Ok(segments.join(TAG_SEPARATOR))
+}
+
+/// `name` の子孫のタグ名の接頭辞
+fn descendant_prefix(name: &str) -> String {
+    format!("{}{}", name, TAG_SEPARATOR)
+}
+
+fn find_tag(conn: &Connection, id: &str) -> Result<LocalTag, DbError> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM tags WHERE id = ?1 AND sync_status != 'deleted'",
+            TAG_COLUMNS
+        ),
+        [id],
+        LocalTag::from_row,
+    )
+    .optional()?
+    .ok_or_else(|| DbError::NotFound(format!("Tag {}", id)))
+}
+
+fn find_tag_by_name(
+    conn: &Connection,
+    user_id: &str,
+    name: &str,
+) -> Result<Option<LocalTag>, DbError> {
+    let tag = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM tags
+                 WHERE user_id = ?1 AND name = ?2 AND sync_status != 'deleted'",
+                TAG_COLUMNS
+            ),
+            [user_id, name],
+            LocalTag::from_row,
+        )
+        .optional()?;
+    Ok(tag)
+}
+
+/// 子孫のタグを取得（名前順）
+fn descendant_tags(conn: &Connection, tag: &LocalTag) -> Result<Vec<LocalTag>, DbError> {
+    let prefix = descendant_prefix(&tag.name);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM tags
+         WHERE user_id = ?1 AND sync_status != 'deleted'
+           AND substr(name, 1, length(?2)) = ?2
+         ORDER BY name",
+        TAG_COLUMNS
+    ))?;
+    let tags = stmt
+        .query_map([tag.user_id.as_str(), prefix.as_str()], LocalTag::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(tags)
+}
+
+fn set_tag_name(conn: &Connection, id: &str, name: &str, now: &str) -> Result<(), DbError> {
+    conn.execute(
+        "UPDATE tags SET name = ?1, updated_at = ?2, local_updated_at = ?2, sync_status = 'pending'
+         WHERE id = ?3",
+        rusqlite::params![name, now, id],
+    )?;
+    Ok(())
+}
+
+fn delete_tag_row(conn: &Connection, id: &str, now: &str) -> Result<(), DbError> {
+    conn.execute(
+        "UPDATE entity_tags SET sync_status = 'deleted', local_updated_at = ?1
+         WHERE tag_id = ?2 AND sync_status != 'deleted'",
+        rusqlite::params![now, id],
+    )?;
+    conn.execute(
+        "UPDATE tags SET sync_status = 'deleted', local_updated_at = ?1 WHERE id = ?2",
+        rusqlite::params![now, id],
+    )?;
+    Ok(())
+}
+
+/// タグ付けを別のタグへ付け替える（統合先に既にあるものは削除する）
+fn move_entity_tags(
+    conn: &Connection,
+    from_tag_id: &str,
+    to_tag_id: &str,
+    now: &str,
+) -> Result<usize, DbError> {
+    conn.execute(
+        r#"
+        UPDATE entity_tags SET sync_status = 'deleted', local_updated_at = ?1
+        WHERE tag_id = ?2 AND sync_status != 'deleted'
+          AND EXISTS (
+              SELECT 1 FROM entity_tags t
+              WHERE t.tag_id = ?3 AND t.sync_status != 'deleted'
+                AND t.entity_type = entity_tags.entity_type
+                AND t.entity_id = entity_tags.entity_id
+          )
+        "#,
+        rusqlite::params![now, from_tag_id, to_tag_id],
+    )?;
+    let moved = conn.execute(
+        "UPDATE entity_tags SET tag_id = ?1, local_updated_at = ?2, sync_status = 'pending'
+         WHERE tag_id = ?3 AND sync_status != 'deleted'",
+        rusqlite::params![to_tag_id, now, from_tag_id],
+    )?;
+    Ok(moved)
+}
+
+impl LocalDB {
+    /// ユーザーのタグを使用数とともに取得（名前順）
+    pub fn get_tags(&self, user_id: &str) -> Result<Vec<TagSummary>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT {},
+                   (SELECT COUNT(*) FROM entity_tags et
+                    WHERE et.tag_id = tags.id AND et.sync_status != 'deleted')
+            FROM tags
+            WHERE user_id = ?1 AND sync_status != 'deleted'
+            ORDER BY name
+            "#,
+            TAG_COLUMNS
+        ))?;
+        let tags = stmt
+            .query_map([user_id], |row| {
+                Ok(TagSummary {
+                    tag: LocalTag::from_row(row)?,
+                    usage_count: row.get::<_, i64>(9)? as usize,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(tags)
+    }
+
+    /// データに付いているタグを取得（名前順）
+    pub fn get_entity_tags(
+        &self,
+        entity_type: TagEntity,
+        entity_id: &str,
+    ) -> Result<Vec<LocalTag>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT t.id, t.user_id, t.name, t.created_at, t.updated_at,
+                   t.sync_status, t.synced_at, t.local_updated_at, t.server_updated_at
+            FROM entity_tags et
+            JOIN tags t ON t.id = et.tag_id
+            WHERE et.entity_type = ?1 AND et.entity_id = ?2
+              AND et.sync_status != 'deleted' AND t.sync_status != 'deleted'
+            ORDER BY t.name
+            "#,
+        )?;
+        let tags = stmt
+            .query_map([entity_type.as_str(), entity_id], LocalTag::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(tags)
+    }
+
+    /// データにタグを付ける（タグが無ければ作成する）
+    pub fn add_tag(
+        &self,
+        user_id: &str,
+        entity_type: TagEntity,
+        entity_id: &str,
+        name: &str,
+    ) -> Result<LocalTag, DbError> {
+        let name = normalize_tag_name(name)?;
+
+        self.with_journal("add_tag", &[], |tx| {
+            let owner: Option<String> = tx
+                .query_row(entity_type.owner_query(), [entity_id], |row| row.get(0))
+                .optional()?;
+            if owner.as_deref() != Some(user_id) {
+                return Err(DbError::NotFound(format!(
+                    "{} {}",
+                    entity_type.as_str(),
+                    entity_id
+                )));
+            }
+
+            let existing = find_tag_by_name(tx, user_id, &name)?;
+            if let Some(tag) = &existing {
+                let already_tagged = tx
+                    .query_row(
+                        "SELECT 1 FROM entity_tags
+                         WHERE tag_id = ?1 AND entity_type = ?2 AND entity_id = ?3
+                           AND sync_status != 'deleted'",
+                        [tag.id.as_str(), entity_type.as_str(), entity_id],
+                        |_| Ok(()),
+                    )
+                    .optional()?
+                    .is_some();
+                if already_tagged {
+                    return Ok(tag.clone());
+                }
+            }
+
+            let now = chrono::Utc::now().to_rfc3339();
+            let tag = existing.unwrap_or_else(|| LocalTag {
+                id: uuid::Uuid::new_v4().to_string(),
+                user_id: user_id.to_string(),
+                name,
+                created_at: now.clone(),
+                updated_at: now.clone(),
+                sync_status: "pending".to_string(),
+                synced_at: None,
+                local_updated_at: now.clone(),
+                server_updated_at: None,
+            });
+            let entity_tag_id = uuid::Uuid::new_v4().to_string();
+            tx.track("tags", &tag.id)?;
+            tx.track("entity_tags", &entity_tag_id)?;
+
+            tx.execute(
+                r#"
+                INSERT OR IGNORE INTO tags (
+                    id, user_id, name, created_at, updated_at,
+                    sync_status, synced_at, local_updated_at, server_updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                "#,
+                rusqlite::params![
+                    tag.id,
+                    tag.user_id,
+                    tag.name,
+                    tag.created_at,
+                    tag.updated_at,
+                    tag.sync_status,
+                    tag.synced_at,
+                    tag.local_updated_at,
+                    tag.server_updated_at,
+                ],
+            )?;
+            tx.execute(
+                r#"
+                INSERT INTO entity_tags (
+                    id, tag_id, entity_type, entity_id, user_id, created_at,
+                    sync_status, local_updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'pending', ?6)
+                "#,
+                rusqlite::params![
+                    entity_tag_id,
+                    tag.id,
+                    entity_type.as_str(),
+                    entity_id,
+                    user_id,
+                    now,
+                ],
+            )?;
+            Ok(tag)
+        })
+    }
+
+    /// データからタグを外す
+    pub fn remove_tag(
+        &self,
+        entity_type: TagEntity,
+        entity_id: &str,
+        tag_id: &str,
+    ) -> Result<bool, DbError> {
+        self.with_journal("remove_tag", &[], |tx| {
+            let entity_tag_id: Option<String> = tx
+                .query_row(
+                    "SELECT id FROM entity_tags
+                     WHERE tag_id = ?1 AND entity_type = ?2 AND entity_id = ?3
+                       AND sync_status != 'deleted'",
+                    [tag_id, entity_type.as_str(), entity_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let Some(entity_tag_id) = entity_tag_id else {
+                return Ok(false);
+            };
+
+            tx.track("entity_tags", &entity_tag_id)?;
+            let now = chrono::Utc::now().to_rfc3339();
+            tx.execute(
+                "UPDATE entity_tags SET sync_status = 'deleted', local_updated_at = ?1
+                 WHERE id = ?2",
+                rusqlite::params![now, entity_tag_id],
+            )?;
+            Ok(true)
+        })
+    }
+
+    /// タグ名を変更する（子孫のタグも `新しい名前::…` に変わる）
+    ///
+    /// 変更後の名前のタグが既にある場合は `DbError::Conflict` を返す（統合は `merge_tags`）。
+    pub fn rename_tag(&self, id: &str, new_name: &str) -> Result<Vec<LocalTag>, DbError> {
+        let new_name = normalize_tag_name(new_name)?;
+
+        self.with_journal("rename_tag", &[("tags", id)], |tx| {
+            let tag = find_tag(tx, id)?;
+            if tag.name == new_name {
+                return Ok(vec![tag]);
+            }
+            if new_name.starts_with(&descendant_prefix(&tag.name)) {
+                return Err(DbError::ValidationError(format!(
+                    "Cannot move tag \"{}\" under itself",
+                    tag.name
+                )));
+            }
+            let descendants = descendant_tags(tx, &tag)?;
+
+            let renamed = std::iter::once(new_name.clone()).chain(
+                descendants
+                    .iter()
+                    .map(|child| format!("{}{}", new_name, &child.name[tag.name.len()..])),
+            );
+            for name in renamed {
+                if find_tag_by_name(tx, &tag.user_id, &name)?.is_some() {
+                    return Err(DbError::Conflict(format!(
+                        "Tag \"{}\" already exists",
+                        name
+                    )));
+                }
+            }
+
+            let now = chrono::Utc::now().to_rfc3339();
+            set_tag_name(tx, &tag.id, &new_name, &now)?;
+            for child in &descendants {
+                tx.track("tags", &child.id)?;
+                let name = format!("{}{}", new_name, &child.name[tag.name.len()..]);
+                set_tag_name(tx, &child.id, &name, &now)?;
+            }
+
+            std::iter::once(&tag)
+                .chain(&descendants)
+                .map(|tag| find_tag(tx, &tag.id))
+                .collect()
+        })
+    }
+
+    /// タグを統合する
+    ///
+    /// `source_id` のタグ付けを `target_id` へ付け替え、`source_id` は削除する。
+    /// 子孫のタグは統合先の下へ移し、同じ名前のタグがあればそれに統合する。
+    pub fn merge_tags(&self, source_id: &str, target_id: &str) -> Result<TagMergeResult, DbError> {
+        if source_id == target_id {
+            return Err(DbError::ValidationError(
+                "Cannot merge a tag into itself".to_string(),
+            ));
+        }
+
+        self.with_journal(
+            "merge_tags",
+            &[("tags", source_id), ("tags", target_id)],
+            |tx| {
+                let source = find_tag(tx, source_id)?;
+                let target = find_tag(tx, target_id)?;
+                if source.user_id != target.user_id {
+                    return Err(DbError::ValidationError(
+                        "Cannot merge tags of different users".to_string(),
+                    ));
+                }
+                if target.name.starts_with(&descendant_prefix(&source.name)) {
+                    return Err(DbError::ValidationError(format!(
+                        "Cannot merge tag \"{}\" into its descendant",
+                        source.name
+                    )));
+                }
+                let descendants = descendant_tags(tx, &source)?;
+
+                // 統合元と子孫のタグ付けは付け替えか削除になる
+                {
+                    let mut stmt = tx.prepare(
+                        "SELECT et.id FROM entity_tags et
+                         JOIN tags t ON t.id = et.tag_id
+                         WHERE t.user_id = ?1 AND et.sync_status != 'deleted'
+                           AND (t.id = ?2 OR substr(t.name, 1, length(?3)) = ?3)",
+                    )?;
+                    let entity_tag_ids = stmt
+                        .query_map(
+                            [
+                                source.user_id.as_str(),
+                                source.id.as_str(),
+                                descendant_prefix(&source.name).as_str(),
+                            ],
+                            |row| row.get::<_, String>(0),
+                        )?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    for entity_tag_id in &entity_tag_ids {
+                        tx.track("entity_tags", entity_tag_id)?;
+                    }
+                }
+                for child in &descendants {
+                    tx.track("tags", &child.id)?;
+                }
+
+                let now = chrono::Utc::now().to_rfc3339();
+
+                let moved_entities = move_entity_tags(tx, &source.id, &target.id, &now)?;
+                delete_tag_row(tx, &source.id, &now)?;
+
+                for child in &descendants {
+                    let name = format!("{}{}", target.name, &child.name[source.name.len()..]);
+                    match find_tag_by_name(tx, &target.user_id, &name)? {
+                        Some(existing) => {
+                            tx.track("tags", &existing.id)?;
+                            move_entity_tags(tx, &child.id, &existing.id, &now)?;
+                            delete_tag_row(tx, &child.id, &now)?;
+                        }
+                        None => set_tag_name(tx, &child.id, &name, &now)?,
+                    }
+                }
+
+                Ok(TagMergeResult {
+                    target: find_tag(tx, &target.id)?,
+                    moved_entities,
+                })
+            },
+        )
+    }
+
+    /// タグを削除する（タグ付けも外す。子孫のタグは残す）
+    pub fn delete_tag(&self, id: &str) -> Result<bool, DbError> {
+        self.with_journal("delete_tag", &[("tags", id)], |tx| {
+            if find_tag(tx, id).is_err() {
+                return Ok(false);
+            }
+            {
+                let mut stmt = tx.prepare(
+                    "SELECT id FROM entity_tags WHERE tag_id = ?1 AND sync_status != 'deleted'",
+                )?;
+                let entity_tag_ids = stmt
+                    .query_map([id], |row| row.get::<_, String>(0))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                for entity_tag_id in &entity_tag_ids {
+                    tx.track("entity_tags", entity_tag_id)?;
+                }
+            }
+
+            let now = chrono::Utc::now().to_rfc3339();
+            delete_tag_row(tx, id, &now)?;
+            Ok(true)
+        })
+    }
+
+    /// タグが付いたデータを取得（タグ付けの古い順）
+    ///
+    /// `include_descendants` が true なら `名前::…` のタグが付いたものも含める。
+    /// 削除されたノート・ページ・カードは除く。
+    pub fn get_entities_by_tag(
+        &self,
+        user_id: &str,
+        name: &str,
+        include_descendants: bool,
+    ) -> Result<Vec<TaggedEntity>, DbError> {
+        let name = normalize_tag_name(name)?;
+        let prefix = if include_descendants {
+            descendant_prefix(&name)
+        } else {
+            // 子孫を含めない場合は一致しない接頭辞にする
+            String::new()
+        };
+
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT et.entity_type, et.entity_id, t.name
+            FROM entity_tags et
+            JOIN tags t ON t.id = et.tag_id
+            WHERE t.user_id = ?1 AND t.sync_status != 'deleted' AND et.sync_status != 'deleted'
+              AND (t.name = ?2 OR (?3 != '' AND substr(t.name, 1, length(?3)) = ?3))
+              AND CASE et.entity_type
+                  WHEN 'note' THEN EXISTS (
+                      SELECT 1 FROM notes n WHERE n.id = et.entity_id AND n.sync_status != 'deleted'
+                  )
+                  WHEN 'page' THEN EXISTS (
+                      SELECT 1 FROM pages p WHERE p.id = et.entity_id AND p.sync_status != 'deleted'
+                  )
+                  WHEN 'card' THEN EXISTS (
+                      SELECT 1 FROM cards c WHERE c.id = et.entity_id AND c.sync_status != 'deleted'
+                  )
+              END
+            ORDER BY et.created_at, t.name
+            "#,
+        )?;
+        let rows = stmt
+            .query_map([user_id, name.as_str(), prefix.as_str()], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut entities: Vec<TaggedEntity> = Vec::new();
+        for (entity_type, entity_id, tag_name) in rows {
+            let Some(entity_type) = TagEntity::parse(&entity_type) else {
+                continue;
+            };
+            match entities
+                .iter_mut()
+                .find(|e| e.entity_type == entity_type && e.entity_id == entity_id)
+            {
+                Some(entity) => entity.tag_names.push(tag_name),
+                None => entities.push(TaggedEntity {
+                    entity_type,
+                    entity_id,
+                    tag_names: vec![tag_name],
+                }),
+            }
+        }
+        Ok(entities)
+    }
+
+    /// 同期待ちのタグを取得（削除したタグも含む）
+    pub fn get_pending_sync_tags(&self) -> Result<Vec<LocalTag>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM tags WHERE sync_status IN ('pending', 'deleted')",
+            TAG_COLUMNS
+        ))?;
+        let tags = stmt
+            .query_map([], LocalTag::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(tags)
+    }
+
+    /// 同期待ちのタグ付けを取得（外したタグ付けも含む）
+    pub fn get_pending_sync_entity_tags(&self) -> Result<Vec<LocalEntityTag>, DbError> {
+        let conn = self.connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM entity_tags WHERE sync_status IN ('pending', 'deleted')",
+            ENTITY_TAG_COLUMNS
+        ))?;
+        let entity_tags = stmt
+            .query_map([], LocalEntityTag::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entity_tags)
+    }
+
+    /// タグを同期済みにマーク（削除したタグは行を消す）
+    pub fn mark_tag_synced(&self, id: &str, server_updated_at: &str) -> Result<(), DbError> {
+        let conn = self.connection()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "DELETE FROM tags WHERE id = ?1 AND sync_status = 'deleted'",
+            [id],
+        )?;
+        conn.execute(
+            "UPDATE tags SET sync_status = 'synced', synced_at = ?1, server_updated_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![now, server_updated_at, id],
+        )?;
+        Ok(())
+    }
+
+    /// タグ付けを同期済みにマーク（外したタグ付けは行を消す）
+    pub fn mark_entity_tag_synced(&self, id: &str, server_updated_at: &str) -> Result<(), DbError> {
+        let conn = self.connection()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "DELETE FROM entity_tags WHERE id = ?1 AND sync_status = 'deleted'",
+            [id],
+        )?;
+        conn.execute(
+            "UPDATE entity_tags SET sync_status = 'synced', synced_at = ?1, server_updated_at = ?2
+             WHERE id = ?3",
+            rusqlite::params![now, server_updated_at, id],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::fixtures;
+    use tempfile::TempDir;
+
+    fn open_db() -> (TempDir, LocalDB) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = LocalDB::open(temp_dir.path().to_path_buf()).unwrap();
+        for id in ["a", "b", "c"] {
+            db.insert_page(&fixtures::page(id)).unwrap();
+        }
+        (temp_dir, db)
+    }
+
+    #[test]
+    fn test_normalize_tag_name() {
+        assert_eq!(
+            normalize_tag_name(" #英語 :: verb  phrase ").unwrap(),
+            "英語::verb phrase"
+        );
+        assert!(normalize_tag_name("a::::b").is_err());
+        assert!(normalize_tag_name("#").is_err());
+    }
+
+    #[test]
+    fn test_hierarchical_tags_and_rename() {
+        let (_temp_dir, db) = open_db();
+        let english = db.add_tag("user-1", TagEntity::Page, "a", "英語").unwrap();
+        let verb = db
+            .add_tag("user-1", TagEntity::Page, "b", "英語::verb")
+            .unwrap();
+        db.add_tag("user-1", TagEntity::Page, "b", "英語").unwrap();
+        db.add_tag("user-1", TagEntity::Page, "c", "数学").unwrap();
+        assert!(db
+            .add_tag("user-1", TagEntity::Card, "missing", "英語")
+            .is_err());
+
+        let entities = db.get_entities_by_tag("user-1", "英語", true).unwrap();
+        assert_eq!(entities.len(), 2);
+        assert_eq!(entities[1].entity_id, "b");
+        assert_eq!(entities[1].tag_names.len(), 2);
+        assert_eq!(
+            db.get_entities_by_tag("user-1", "英語", false)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            db.get_entities_by_tag("user-1", "英語::verb", false)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let renamed = db.rename_tag(&english.id, "English").unwrap();
+        assert_eq!(renamed.len(), 2);
+        assert_eq!(renamed[1].id, verb.id);
+        assert_eq!(renamed[1].name, "English::verb");
+        assert!(matches!(
+            db.rename_tag(&verb.id, "数学"),
+            Err(DbError::Conflict(_))
+        ));
+
+        assert!(db.remove_tag(TagEntity::Page, "a", &english.id).unwrap());
+        let summaries = db.get_tags("user-1").unwrap();
+        let english = summaries.iter().find(|s| s.tag.name == "English").unwrap();
+        assert_eq!(english.usage_count, 1);
+    }
+
+    #[test]
+    fn test_merge_tags_moves_entities_and_descendants() {
+        let (_temp_dir, db) = open_db();
+        let source = db.add_tag("user-1", TagEntity::Page, "a", "lang").unwrap();
+        db.add_tag("user-1", TagEntity::Page, "b", "lang").unwrap();
+        db.add_tag("user-1", TagEntity::Page, "c", "lang::rust")
+            .unwrap();
+        let target = db
+            .add_tag("user-1", TagEntity::Page, "a", "language")
+            .unwrap();
+
+        let result = db.merge_tags(&source.id, &target.id).unwrap();
+        assert_eq!(result.moved_entities, 1);
+
+        let names = db
+            .get_tags("user-1")
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.tag.name, s.usage_count))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                ("language".to_string(), 2),
+                ("language::rust".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            db.get_entities_by_tag("user-1", "language", true)
+                .unwrap()
+                .len(),
+            3
+        );
+
+        // 削除とタグ付けの解除も同期待ちに含まれる
+        let pending = db.get_pending_sync_tags().unwrap();
+        assert!(pending
+            .iter()
+            .any(|tag| tag.id == source.id && tag.sync_status == "deleted"));
+        db.mark_tag_synced(&source.id, "2025-01-01T00:00:00Z")
+            .unwrap();
+        assert!(db
+            .get_pending_sync_tags()
+            .unwrap()
+            .iter()
+            .all(|tag| tag.id != source.id));
+    }
+}
@@ -4,6 +4,10 @@
 //!
 //! Children (Modules):
 //!   ├─ db/ - ローカルデータベース
+//!   ├─ content/ - ページ本文の変換（Markdown・プレーンテキスト）
+//!   ├─ auth.rs - 再認証の検証
+//!   ├─ media_protocol.rs - メディアのURIスキーム
+//!   ├─ thumbnail.rs - サムネイル生成
 //!   └─ commands/ - Tauri コマンド
 //!
 //! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
@@ -11,10 +15,14 @@
 
 use tauri::{AppHandle, Emitter, Manager};
 
+pub mod auth;
 pub mod commands;
+pub mod content;
 pub mod db;
+pub mod media_protocol;
+pub mod thumbnail;
 
-use db::LocalDB;
+use db::{LocalDB, DB_CHANGED_EVENT, MEDIA_SCHEME};
 
 #[tauri::command]
 async fn start_oauth_server(app: AppHandle) -> Result<u16, String> {
@@ -31,6 +39,14 @@ pub fn run() {
         .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .register_uri_scheme_protocol(MEDIA_SCHEME, |ctx, request| {
+            match ctx.app_handle().try_state::<LocalDB>() {
+                Some(db) => media_protocol::handle_media_request(&db, &request),
+                None => media_protocol::error_response(
+                    tauri::http::StatusCode::SERVICE_UNAVAILABLE,
+                ),
+            }
+        })
         .setup(|app| {
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -43,6 +59,19 @@ pub fn run() {
             // ローカルDB初期化
             let db = LocalDB::new(app.handle())
                 .map_err(|e| format!("Failed to initialize LocalDB: {}", e))?;
+
+            // コミットされた変更を全ウィンドウへ通知
+            let handle = app.handle().clone();
+            db.start_change_events(move |event| {
+                let _ = handle.emit(DB_CHANGED_EVENT, event);
+            })
+            .map_err(|e| format!("Failed to start DB change events: {}", e))?;
+
+            // 参照されなくなったメディアを削除
+            if let Err(e) = db.collect_media_garbage() {
+                log::warn!("Failed to collect media garbage: {}", e);
+            }
+
             app.manage(db);
             log::info!("LocalDB initialized successfully");
 
@@ -58,6 +87,9 @@ pub fn run() {
             commands::update_note,
             commands::delete_note,
             commands::hard_delete_note,
+            commands::suggest_note_slug,
+            commands::change_note_slug,
+            commands::resolve_note_slug,
             commands::get_pending_sync_notes,
             commands::get_deleted_notes,
             commands::mark_note_synced,
@@ -68,22 +100,87 @@ pub fn run() {
             commands::get_page,
             commands::create_page,
             commands::update_page,
+            commands::rename_page,
             commands::delete_page,
             commands::get_pending_sync_pages,
             commands::mark_page_synced,
+            // Note Pages
+            commands::get_note_pages,
+            commands::get_notes_by_page,
+            commands::link_page_to_note,
+            commands::unlink_page_from_note,
+            commands::reorder_note_pages,
+            commands::get_pending_sync_note_pages,
+            commands::mark_note_page_synced,
+            // Note Members
+            commands::get_note_members,
+            commands::get_shared_notes,
+            commands::get_note_role,
+            commands::invite_note_member,
+            commands::accept_note_invitation,
+            commands::remove_note_member,
+            commands::create_note_share_link,
+            commands::get_note_share_links,
+            commands::revoke_note_share_link,
+            commands::join_note_by_share_link,
+            commands::get_pending_sync_note_members,
+            commands::get_pending_sync_note_share_links,
+            commands::mark_note_member_synced,
+            commands::mark_note_share_link_synced,
             // Decks
             commands::get_decks,
             commands::get_deck,
             commands::create_deck,
             commands::delete_deck,
+            commands::get_deck_tree,
+            commands::move_deck,
+            commands::get_study_cards,
             commands::get_pending_sync_decks,
+            // Deck Configs
+            commands::get_deck_configs,
+            commands::get_deck_config,
+            commands::create_deck_config,
+            commands::update_deck_config,
+            commands::delete_deck_config,
+            commands::set_deck_config,
+            commands::get_deck_study_options,
+            commands::get_pending_sync_deck_configs,
+            commands::get_deleted_deck_configs,
+            commands::mark_deck_config_synced,
             // Cards
             commands::get_cards,
             commands::get_card,
             commands::create_card,
+            commands::update_card,
             commands::delete_card,
             commands::get_due_cards,
+            commands::review_card,
+            commands::suspend_cards,
+            commands::unsuspend_cards,
+            commands::bury_cards,
+            commands::unbury_cards,
+            commands::set_card_flags,
             commands::get_pending_sync_cards,
+            // Card Notes
+            commands::get_card_note,
+            commands::get_card_notes,
+            commands::get_card_note_cards,
+            commands::create_card_note,
+            commands::update_card_note,
+            commands::delete_card_note,
+            commands::get_pending_sync_card_notes,
+            commands::mark_card_note_synced,
+            // Questions
+            commands::get_questions,
+            commands::get_question,
+            commands::create_question,
+            commands::update_question,
+            commands::delete_question,
+            commands::get_question_stats,
+            commands::generate_quiz_question,
+            commands::grade_typing_answer,
+            commands::get_pending_sync_questions,
+            commands::mark_question_synced,
             // Study Goals
             commands::get_study_goals,
             commands::get_study_goal,
@@ -105,6 +202,69 @@ pub fn run() {
             commands::get_user_settings,
             commands::upsert_user_settings,
             commands::get_pending_sync_user_settings,
+            // Profiles
+            commands::switch_profile,
+            commands::list_local_profiles,
+            commands::get_active_profile,
+            commands::enter_guest_mode,
+            commands::claim_guest_data,
+            commands::sign_out,
+            commands::unseal_profile,
+            // Change Events
+            commands::subscribe_query,
+            commands::unsubscribe_query,
+            // History
+            commands::get_history,
+            commands::restore_revision,
+            // Undo / Redo
+            commands::undo,
+            commands::redo,
+            commands::get_undo_state,
+            // Page Documents
+            commands::get_page_document,
+            commands::get_page_state_vector,
+            commands::apply_page_update,
+            commands::get_page_document_diff,
+            commands::mark_page_document_synced,
+            commands::get_pending_page_documents,
+            commands::get_page_document_stats,
+            commands::compact_page_documents,
+            // Page Links
+            commands::get_backlinks,
+            commands::get_outgoing_links,
+            commands::get_unresolved_links,
+            commands::get_two_hop_links,
+            commands::rebuild_page_links,
+            // Media
+            commands::download_media,
+            commands::ingest_media,
+            commands::attach_media,
+            commands::detach_media,
+            commands::cache_entity_media,
+            commands::get_entity_media,
+            commands::resolve_media_url,
+            commands::collect_media_garbage,
+            // Tags
+            commands::get_tags,
+            commands::get_entity_tags,
+            commands::add_tag,
+            commands::remove_tag,
+            commands::rename_tag,
+            commands::merge_tags,
+            commands::delete_tag,
+            commands::get_entities_by_tag,
+            commands::get_pending_sync_tags,
+            commands::get_pending_sync_entity_tags,
+            commands::mark_tag_synced,
+            commands::mark_entity_tag_synced,
+            // Thumbnails
+            commands::generate_thumbnail_media,
+            commands::update_page_thumbnail_from_content,
+            commands::update_page_thumbnail_from_image,
+            // Content
+            commands::convert_tiptap_to_markdown,
+            commands::convert_tiptap_to_plain_text,
+            commands::convert_markdown_to_tiptap,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
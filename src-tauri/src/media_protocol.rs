@@ -0,0 +1,126 @@
+//! メディアのURIスキーム（`media://localhost/<ハッシュ>`）
+//!
+//! ローカルメディアストアのファイルをWebViewに配信する。
+//! Windows・Androidでは `http://media.localhost/<ハッシュ>` として届くが、パスは同じ。
+//! 内容はハッシュで決まるため、キャッシュは無期限にする。
+//! 音声のシークのため、単一範囲の Range リクエストに対応する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   └─ src-tauri/src/lib.rs
+//!
+//! Dependencies:
+//!   └─ src-tauri/src/db/media.rs (LocalDB)
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use crate::db::LocalDB;
+use tauri::http::{header, Request, Response, StatusCode};
+
+/// 配信するファイルのキャッシュ設定
+const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// 本文なしのエラーレスポンス
+pub fn error_response(status: StatusCode) -> Response<Vec<u8>> {
+    let mut response = Response::new(Vec::new());
+    *response.status_mut() = status;
+    response
+}
+
+/// `Range: bytes=start-end` を解釈する（終端を含む範囲）
+///
+/// 複数範囲は扱わず None を返す（全体を返す）。
+/// 範囲外なら `Some(Err(()))`
+fn parse_range(value: &str, len: usize) -> Option<Result<(usize, usize), ()>> {
+    let spec = value.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        // 末尾からの長さ指定（bytes=-500）
+        ("", suffix) => {
+            let suffix = suffix.parse::<usize>().ok()?;
+            if suffix == 0 {
+                return Some(Err(()));
+            }
+            (len.saturating_sub(suffix), len.saturating_sub(1))
+        }
+        (start, "") => (start.parse::<usize>().ok()?, len.saturating_sub(1)),
+        (start, end) => {
+            let end = end.parse::<usize>().ok()?;
+            (start.parse::<usize>().ok()?, end.min(len.saturating_sub(1)))
+        }
+    };
+    if len == 0 || start > end || start >= len {
+        return Some(Err(()));
+    }
+    Some(Ok((start, end)))
+}
+
+/// メディアのリクエストを処理する
+pub fn handle_media_request(db: &LocalDB, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let hash = request.uri().path().trim_matches('/');
+    let (media, bytes) = match db.read_media(hash) {
+        Ok(Some(found)) => found,
+        Ok(None) => return error_response(StatusCode::NOT_FOUND),
+        Err(err) => {
+            log::error!("Failed to read media {}: {}", hash, err);
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let len = bytes.len();
+    let range = request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_range(value, len));
+
+    let builder = Response::builder()
+        .header(header::CONTENT_TYPE, &media.mime_type)
+        .header(header::CACHE_CONTROL, CACHE_CONTROL)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+
+    let response = match range {
+        None => builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_LENGTH, len)
+            .body(bytes),
+        Some(Ok((start, end))) => builder
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", start, end, len),
+            )
+            .header(header::CONTENT_LENGTH, end - start + 1)
+            .body(bytes[start..=end].to_vec()),
+        Some(Err(())) => builder
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{}", len))
+            .body(Vec::new()),
+    };
+
+    response.unwrap_or_else(|err| {
+        log::error!("Failed to build media response: {}", err);
+        error_response(StatusCode::INTERNAL_SERVER_ERROR)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-9", 100), Some(Ok((0, 9))));
+        assert_eq!(parse_range("bytes=90-", 100), Some(Ok((90, 99))));
+        assert_eq!(parse_range("bytes=-10", 100), Some(Ok((90, 99))));
+        assert_eq!(parse_range("bytes=50-200", 100), Some(Ok((50, 99))));
+        assert_eq!(parse_range("bytes=100-", 100), Some(Err(())));
+        assert_eq!(parse_range("bytes=0-1,5-6", 100), None);
+        assert_eq!(parse_range("items=0-1", 100), None);
+    }
+}
@@ -0,0 +1,219 @@
+//! サムネイル生成
+//!
+//! 画像をデコードして縮小し、WebP または PNG で書き出す。
+//! 画素だけを書き出し直すため、EXIFなどのメタデータ（撮影位置など）は残らない。
+//! EXIFの向き情報は縮小前に画素へ反映する。
+//!
+//! DEPENDENCY MAP:
+//!
+//! Parents (Files that import this module):
+//!   ├─ src-tauri/src/lib.rs
+//!   └─ src-tauri/src/commands/thumbnail_commands.rs
+//!
+//! Spec: docs/03_plans/tauri-migration/20251109_01_implementation-plan.md
+
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use thiserror::Error;
+
+/// サムネイルの長辺のデフォルト（px）
+pub const DEFAULT_THUMBNAIL_SIZE: u32 = 320;
+
+/// サムネイルの長辺の上限（px）
+pub const MAX_THUMBNAIL_SIZE: u32 = 1024;
+
+/// デコードする画像の縦横の上限（px）
+const MAX_SOURCE_DIMENSION: u32 = 16384;
+
+/// サムネイル生成のエラー
+#[derive(Debug, Error)]
+pub enum ThumbnailError {
+    /// 画像として読み込めない
+    #[error("Unsupported image: {0}")]
+    Unsupported(String),
+
+    /// 画像が大きすぎる
+    #[error("Image is too large: {0}x{1}")]
+    TooLarge(u32, u32),
+
+    /// サイズの指定が不正
+    #[error("Invalid thumbnail size: {0}")]
+    InvalidSize(u32),
+
+    /// デコード・エンコードのエラー
+    #[error("Image error: {0}")]
+    ImageError(#[from] image::ImageError),
+}
+
+/// サムネイルの形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbnailFormat {
+    /// WebP（可逆圧縮）
+    #[default]
+    Webp,
+    Png,
+}
+
+impl ThumbnailFormat {
+    /// 形式の名前
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThumbnailFormat::Webp => "webp",
+            ThumbnailFormat::Png => "png",
+        }
+    }
+
+    /// MIMEタイプ
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ThumbnailFormat::Webp => "image/webp",
+            ThumbnailFormat::Png => "image/png",
+        }
+    }
+
+    fn image_format(&self) -> ImageFormat {
+        match self {
+            ThumbnailFormat::Webp => ImageFormat::WebP,
+            ThumbnailFormat::Png => ImageFormat::Png,
+        }
+    }
+}
+
+fn default_thumbnail_size() -> u32 {
+    DEFAULT_THUMBNAIL_SIZE
+}
+
+/// サムネイル生成のオプション
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThumbnailOptions {
+    /// 長辺の最大サイズ（px）。小さい画像は拡大しない
+    #[serde(default = "default_thumbnail_size")]
+    pub max_size: u32,
+    #[serde(default)]
+    pub format: ThumbnailFormat,
+}
+
+impl Default for ThumbnailOptions {
+    fn default() -> Self {
+        ThumbnailOptions {
+            max_size: DEFAULT_THUMBNAIL_SIZE,
+            format: ThumbnailFormat::default(),
+        }
+    }
+}
+
+impl ThumbnailOptions {
+    /// メディアストアの派生メディアのキー
+    pub fn variant_key(&self) -> String {
+        format!("thumbnail:{}:{}", self.format.as_str(), self.max_size)
+    }
+}
+
+/// 生成したサムネイル
+#[derive(Debug, Clone)]
+pub struct Thumbnail {
+    pub bytes: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    pub mime_type: &'static str,
+}
+
+/// 画像からサムネイルを生成する
+pub fn generate_thumbnail(
+    bytes: &[u8],
+    options: &ThumbnailOptions,
+) -> Result<Thumbnail, ThumbnailError> {
+    if options.max_size == 0 || options.max_size > MAX_THUMBNAIL_SIZE {
+        return Err(ThumbnailError::InvalidSize(options.max_size));
+    }
+
+    let reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| ThumbnailError::Unsupported(e.to_string()))?;
+    if reader.format().is_none() {
+        return Err(ThumbnailError::Unsupported("unknown format".to_string()));
+    }
+
+    let mut decoder = reader.into_decoder()?;
+    let (width, height) = decoder.dimensions();
+    if width > MAX_SOURCE_DIMENSION || height > MAX_SOURCE_DIMENSION {
+        return Err(ThumbnailError::TooLarge(width, height));
+    }
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+
+    if image.width() > options.max_size || image.height() > options.max_size {
+        image = image.thumbnail(options.max_size, options.max_size);
+    }
+
+    // WebPのエンコーダーは8bitのRGB・RGBAのみ対応
+    let image = if image.color().has_alpha() {
+        DynamicImage::from(image.to_rgba8())
+    } else {
+        DynamicImage::from(image.to_rgb8())
+    };
+
+    let mut encoded = Vec::new();
+    image.write_to(
+        &mut Cursor::new(&mut encoded),
+        options.format.image_format(),
+    )?;
+
+    Ok(Thumbnail {
+        bytes: encoded,
+        width: image.width(),
+        height: image.height(),
+        mime_type: options.format.mime_type(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::new_rgba8(width, height)
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_thumbnail_keeps_aspect_ratio() {
+        let source = encode_png(800, 400);
+        let thumbnail = generate_thumbnail(&source, &ThumbnailOptions::default()).unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (320, 160));
+        assert_eq!(thumbnail.mime_type, "image/webp");
+
+        let decoded = image::load_from_memory(&thumbnail.bytes).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (320, 160));
+
+        // 小さい画像は拡大しない
+        let options = ThumbnailOptions {
+            max_size: 320,
+            format: ThumbnailFormat::Png,
+        };
+        let thumbnail = generate_thumbnail(&encode_png(100, 50), &options).unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (100, 50));
+    }
+
+    #[test]
+    fn test_thumbnail_rejects_invalid_input() {
+        assert!(matches!(
+            generate_thumbnail(b"not an image", &ThumbnailOptions::default()),
+            Err(ThumbnailError::Unsupported(_))
+        ));
+        let options = ThumbnailOptions {
+            max_size: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            generate_thumbnail(&encode_png(10, 10), &options),
+            Err(ThumbnailError::InvalidSize(0))
+        ));
+    }
+}